@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A union-find structure over arbitrary keys, with path compression and union by size.
+///
+/// Keys are added with [`Self::insert`], or on the fly by [`Self::union`]. Lookups that only read
+/// still take `&mut self`, as they compress the paths they walk.
+///
+/// # Example
+/// ```rust
+/// use advent_of_code_common::algorithms::disjoint_set::DisjointSet;
+/// let mut set: DisjointSet<char> = "abcde".chars().collect();
+/// set.union(&'a', &'b');
+/// set.union(&'d', &'b');
+/// assert!(set.connected(&'a', &'d'));
+/// assert_eq!(set.component_size(&'d'), 3);
+/// assert_eq!(set.component_count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisjointSet<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl<K> Default for DisjointSet<K> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+            parents: Vec::new(),
+            sizes: Vec::new(),
+            components: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> DisjointSet<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `key` as a component of its own, if it's not there yet. Returns its index.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+        let index = self.keys.len();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        self.parents.push(index);
+        self.sizes.push(1);
+        self.components += 1;
+        index
+    }
+
+    /// The number of keys
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// The number of disjoint components
+    #[must_use]
+    pub const fn component_count(&self) -> usize {
+        self.components
+    }
+
+    fn root(&mut self, mut index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        while self.parents[index] != root {
+            index = std::mem::replace(&mut self.parents[index], root);
+        }
+        root
+    }
+
+    /// The representative of the component of `key`, the same for all the keys in it
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        let root = self.root(index);
+        Some(&self.keys[root])
+    }
+
+    /// Merge the components of `a` and `b`, adding them if needed. Returns `false` if they were
+    /// already in the same component.
+    pub fn union(&mut self, a: &K, b: &K) -> bool {
+        let a = self.insert(a.clone());
+        let b = self.insert(b.clone());
+        let (a, b) = (self.root(a), self.root(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.sizes[a] < self.sizes[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.components -= 1;
+        true
+    }
+
+    /// Whether `a` and `b` are in the same component (`false` if either is missing)
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.root(a) == self.root(b),
+            _ => false,
+        }
+    }
+
+    /// The number of keys in the component of `key`, 0 if it's missing
+    pub fn component_size(&mut self, key: &K) -> usize {
+        let Some(&index) = self.indices.get(key) else {
+            return 0;
+        };
+        let root = self.root(index);
+        self.sizes[root]
+    }
+
+    /// The size of each component, largest first
+    #[must_use]
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&index| self.parents[index] == index)
+            .map(|index| self.sizes[index])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// The keys of each component, in the order they were inserted
+    pub fn components(&mut self) -> Vec<Vec<&K>> {
+        let mut components: Vec<Vec<&K>> = Vec::with_capacity(self.components);
+        let mut positions = HashMap::new();
+        let roots: Vec<usize> = (0..self.len()).map(|index| self.root(index)).collect();
+        for (key, root) in self.keys.iter().zip(roots) {
+            let position = *positions.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[position].push(key);
+        }
+        components
+    }
+}
+
+impl<K: Hash + Eq + Clone> FromIterator<K> for DisjointSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for key in iter {
+            set.insert(key);
+        }
+        set
+    }
+}
+
+/// A minimum spanning forest, by Kruskal's algorithm: the edges that join two components, by
+/// increasing weight (edges of the same weight keep their order).
+///
+/// The `nodes` only matter to know when everything is connected, so that the last edge is the
+/// one that joins the last two components.
+pub fn kruskal<K, W>(
+    nodes: impl IntoIterator<Item = K>,
+    edges: impl IntoIterator<Item = (K, K, W)>,
+) -> Vec<(K, K, W)>
+where
+    K: Hash + Eq + Clone,
+    W: Ord,
+{
+    let mut set: DisjointSet<K> = nodes.into_iter().collect();
+    let known = set.len();
+    let mut edges: Vec<(K, K, W)> = edges.into_iter().collect();
+    edges.sort_by(|a, b| a.2.cmp(&b.2));
+    let mut tree = Vec::new();
+    for (a, b, weight) in edges {
+        if set.union(&a, &b) {
+            tree.push((a, b, weight));
+            if set.component_count() == 1 && set.len() == known {
+                break;
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components() {
+        let mut set: DisjointSet<u32> = (0..10).collect();
+        for (a, b) in [(0, 1), (2, 3), (1, 3), (5, 6), (3, 0)] {
+            set.union(&a, &b);
+        }
+        assert!(set.union(&10, &11));
+        assert!(!set.union(&11, &10));
+        assert_eq!(set.len(), 12);
+        assert_eq!(set.component_count(), 7);
+        assert_eq!(set.component_sizes(), [4, 2, 2, 1, 1, 1, 1]);
+        let root = set.find(&2).copied();
+        assert_eq!(set.find(&1).copied(), root);
+        assert_ne!(set.find(&5).copied(), root);
+        assert_eq!(set.find(&12), None);
+        assert!(!set.connected(&4, &12));
+        let components: Vec<Vec<u32>> = set
+            .components()
+            .into_iter()
+            .map(|c| c.into_iter().copied().collect())
+            .collect();
+        assert_eq!(components[0], [0, 1, 2, 3]);
+        assert_eq!(components[2], [5, 6]);
+        assert_eq!(components.last(), Some(&vec![10, 11]));
+    }
+
+    #[test]
+    fn minimum_spanning_tree() {
+        let edges = [
+            ('a', 'b', 4),
+            ('b', 'c', 1),
+            ('a', 'c', 2),
+            ('c', 'd', 7),
+            ('b', 'd', 5),
+            ('d', 'e', 3),
+        ];
+        let tree = kruskal("abcde".chars(), edges);
+        assert_eq!(
+            tree,
+            [('b', 'c', 1), ('a', 'c', 2), ('d', 'e', 3), ('b', 'd', 5)]
+        );
+        // Without the nodes, it goes on until the edges run out
+        assert_eq!(kruskal([], edges).len(), 4);
+    }
+}
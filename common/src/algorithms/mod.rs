@@ -1,2 +1,3 @@
 pub mod dijkstra;
+pub mod disjoint_set;
 pub mod graph;
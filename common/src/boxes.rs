@@ -0,0 +1,288 @@
+//! Axis-aligned boxes in any number of dimensions, and sets of them.
+//!
+//! Rectangles (2D), cuboids (3D) and hyper-rectangles (4D, like the `xmas` ratings of 2023
+//! day 19) all share the same algebra: intersection, subtraction into disjoint pieces and volume.
+use std::ops::RangeInclusive;
+
+/// An axis-aligned box with inclusive integer bounds on each of its `D` axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoxN<const D: usize> {
+    pub min: [i64; D],
+    pub max: [i64; D],
+}
+
+impl<const D: usize> BoxN<D> {
+    /// A box from `min` to `max` (inclusive), or `None` if it would be empty
+    #[must_use]
+    pub fn new(min: [i64; D], max: [i64; D]) -> Option<Self> {
+        (0..D)
+            .all(|i| min[i] <= max[i])
+            .then_some(Self { min, max })
+    }
+
+    /// A box from one range per axis, or `None` if any range is empty
+    #[must_use]
+    pub fn from_ranges(ranges: [RangeInclusive<i64>; D]) -> Option<Self> {
+        Self::new(ranges.clone().map(|r| *r.start()), ranges.map(|r| *r.end()))
+    }
+
+    #[must_use]
+    pub const fn range(&self, axis: usize) -> RangeInclusive<i64> {
+        self.min[axis]..=self.max[axis]
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &[i64; D]) -> bool {
+        (0..D).all(|i| self.range(i).contains(&point[i]))
+    }
+
+    /// The number of integer points in the box
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        (0..D)
+            .map(|i| self.max[i].abs_diff(self.min[i]) + 1)
+            .product()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut min = self.min;
+        let mut max = self.max;
+        for i in 0..D {
+            min[i] = min[i].max(other.min[i]);
+            max[i] = max[i].min(other.max[i]);
+        }
+        Self::new(min, max)
+    }
+
+    /// Splits the box along `axis` into the parts below `at` and from `at` up
+    #[must_use]
+    pub fn split(&self, axis: usize, at: i64) -> (Option<Self>, Option<Self>) {
+        let mut below = *self;
+        let mut above = *self;
+        below.max[axis] = below.max[axis].min(at - 1);
+        above.min[axis] = above.min[axis].max(at);
+        (
+            Self::new(below.min, below.max),
+            Self::new(above.min, above.max),
+        )
+    }
+
+    /// The parts of this box that are not in `other`, as at most `2 * D` disjoint boxes
+    #[must_use]
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.intersection(other).is_none() {
+            return vec![*self];
+        }
+        // Peel off the slabs on either side of `other`, one axis at a time
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        for axis in 0..D {
+            if let (Some(below), Some(above)) = rest.split(axis, other.min[axis]) {
+                pieces.push(below);
+                rest = above;
+            }
+            if let (Some(inside), Some(above)) = rest.split(axis, other.max[axis] + 1) {
+                pieces.push(above);
+                rest = inside;
+            }
+        }
+        pieces
+    }
+}
+
+/// A set of points stored as disjoint boxes, with lights-style on / off / toggle operations
+///
+/// ```
+/// use advent_of_code_common::boxes::{BoxN, BoxSet};
+///
+/// let mut cubes = BoxSet::new();
+/// cubes.insert(BoxN::new([10, 10, 10], [12, 12, 12]).unwrap());
+/// cubes.insert(BoxN::new([11, 11, 11], [13, 13, 13]).unwrap());
+/// cubes.remove(&BoxN::new([9, 9, 9], [11, 11, 11]).unwrap());
+/// cubes.insert(BoxN::new([10, 10, 10], [10, 10, 10]).unwrap());
+/// assert_eq!(cubes.volume(), 39);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BoxSet<const D: usize> {
+    boxes: Vec<BoxN<D>>,
+}
+
+impl<const D: usize> BoxSet<D> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { boxes: Vec::new() }
+    }
+
+    /// The disjoint boxes that make up the set, in no particular order
+    #[must_use]
+    pub fn boxes(&self) -> &[BoxN<D>] {
+        &self.boxes
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// Turns on every point in `b`
+    pub fn insert(&mut self, b: BoxN<D>) {
+        self.remove(&b);
+        self.boxes.push(b);
+    }
+
+    /// Turns off every point in `b`
+    pub fn remove(&mut self, b: &BoxN<D>) {
+        self.boxes = self
+            .boxes
+            .iter()
+            .flat_map(|existing| existing.subtract(b))
+            .collect();
+    }
+
+    /// Turns on the points in `b` that were off, and turns off the ones that were on
+    pub fn toggle(&mut self, b: BoxN<D>) {
+        let mut added = vec![b];
+        for existing in &self.boxes {
+            added = added.iter().flat_map(|a| a.subtract(existing)).collect();
+        }
+        self.remove(&b);
+        self.boxes.extend(added);
+    }
+
+    /// Keeps only the points inside `b`
+    pub fn retain_within(&mut self, b: &BoxN<D>) {
+        self.boxes = self
+            .boxes
+            .iter()
+            .filter_map(|existing| existing.intersection(b))
+            .collect();
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &[i64; D]) -> bool {
+        self.boxes.iter().any(|b| b.contains(point))
+    }
+
+    /// The total number of points in the set
+    #[must_use]
+    pub fn volume(&self) -> u64 {
+        self.boxes.iter().map(BoxN::volume).sum()
+    }
+}
+
+impl<const D: usize> FromIterator<BoxN<D>> for BoxSet<D> {
+    fn from_iter<I: IntoIterator<Item = BoxN<D>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for b in iter {
+            set.insert(b);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    fn point(x: usize, y: usize) -> [i64; 2] {
+        [x, y].map(|v| i64::try_from(v).unwrap())
+    }
+
+    fn cube(min: i64, max: i64) -> BoxN<3> {
+        BoxN::new([min; 3], [max; 3]).unwrap()
+    }
+
+    #[test]
+    fn test_volume_and_intersection() {
+        let a = BoxN::new([0, 0], [9, 4]).unwrap();
+        let b = BoxN::new([5, -5], [20, 2]).unwrap();
+        assert_eq!(a.volume(), 50);
+        assert_eq!(a.intersection(&b), BoxN::new([5, 0], [9, 2]));
+        assert_eq!(a.intersection(&BoxN::new([10, 0], [11, 0]).unwrap()), None);
+        assert_eq!(BoxN::new([1], [0]), None);
+        assert_eq!(
+            BoxN::from_ranges([1..=4, 0..=0, -2..=2, 7..=8])
+                .unwrap()
+                .volume(),
+            40
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let a = cube(0, 9);
+        let (below, above) = a.split(1, 3);
+        assert_eq!(below, BoxN::new([0, 0, 0], [9, 2, 9]));
+        assert_eq!(above, BoxN::new([0, 3, 0], [9, 9, 9]));
+        assert_eq!(a.split(0, 0), (None, Some(a)));
+        assert_eq!(a.split(2, 10), (Some(a), None));
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = cube(0, 9);
+        for b in [
+            cube(3, 5),
+            cube(-5, 4),
+            cube(0, 9),
+            cube(20, 30),
+            BoxN::new([2, -1, 4], [3, 12, 4]).unwrap(),
+        ] {
+            let pieces = a.subtract(&b);
+            assert!(pieces.len() <= 6);
+            let overlap = a.intersection(&b).map_or(0, |i| i.volume());
+            assert_eq!(
+                pieces.iter().map(BoxN::volume).sum::<u64>(),
+                a.volume() - overlap
+            );
+            for (i, p) in pieces.iter().enumerate() {
+                assert_eq!(p.intersection(&b), None, "{p:?} overlaps {b:?}");
+                assert_eq!(p.intersection(&a), Some(*p));
+                for q in &pieces[i + 1..] {
+                    assert_eq!(p.intersection(q), None, "{p:?} overlaps {q:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_box_set() {
+        let boxes = [
+            (true, BoxN::new([0, 0], [5, 5]).unwrap()),
+            (true, BoxN::new([3, 3], [8, 4]).unwrap()),
+            (false, BoxN::new([1, -1], [2, 9]).unwrap()),
+            (true, BoxN::new([2, 2], [2, 2]).unwrap()),
+        ];
+        let mut set = BoxSet::new();
+        let mut grid = [[false; 10]; 10];
+        for (on, b) in boxes {
+            if on {
+                set.insert(b);
+            } else {
+                set.remove(&b);
+            }
+            for (x, y) in iproduct!(0..10_usize, 0..10_usize) {
+                if b.contains(&point(x, y)) {
+                    grid[x][y] = on;
+                }
+            }
+        }
+        let toggled = BoxN::new([4, 0], [9, 9]).unwrap();
+        set.toggle(toggled);
+        for (x, y) in iproduct!(0..10_usize, 0..10_usize) {
+            if toggled.contains(&point(x, y)) {
+                grid[x][y] ^= true;
+            }
+        }
+        for (x, y) in iproduct!(0..10_usize, 0..10_usize) {
+            assert_eq!(set.contains(&point(x, y)), grid[x][y], "{x},{y}");
+        }
+        let count = grid.iter().flatten().filter(|&&on| on).count();
+        assert_eq!(set.volume(), u64::try_from(count).unwrap());
+        set.retain_within(&BoxN::new([0, 0], [4, 9]).unwrap());
+        let count = grid[..5].iter().flatten().filter(|&&on| on).count();
+        assert_eq!(set.volume(), u64::try_from(count).unwrap());
+    }
+}
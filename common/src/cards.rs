@@ -46,7 +46,7 @@ impl Display for CardValue {
 }
 
 impl TryFrom<char> for CardValue {
-    type Error = &'static str;
+    type Error = crate::Error;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
@@ -64,7 +64,10 @@ impl TryFrom<char> for CardValue {
             'K' => Ok(Self::King),
             'Q' => Ok(Self::Queen),
             'A' => Ok(Self::Ace),
-            _ => Err("Invalid card value"),
+            _ => Err(crate::Error::invalid_char(
+                c,
+                "a card value (2-9, T, J, Q, K, A or *)",
+            )),
         }
     }
 }
@@ -89,7 +92,7 @@ impl Display for CardSuit {
 }
 
 impl TryFrom<char> for CardSuit {
-    type Error = &'static str;
+    type Error = crate::Error;
 
     fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
@@ -97,7 +100,7 @@ impl TryFrom<char> for CardSuit {
             'D' | 'd' | '♦' => Ok(Self::Diamonds),
             'H' | 'h' | '♥' => Ok(Self::Hearts),
             'S' | 's' | '♠' => Ok(Self::Spades),
-            _ => Err("Invalid card suit"),
+            _ => Err(crate::Error::invalid_char(c, "a card suit (C, D, H, S)")),
         }
     }
 }
@@ -115,14 +118,20 @@ impl Display for Card {
 }
 
 impl FromStr for Card {
-    type Err = &'static str;
+    type Err = crate::Error;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let mut chars = value.chars();
-        Ok(Self {
-            value: chars.next().ok_or("Invalid card")?.try_into()?,
-            suit: chars.next().ok_or("Invalid card")?.try_into()?,
-        })
+        let value = chars
+            .next()
+            .ok_or_else(|| crate::Error::unexpected_end("a card value"))?
+            .try_into()?;
+        let suit = chars
+            .next()
+            .ok_or_else(|| crate::Error::unexpected_end("a card suit").at(1, 2))?
+            .try_into()
+            .map_err(|e: crate::Error| e.at(1, 2))?;
+        Ok(Self { value, suit })
     }
 }
 
@@ -478,6 +487,28 @@ impl Ord for Hand5 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn card_from_str() {
+        let card: Card = "Q♥".parse().unwrap();
+        assert_eq!(
+            (card.value, card.suit),
+            (CardValue::Queen, CardSuit::Hearts)
+        );
+        let error = "QX".parse::<Card>().unwrap_err();
+        assert_eq!(error.position(), (1, 2));
+        assert!(matches!(
+            error,
+            crate::Error::InvalidChar { found: 'X', .. }
+        ));
+        let error = "1H".parse::<Card>().unwrap_err();
+        assert_eq!(error.position(), (1, 1));
+        let error = "Q".parse::<Card>().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UnexpectedEnd { column: 2, .. }
+        ));
+    }
+
     #[test]
     #[ignore = "Not implemented"]
     fn joker_swap() {
@@ -0,0 +1,599 @@
+//! Boolean circuits made of named wires and gates, such as the netlists of 2015 day 7 and
+//! 2024 day 24.
+//!
+//! A [`Circuit`] is parsed from lines like `x AND y -> z`, `NOT x -> h`, `123 -> x` or `x00: 1`.
+//! The wires are sorted topologically once, so every evaluation is a single pass over them.
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A line that is neither a gate nor a wire value
+    InvalidLine(String),
+    /// A wire that is used (or referenced by name) but never driven by anything
+    UndefinedWire(String),
+    /// A wire driven by more than one gate
+    MultipleDrivers(String),
+    /// The wires form a loop that includes this one
+    Cycle(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "invalid line {line:?}"),
+            Self::UndefinedWire(name) => write!(f, "wire {name} is never driven"),
+            Self::MultipleDrivers(name) => write!(f, "wire {name} is driven more than once"),
+            Self::Cycle(name) => write!(f, "wire {name} is part of a loop"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type WireId = usize;
+
+/// A gate input, either another wire or a literal signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Wire(WireId),
+    Const(u16),
+}
+
+/// The gate that drives a single wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate {
+    /// Copies its input, also used for wires with a constant value
+    Buffer(Operand),
+    Not(Operand),
+    And(Operand, Operand),
+    Or(Operand, Operand),
+    Xor(Operand, Operand),
+    LShift(Operand, Operand),
+    RShift(Operand, Operand),
+}
+
+impl Gate {
+    const fn operands(self) -> [Option<Operand>; 2] {
+        match self {
+            Self::Buffer(a) | Self::Not(a) => [Some(a), None],
+            Self::And(a, b)
+            | Self::Or(a, b)
+            | Self::Xor(a, b)
+            | Self::LShift(a, b)
+            | Self::RShift(a, b) => [Some(a), Some(b)],
+        }
+    }
+
+    /// The wires this gate reads from
+    pub fn inputs(self) -> impl Iterator<Item = WireId> {
+        self.operands()
+            .into_iter()
+            .flatten()
+            .filter_map(|op| match op {
+                Operand::Wire(id) => Some(id),
+                Operand::Const(_) => None,
+            })
+    }
+}
+
+/// A netlist of named wires, each driven by a single [`Gate`]
+///
+/// Signals are 16 bits wide by default, see [`Self::with_width`] for narrower circuits.
+///
+/// ```
+/// use advent_of_code_common::circuit::Circuit;
+///
+/// let mut circuit: Circuit = "123 -> x\nNOT x -> h\nx LSHIFT 2 -> f".parse().unwrap();
+/// assert_eq!(circuit.evaluate().get("h"), Some(65412));
+/// circuit.set("x", 1).unwrap();
+/// assert_eq!(circuit.evaluate().get("f"), Some(4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    drivers: Vec<Option<Gate>>,
+    /// Every wire, with each one after all of the wires it reads from
+    order: Vec<WireId>,
+    mask: u16,
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            drivers: Vec::new(),
+            order: Vec::new(),
+            mask: u16::MAX,
+        }
+    }
+}
+
+impl Circuit {
+    /// Limits all signals to their lowest `bits` bits (at most 16), so `NOT` on a 1-bit wire
+    /// gives 0 or 1
+    #[must_use]
+    pub fn with_width(mut self, bits: u32) -> Self {
+        self.mask = u16::MAX.checked_shr(16 - bits.min(16)).unwrap_or(0);
+        self
+    }
+
+    #[must_use]
+    pub fn id(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn name(&self, id: WireId) -> &str {
+        &self.names[id]
+    }
+
+    /// All wire names, in no particular order
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// The gate that drives the wire `name`
+    #[must_use]
+    pub fn gate(&self, name: &str) -> Option<Gate> {
+        self.drivers[self.id(name)?]
+    }
+
+    fn intern(&mut self, name: &str) -> WireId {
+        if let Some(id) = self.id(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.drivers.push(None);
+        id
+    }
+
+    fn operand(&mut self, s: &str) -> Operand {
+        s.parse()
+            .map_or_else(|_| Operand::Wire(self.intern(s)), Operand::Const)
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), Error> {
+        let invalid = || Error::InvalidLine(line.to_string());
+        let (name, gate) = if let Some((expression, name)) = line.split_once(" -> ") {
+            let tokens: Vec<&str> = expression.split_whitespace().collect();
+            let gate = match tokens[..] {
+                [a] => Gate::Buffer(self.operand(a)),
+                ["NOT", a] => Gate::Not(self.operand(a)),
+                [a, op, b] => {
+                    let (a, b) = (self.operand(a), self.operand(b));
+                    match op {
+                        "AND" => Gate::And(a, b),
+                        "OR" => Gate::Or(a, b),
+                        "XOR" => Gate::Xor(a, b),
+                        "LSHIFT" => Gate::LShift(a, b),
+                        "RSHIFT" => Gate::RShift(a, b),
+                        _ => return Err(invalid()),
+                    }
+                }
+                _ => return Err(invalid()),
+            };
+            (name.trim(), gate)
+        } else if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim().parse().map_err(|_| invalid())?;
+            (name.trim(), Gate::Buffer(Operand::Const(value)))
+        } else {
+            return Err(invalid());
+        };
+        let id = self.intern(name);
+        if self.drivers[id].replace(gate).is_some() {
+            return Err(Error::MultipleDrivers(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Sorts the wires topologically (Kahn's algorithm)
+    fn sort(&mut self) -> Result<(), Error> {
+        let mut pending = vec![0_usize; self.names.len()];
+        let mut readers: Vec<Vec<WireId>> = vec![Vec::new(); self.names.len()];
+        for (id, driver) in self.drivers.iter().enumerate() {
+            let Some(gate) = driver else {
+                return Err(Error::UndefinedWire(self.names[id].clone()));
+            };
+            for input in gate.inputs() {
+                pending[id] += 1;
+                readers[input].push(id);
+            }
+        }
+        let mut order: Vec<WireId> = (0..self.names.len())
+            .filter(|&id| pending[id] == 0)
+            .collect();
+        let mut next = 0;
+        while let Some(&id) = order.get(next) {
+            next += 1;
+            for &reader in &readers[id] {
+                pending[reader] -= 1;
+                if pending[reader] == 0 {
+                    order.push(reader);
+                }
+            }
+        }
+        if let Some(id) = pending.iter().position(|&p| p > 0) {
+            return Err(Error::Cycle(self.names[id].clone()));
+        }
+        self.order = order;
+        Ok(())
+    }
+
+    /// Replaces whatever drives the wire `name` with a constant signal
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UndefinedWire`] if there is no such wire
+    pub fn set(&mut self, name: &str, value: u16) -> Result<(), Error> {
+        let id = self
+            .id(name)
+            .ok_or_else(|| Error::UndefinedWire(name.to_string()))?;
+        // Removing the gate's inputs can't invalidate the topological order
+        self.drivers[id] = Some(Gate::Buffer(Operand::Const(value)));
+        Ok(())
+    }
+
+    /// Sets every wire of the bus `prefix` (see [`Signals::bus`]) to the matching bit of `value`
+    pub fn set_bus(&mut self, prefix: &str, value: u64) {
+        for id in 0..self.names.len() {
+            if let Some(bit) = bus_bit(&self.names[id], prefix) {
+                let signal = u16::from(value.checked_shr(bit).unwrap_or(0) & 1 == 1);
+                self.drivers[id] = Some(Gate::Buffer(Operand::Const(signal)));
+            }
+        }
+    }
+
+    /// Swaps the gates that drive the wires `a` and `b`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UndefinedWire`] for unknown wires, or [`Error::Cycle`] if the swap would
+    /// create a loop (in which case the circuit is left unchanged)
+    pub fn swap_outputs(&mut self, a: &str, b: &str) -> Result<(), Error> {
+        let undefined = |name: &str| Error::UndefinedWire(name.to_string());
+        let a = self.id(a).ok_or_else(|| undefined(a))?;
+        let b = self.id(b).ok_or_else(|| undefined(b))?;
+        self.drivers.swap(a, b);
+        self.sort().inspect_err(|_| self.drivers.swap(a, b))
+    }
+
+    /// Computes the signal on every wire
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // Every sorted wire has a driver
+    pub fn evaluate(&self) -> Signals<'_> {
+        let mut values = vec![0_u16; self.names.len()];
+        for &id in &self.order {
+            let value = |op: Operand| match op {
+                Operand::Wire(input) => values[input],
+                Operand::Const(c) => c,
+            };
+            let signal = match self.drivers[id].expect("sorted wires are driven") {
+                Gate::Buffer(a) => value(a),
+                Gate::Not(a) => !value(a),
+                Gate::And(a, b) => value(a) & value(b),
+                Gate::Or(a, b) => value(a) | value(b),
+                Gate::Xor(a, b) => value(a) ^ value(b),
+                Gate::LShift(a, b) => value(a).checked_shl(value(b).into()).unwrap_or(0),
+                Gate::RShift(a, b) => value(a).checked_shr(value(b).into()).unwrap_or(0),
+            };
+            values[id] = signal & self.mask;
+        }
+        Signals {
+            circuit: self,
+            values,
+        }
+    }
+
+    /// Checks the circuit against the structure of a ripple-carry adder that adds the buses
+    /// `x_bus` and `y_bus` into `z_bus` (see [`Signals::bus`]), and returns the (sorted) names of
+    /// the wires whose gates are in the wrong place.
+    ///
+    /// Every bit `i` of a ripple-carry adder is made of the same five gates, with `c` being the
+    /// carry from the previous bit:
+    ///
+    /// ```text
+    /// x_i XOR y_i -> s_i      s_i XOR c -> z_i
+    /// x_i AND y_i -> p_i      s_i AND c -> q_i      p_i OR q_i -> carry
+    /// ```
+    ///
+    /// Bit 0 has no carry in (`x00 XOR y00 -> z00` and `x00 AND y00` is the carry), and the
+    /// final carry is the highest bit of `z`. Gates with swapped outputs show up as wires that
+    /// break one of these rules:
+    /// - `z` wires are driven by `XOR`, except the highest bit which is driven by `OR`
+    /// - An `XOR` that doesn't read from `x` and `y` drives a `z` wire
+    /// - An `XOR` of `x_i` and `y_i` feeds another `XOR` (for `i > 0`)
+    /// - An `AND` feeds an `OR` (except for bit 0)
+    /// - An `OR` feeds an `XOR` (except for the highest bit of `z`)
+    #[must_use]
+    pub fn adder_faults(&self, x_bus: &str, y_bus: &str, z_bus: &str) -> Vec<&str> {
+        let Some(last_z) = self.names.iter().filter_map(|n| bus_bit(n, z_bus)).max() else {
+            return Vec::new();
+        };
+        let mut readers: Vec<Vec<Gate>> = vec![Vec::new(); self.names.len()];
+        for gate in self.drivers.iter().flatten() {
+            for input in gate.inputs() {
+                readers[input].push(*gate);
+            }
+        }
+        let feeds = |id: WireId, kind: fn(&Gate) -> bool| readers[id].iter().any(kind);
+        let input_bit = |op: Operand| match op {
+            Operand::Wire(id) => {
+                bus_bit(&self.names[id], x_bus).or_else(|| bus_bit(&self.names[id], y_bus))
+            }
+            Operand::Const(_) => None,
+        };
+        let mut faults: Vec<&str> = (0..self.names.len())
+            .filter(|&id| {
+                let Some(gate) = self.drivers[id] else {
+                    return false;
+                };
+                let output_bit = bus_bit(&self.names[id], z_bus);
+                match (gate, output_bit) {
+                    (Gate::Or(..), Some(bit)) => bit != last_z,
+                    (Gate::Xor(..), Some(bit)) => bit == last_z && last_z > 0,
+                    (_, Some(_)) => true,
+                    (Gate::Xor(lhs, rhs), None) => match (input_bit(lhs), input_bit(rhs)) {
+                        (Some(bit), Some(_)) => {
+                            bit > 0 && !feeds(id, |g| matches!(g, Gate::Xor(..)))
+                        }
+                        _ => true,
+                    },
+                    (Gate::And(lhs, rhs), None) => {
+                        let first = input_bit(lhs) == Some(0) && input_bit(rhs) == Some(0);
+                        !first && !feeds(id, |g| matches!(g, Gate::Or(..)))
+                    }
+                    (Gate::Or(..), None) => !feeds(id, |g| matches!(g, Gate::Xor(..))),
+                    _ => false,
+                }
+            })
+            .map(|id| self.names[id].as_str())
+            .collect();
+        faults.sort_unstable();
+        faults
+    }
+}
+
+/// The bit index of a bus wire, such as 7 for `z07` in the bus `z`
+fn bus_bit(name: &str, prefix: &str) -> Option<u32> {
+    let digits = name.strip_prefix(prefix)?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+impl FromStr for Circuit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut circuit = Self::default();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            circuit.parse_line(line)?;
+        }
+        circuit.sort()?;
+        Ok(circuit)
+    }
+}
+
+impl fmt::Display for Circuit {
+    /// Writes the netlist back in the `x AND y -> z` format, in topological order
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operand = |op: Operand| match op {
+            Operand::Wire(id) => self.names[id].clone(),
+            Operand::Const(c) => c.to_string(),
+        };
+        for &id in &self.order {
+            let expression = match self.drivers[id].expect("sorted wires are driven") {
+                Gate::Buffer(a) => operand(a),
+                Gate::Not(a) => format!("NOT {}", operand(a)),
+                Gate::And(a, b) => format!("{} AND {}", operand(a), operand(b)),
+                Gate::Or(a, b) => format!("{} OR {}", operand(a), operand(b)),
+                Gate::Xor(a, b) => format!("{} XOR {}", operand(a), operand(b)),
+                Gate::LShift(a, b) => format!("{} LSHIFT {}", operand(a), operand(b)),
+                Gate::RShift(a, b) => format!("{} RSHIFT {}", operand(a), operand(b)),
+            };
+            writeln!(f, "{expression} -> {}", self.names[id])?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of [`Circuit::evaluate`]
+#[derive(Debug, Clone)]
+pub struct Signals<'a> {
+    circuit: &'a Circuit,
+    values: Vec<u16>,
+}
+
+impl Signals<'_> {
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<u16> {
+        Some(self.values[self.circuit.id(name)?])
+    }
+
+    /// Reads the wires named `prefix` followed by a bit index (like `z00`, `z01`, ...) as a
+    /// single number, using the lowest bit of each wire
+    #[must_use]
+    pub fn bus(&self, prefix: &str) -> u64 {
+        self.circuit
+            .names
+            .iter()
+            .zip(&self.values)
+            .filter_map(|(name, &value)| Some(u64::from(value & 1) << bus_bit(name, prefix)?))
+            .fold(0, |acc, bit| acc | bit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "123 -> x
+                           456 -> y
+                           x AND y -> d
+                           x OR y -> e
+                           x LSHIFT 2 -> f
+                           y RSHIFT 2 -> g
+                           NOT x -> h
+                           NOT y -> i";
+
+    /// A ripple-carry adder for `bits`-bit numbers, with the intermediate wires named in a
+    /// scrambled order like the puzzle inputs
+    fn adder(bits: u32) -> String {
+        let name = |kind: &str, i: u32| format!("{kind}{:02}", (i * 7 + 3) % 50);
+        let mut lines = vec![
+            "x00 XOR y00 -> z00".to_string(),
+            format!("x00 AND y00 -> {}", name("c", 0)),
+        ];
+        for i in 1..bits {
+            let carry = name("c", i - 1);
+            let (s, p, q) = (name("s", i), name("p", i), name("q", i));
+            let out = if i + 1 == bits {
+                format!("z{bits:02}")
+            } else {
+                name("c", i)
+            };
+            lines.push(format!("x{i:02} XOR y{i:02} -> {s}"));
+            lines.push(format!("{carry} XOR {s} -> z{i:02}"));
+            lines.push(format!("y{i:02} AND x{i:02} -> {p}"));
+            lines.push(format!("{s} AND {carry} -> {q}"));
+            lines.push(format!("{p} OR {q} -> {out}"));
+        }
+        for i in 0..bits {
+            lines.push(format!("x{i:02}: 0"));
+            lines.push(format!("y{i:02}: 0"));
+        }
+        lines.join("\n")
+    }
+
+    fn add(circuit: &mut Circuit, x: u64, y: u64) -> u64 {
+        circuit.set_bus("x", x);
+        circuit.set_bus("y", y);
+        circuit.evaluate().bus("z")
+    }
+
+    #[test]
+    fn example_signals() {
+        let circuit: Circuit = EXAMPLE.parse().unwrap();
+        let signals = circuit.evaluate();
+        let expected = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        for (name, value) in expected {
+            assert_eq!(signals.get(name), Some(value), "{name}");
+        }
+        assert_eq!(signals.get("a"), None);
+    }
+
+    #[test]
+    fn order_independent() {
+        let circuit: Circuit = "x -> a\nNOT y -> x\n1 -> y".parse().unwrap();
+        assert_eq!(circuit.evaluate().get("a"), Some(0xfffe));
+        let circuit = circuit.with_width(1);
+        assert_eq!(circuit.evaluate().get("a"), Some(0));
+    }
+
+    #[test]
+    fn overrides() {
+        let mut circuit: Circuit = EXAMPLE.parse().unwrap();
+        circuit.set("x", 1).unwrap();
+        assert_eq!(circuit.evaluate().get("f"), Some(4));
+        assert_eq!(circuit.evaluate().get("d"), Some(0));
+        assert_eq!(circuit.set("z", 1), Err(Error::UndefinedWire("z".into())));
+    }
+
+    #[test]
+    fn invalid_netlists() {
+        let parse = |s: &str| s.parse::<Circuit>().unwrap_err();
+        assert_eq!(
+            parse("a NAND b -> c"),
+            Error::InvalidLine("a NAND b -> c".into())
+        );
+        assert_eq!(parse("hello"), Error::InvalidLine("hello".into()));
+        assert_eq!(
+            parse("1 -> a\na -> b\nc -> d"),
+            Error::UndefinedWire("c".into())
+        );
+        assert_eq!(parse("1 -> a\n2 -> a"), Error::MultipleDrivers("a".into()));
+        assert_eq!(parse("b -> a\nNOT a -> b"), Error::Cycle("b".into()));
+        assert_eq!(
+            parse("b -> a\nNOT a -> b").to_string(),
+            "wire b is part of a loop"
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let circuit: Circuit = EXAMPLE.parse().unwrap();
+        let reparsed: Circuit = circuit.to_string().parse().unwrap();
+        let (signals, reparsed) = (circuit.evaluate(), reparsed.evaluate());
+        for name in circuit.wires() {
+            assert_eq!(signals.get(name), reparsed.get(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn adds() {
+        let mut circuit: Circuit = adder(8).parse::<Circuit>().unwrap().with_width(1);
+        for (x, y) in [(0, 0), (1, 1), (255, 1), (200, 100), (255, 255), (37, 91)] {
+            assert_eq!(add(&mut circuit, x, y), x + y, "{x} + {y}");
+        }
+        assert!(circuit.adder_faults("x", "y", "z").is_empty());
+    }
+
+    #[test]
+    fn finds_swapped_outputs() {
+        let mut circuit: Circuit = adder(12).parse().unwrap();
+        let swaps = [
+            ("z05", "c10"),
+            ("s03", "p03"),
+            ("q08", "z08"),
+            ("z10", "p10"),
+        ];
+        let swaps = swaps.map(|(a, b)| {
+            // The scrambled names of bit `i` wires, see `adder`
+            let real = |n: &str| match n.split_at(1) {
+                ("z", _) => n.to_string(),
+                (kind, i) => format!("{kind}{:02}", (i.parse::<u32>().unwrap() * 7 + 3) % 50),
+            };
+            (real(a), real(b))
+        });
+        for (a, b) in &swaps {
+            circuit.swap_outputs(a, b).unwrap();
+        }
+        let mut expected: Vec<&str> = swaps
+            .iter()
+            .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(circuit.adder_faults("x", "y", "z"), expected);
+        assert_ne!(add(&mut circuit, 4095, 1), 4096);
+        for (a, b) in &swaps {
+            circuit.swap_outputs(a, b).unwrap();
+        }
+        assert_eq!(add(&mut circuit, 4095, 1), 4096);
+        assert_eq!(add(&mut circuit, 1234, 2345), 3579);
+    }
+
+    #[test]
+    fn rejects_swaps_with_loops() {
+        let mut circuit: Circuit = adder(4).parse().unwrap();
+        let before = circuit.to_string();
+        assert!(matches!(
+            circuit.swap_outputs("z03", "x00"),
+            Err(Error::Cycle(_))
+        ));
+        assert_eq!(circuit.to_string(), before);
+    }
+}
@@ -1,3 +1,9 @@
+mod heading;
+mod point_n;
+
+pub use heading::{Move, Pose, Turn};
+pub use point_n::{Point3, PointN};
+
 /// A 2D point with x and y coordinates.
 ///
 /// The number type is `isize` to allow for negative coordinates, or minus operations.
@@ -20,13 +26,29 @@ impl std::fmt::Display for Point {
 ///
 /// Whitespace around the coordinates or around the comma is ignored.
 impl std::str::FromStr for Point {
-    type Err = &'static str;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim().trim_start_matches('(').trim_end_matches(')');
-        let (x_str, y_str) = s.split_once(',').ok_or("Invalid point format")?;
-        let x = x_str.trim().parse().map_err(|_| "Invalid x coordinate")?;
-        let y = y_str.trim().parse().map_err(|_| "Invalid y coordinate")?;
+        // Errors point at the column in the original string, so keep track of the byte offsets
+        let column = |offset: usize| s[..offset].chars().count() + 1;
+        let inner = s.trim().trim_start_matches('(').trim_end_matches(')');
+        let start = s.len() - s.trim_start().trim_start_matches('(').len();
+        let comma = inner.find(',').ok_or_else(|| {
+            crate::Error::unexpected_end("',' between the coordinates")
+                .at(1, column(start + inner.len()))
+        })?;
+        let coordinate = |offset: usize, text: &str, expected: &'static str| {
+            let leading = text.len() - text.trim_start().len();
+            text.trim().parse().map_err(|_| {
+                crate::Error::invalid_token(text.trim(), expected).at(1, column(offset + leading))
+            })
+        };
+        let x = coordinate(start, &inner[..comma], "an integer x coordinate")?;
+        let y = coordinate(
+            start + comma + 1,
+            &inner[comma + 1..],
+            "an integer y coordinate",
+        )?;
         Ok(Self { x, y })
     }
 }
@@ -458,20 +480,22 @@ impl std::fmt::Display for Direction {
     }
 }
 
+/// Parse an absolute direction. `'L'` and `'R'` are west and east, as in `U/D/L/R` instructions,
+/// use [`Move`] where they are relative turns.
 impl TryFrom<char> for Direction {
-    type Error = &'static str;
+    type Error = crate::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'U' | 'N' | '↑' | '^' => Ok(Self::North),
             'D' | 'S' | '↓' | 'v' => Ok(Self::South),
-            'L' | 'E' | '→' | '>' => Ok(Self::East),
-            'R' | 'W' | '←' | '<' => Ok(Self::West),
+            'R' | 'E' | '→' | '>' => Ok(Self::East),
+            'L' | 'W' | '←' | '<' => Ok(Self::West),
             '↗' => Ok(Self::NorthEast),
             '↖' => Ok(Self::NorthWest),
             '↘' => Ok(Self::SouthEast),
             '↙' => Ok(Self::SouthWest),
-            _ => Err("Invalid character in input"),
+            _ => Err(crate::Error::invalid_char(value, "a direction")),
         }
     }
 }
@@ -805,5 +829,15 @@ mod tests {
         assert!(p.is_err());
         let p: Result<Point, _> = "(1.2,2.4)".parse();
         assert!(p.is_err());
+        // Errors point at the offending part
+        let error = "(12)".parse::<Point>().unwrap_err();
+        assert_eq!(error.position(), (1, 4));
+        assert!(matches!(error, crate::Error::UnexpectedEnd { .. }));
+        let error = " (3, 2.4)".parse::<Point>().unwrap_err();
+        assert_eq!(error.position(), (1, 6));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 6: expected an integer y coordinate, found \"2.4\""
+        );
     }
 }
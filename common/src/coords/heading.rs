@@ -0,0 +1,250 @@
+use super::{Direction, Point};
+
+/// A turn relative to the current heading.
+///
+/// As with all [`Direction`] methods, `y` grows to the south, so [`Turn::Right`] is clockwise on
+/// screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+}
+
+/// Parse a relative turn, `'L'` or `'R'`
+impl TryFrom<char> for Turn {
+    type Error = crate::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            _ => Err(crate::Error::invalid_char(value, "a turn ('L' or 'R')")),
+        }
+    }
+}
+
+impl Turn {
+    /// The turn by `n` clockwise quarter turns (negative for counter-clockwise), or `None` if
+    /// that ends up facing the same way
+    #[must_use]
+    pub const fn from_quarter_turns(n: isize) -> Option<Self> {
+        match n.rem_euclid(4) {
+            1 => Some(Self::Right),
+            2 => Some(Self::Around),
+            3 => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// The turn by `degrees` clockwise, like the `R270` of a navigation instruction, or `None`
+    /// for full turns
+    ///
+    /// # Errors
+    ///
+    /// If `degrees` is not a multiple of 90
+    pub fn from_degrees(degrees: isize) -> Result<Option<Self>, crate::Error> {
+        if degrees % 90 == 0 {
+            Ok(Self::from_quarter_turns(degrees / 90))
+        } else {
+            Err(crate::Error::invalid_token(
+                degrees.to_string(),
+                "a multiple of 90 degrees",
+            ))
+        }
+    }
+
+    /// The turn that undoes this one
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Around => Self::Around,
+        }
+    }
+}
+
+impl Direction {
+    /// Get the direction after a relative [`Turn`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::{Direction, Turn};
+    /// assert_eq!(Direction::North.turn(Turn::Left), Direction::West);
+    /// assert_eq!(Direction::NorthEast.turn(Turn::Around), Direction::SouthWest);
+    /// ```
+    #[must_use]
+    pub const fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_cw_270(),
+            Turn::Right => self.turn_cw_90(),
+            Turn::Around => self.turn_180(),
+        }
+    }
+}
+
+impl Point {
+    /// Rotate the point by a quarter or half turn around `(0, 0)`, like a waypoint that is
+    /// relative to a ship.
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::{Point, Turn};
+    /// let waypoint = Point { x: 10, y: -4 };
+    /// assert_eq!(waypoint.rotate(Turn::Right), Point { x: 4, y: 10 });
+    /// ```
+    #[must_use]
+    pub const fn rotate(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => Self {
+                x: self.y,
+                y: -self.x,
+            },
+            Turn::Right => Self {
+                x: -self.y,
+                y: self.x,
+            },
+            Turn::Around => Self {
+                x: -self.x,
+                y: -self.y,
+            },
+        }
+    }
+
+    /// Rotate the point by a quarter or half turn around `origin`
+    #[must_use]
+    pub fn rotate_around(&self, turn: Turn, origin: &Self) -> Self {
+        (*self - *origin).rotate(turn) + *origin
+    }
+}
+
+/// A position and the direction it is heading, like a robot or a ship
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pose {
+    pub position: Point,
+    pub direction: Direction,
+}
+
+impl Pose {
+    #[must_use]
+    pub const fn new(position: Point, direction: Direction) -> Self {
+        Self {
+            position,
+            direction,
+        }
+    }
+
+    /// Turn in place
+    pub const fn apply(&mut self, turn: Turn) {
+        self.direction = self.direction.turn(turn);
+    }
+
+    /// Move `n` steps in the current direction (backwards if `n` is negative)
+    pub fn advance(&mut self, n: isize) {
+        self.position = self.ahead(n);
+    }
+
+    /// The position `n` steps ahead, without moving
+    #[must_use]
+    pub fn ahead(&self, n: isize) -> Point {
+        self.position + self.direction * n
+    }
+}
+
+/// One letter of a movement instruction, keeping absolute and relative letters apart.
+///
+/// Unlike [`Direction`]'s `TryFrom<char>`, where `'L'` and `'R'` are west and east (as in the
+/// `U/D/L/R` puzzles), here they are turns:
+///
+/// - `'N'`, `'U'`, `'^'`, `'S'`, `'D'`, `'v'`, `'E'`, `'>'`, `'W'` and `'<'` are absolute
+/// - `'L'` and `'R'` are relative [`Turn`]s
+/// - `'F'` is forward, in the current direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Absolute(Direction),
+    Relative(Turn),
+    Forward,
+}
+
+impl TryFrom<char> for Move {
+    type Error = crate::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' | 'U' | '^' => Ok(Self::Absolute(Direction::North)),
+            'S' | 'D' | 'v' => Ok(Self::Absolute(Direction::South)),
+            'E' | '>' => Ok(Self::Absolute(Direction::East)),
+            'W' | '<' => Ok(Self::Absolute(Direction::West)),
+            'L' | 'R' => Turn::try_from(value).map(Self::Relative),
+            'F' => Ok(Self::Forward),
+            _ => Err(crate::Error::invalid_char(
+                value,
+                "a move (N/U/^, S/D/v, E/>, W/<, L, R or F)",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Turn::from_degrees(90), Ok(Some(Turn::Right)));
+        assert_eq!(Turn::from_degrees(-90), Ok(Some(Turn::Left)));
+        assert_eq!(Turn::from_degrees(270), Ok(Some(Turn::Left)));
+        assert_eq!(Turn::from_degrees(-180), Ok(Some(Turn::Around)));
+        assert_eq!(Turn::from_degrees(360), Ok(None));
+        assert_eq!(
+            Turn::from_degrees(45).unwrap_err().to_string(),
+            "line 1, column 1: expected a multiple of 90 degrees, found \"45\""
+        );
+        for direction in Direction::all() {
+            for turn in [Turn::Left, Turn::Right, Turn::Around] {
+                assert_eq!(direction.turn(turn).turn(turn.reverse()), direction);
+                let offset = Point::from(direction).rotate(turn);
+                assert_eq!(Point::from(direction.turn(turn)), offset);
+            }
+        }
+    }
+
+    #[test]
+    fn pose() {
+        let mut pose = Pose::new(Point::default(), Direction::North);
+        pose.advance(3);
+        pose.apply(Turn::Right);
+        pose.advance(2);
+        assert_eq!(pose.position, Point { x: 2, y: -3 });
+        assert_eq!(pose.ahead(-2), Point { x: 0, y: -3 });
+        pose.apply(Turn::Around);
+        assert_eq!(pose.direction, Direction::West);
+        let origin = Point { x: 1, y: 1 };
+        assert_eq!(
+            Point { x: 3, y: 1 }.rotate_around(Turn::Left, &origin),
+            Point { x: 1, y: -1 }
+        );
+    }
+
+    #[test]
+    fn moves() {
+        let moves: Vec<Move> = "N^E<LRF".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(
+            moves,
+            [
+                Move::Absolute(Direction::North),
+                Move::Absolute(Direction::North),
+                Move::Absolute(Direction::East),
+                Move::Absolute(Direction::West),
+                Move::Relative(Turn::Left),
+                Move::Relative(Turn::Right),
+                Move::Forward,
+            ]
+        );
+        assert!(Move::try_from('x').is_err());
+        // The absolute parser keeps U/D/L/R puzzles working
+        assert_eq!(Direction::try_from('L').unwrap(), Direction::West);
+        assert_eq!(Direction::try_from('R').unwrap(), Direction::East);
+    }
+}
@@ -0,0 +1,384 @@
+use itertools::Itertools;
+
+/// A point with `D` integer coordinates, the N-dimensional version of [`Point`](super::Point).
+///
+/// Points are ordered by their coordinates, first axis first.
+///
+/// # Example
+/// ```rust
+/// use advent_of_code_common::coords::{Point3, PointN};
+/// let a: Point3 = "1,2,3".parse().unwrap();
+/// let b = Point3::new(4, 6, 3);
+/// assert_eq!(a.manhattan_distance(&b), 7);
+/// assert_eq!((b - a) * 2_i8, Point3::new(6, 8, 0));
+/// assert_eq!(a.face_neighbors().count(), 6);
+/// let p = PointN { coords: [1, 0, 0, -1] };
+/// assert_eq!(p.neighbors().count(), 80);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<const D: usize> {
+    pub coords: [isize; D],
+}
+
+/// A point in 3D space, with `x()`, `y()` and `z()` accessors
+pub type Point3 = PointN<3>;
+
+impl<const D: usize> Default for PointN<D> {
+    fn default() -> Self {
+        Self { coords: [0; D] }
+    }
+}
+
+impl Point3 {
+    #[must_use]
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { coords: [x, y, z] }
+    }
+
+    #[must_use]
+    pub const fn x(&self) -> isize {
+        self.coords[0]
+    }
+
+    #[must_use]
+    pub const fn y(&self) -> isize {
+        self.coords[1]
+    }
+
+    #[must_use]
+    pub const fn z(&self) -> isize {
+        self.coords[2]
+    }
+}
+
+impl<const D: usize> PointN<D> {
+    /// Get the Manhattan distance to another point.
+    /// This is the sum of the absolute differences on every axis.
+    #[must_use]
+    pub fn manhattan_distance(&self, other: &Self) -> isize {
+        (0..D).map(|i| (self[i] - other[i]).abs()).sum()
+    }
+
+    /// Get the Chebyshev distance to another point.
+    /// This is the largest absolute difference on any axis, the number of king moves between them.
+    #[must_use]
+    pub fn chebyshev_distance(&self, other: &Self) -> isize {
+        (0..D)
+            .map(|i| (self[i] - other[i]).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the squared Euclidean distance to another point, which is exact (unlike
+    /// [`Self::distance`]) and sorts the same way.
+    #[must_use]
+    pub fn squared_distance(&self, other: &Self) -> isize {
+        (0..D).map(|i| (self[i] - other[i]).pow(2)).sum()
+    }
+
+    /// Get the Euclidean distance to another point.
+    ///
+    /// # Note
+    /// The squared distance is cast to f64 before the square root is taken.
+    /// This might cause a loss of precision for very large numbers.
+    #[expect(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn distance(&self, other: &Self) -> f64 {
+        (self.squared_distance(other) as f64).sqrt()
+    }
+
+    /// The neighbors in a box of radius 1 around this point where `changed` accepts the number of
+    /// axes that differ, in order of their offsets
+    fn neighbors_changing(&self, changed: impl Fn(usize) -> bool) -> impl Iterator<Item = Self> {
+        let point = *self;
+        (0..D)
+            .map(|_| -1..=1)
+            .multi_cartesian_product()
+            .filter(move |offset| changed(offset.iter().filter(|&&d| d != 0).count()))
+            .map(move |offset| {
+                let mut neighbor = point;
+                for (coord, d) in neighbor.coords.iter_mut().zip(offset) {
+                    *coord += d;
+                }
+                neighbor
+            })
+    }
+
+    /// Get the `2 * D` neighbors that share a face with this point (a side in 2D), i.e. that
+    /// differ by 1 on exactly one axis.
+    pub fn face_neighbors(&self) -> impl Iterator<Item = Self> {
+        self.neighbors_changing(|n| n == 1)
+    }
+
+    /// Get the neighbors that share only an edge with this point, i.e. that differ by 1 on
+    /// exactly two axes. There are 12 of them in 3D.
+    pub fn edge_neighbors(&self) -> impl Iterator<Item = Self> {
+        self.neighbors_changing(|n| n == 2)
+    }
+
+    /// Get the `2^D` neighbors that share only a corner with this point, i.e. that differ by 1 on
+    /// every axis.
+    pub fn corner_neighbors(&self) -> impl Iterator<Item = Self> {
+        self.neighbors_changing(|n| n == D)
+    }
+
+    /// Get all `3^D - 1` neighbors in a box of radius 1 around this point.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> {
+        self.neighbors_changing(|n| n > 0)
+    }
+
+    /// The (min, max) corners of the smallest box that contains all the points, or `None` if
+    /// there are no points
+    #[must_use]
+    pub fn bounding_box<'p>(points: impl IntoIterator<Item = &'p Self>) -> Option<(Self, Self)> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(mut min, mut max), p| {
+            for i in 0..D {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+            (min, max)
+        }))
+    }
+
+    /// Whether the point is inside the box from `min` to `max`, inclusive
+    #[must_use]
+    pub fn is_within(&self, min: &Self, max: &Self) -> bool {
+        (0..D).all(|i| (min[i]..=max[i]).contains(&self[i]))
+    }
+}
+
+impl<const D: usize> std::fmt::Display for PointN<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({})", self.coords.iter().join(", "))
+    }
+}
+
+/// Parse a point from a string in the format "x,y,z" or "(x,y,z)" (with `D` integers).
+///
+/// Whitespace around the coordinates or around the commas is ignored.
+impl<const D: usize> std::str::FromStr for PointN<D> {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let column = |offset: usize| s[..offset].chars().count() + 1;
+        let inner = s.trim().trim_start_matches('(').trim_end_matches(')');
+        let mut offset = s.len() - s.trim_start().trim_start_matches('(').len();
+        let end = offset + inner.len();
+        let mut parts = inner.split(',');
+        let mut coords = [0; D];
+        for coord in &mut coords {
+            let part = parts.next().ok_or_else(|| {
+                crate::Error::unexpected_end(format!("{D} comma separated coordinates"))
+                    .at(1, column(offset.min(end)))
+            })?;
+            let leading = part.len() - part.trim_start().len();
+            *coord = part.trim().parse().map_err(|_| {
+                crate::Error::invalid_token(part.trim(), "an integer coordinate")
+                    .at(1, column(offset + leading))
+            })?;
+            offset += part.len() + 1;
+        }
+        if let Some(extra) = parts.next() {
+            let leading = extra.len() - extra.trim_start().len();
+            let expected = format!("only {D} coordinates");
+            return Err(
+                crate::Error::invalid_token(extra.trim(), expected).at(1, column(offset + leading))
+            );
+        }
+        Ok(Self { coords })
+    }
+}
+
+impl<const D: usize> std::ops::Index<usize> for PointN<D> {
+    type Output = isize;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.coords[axis]
+    }
+}
+
+impl<const D: usize> std::ops::IndexMut<usize> for PointN<D> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.coords[axis]
+    }
+}
+
+impl<N, const D: usize> From<[N; D]> for PointN<D>
+where
+    isize: From<N>,
+{
+    fn from(coords: [N; D]) -> Self {
+        Self {
+            coords: coords.map(isize::from),
+        }
+    }
+}
+
+impl<N> From<(N, N, N)> for Point3
+where
+    isize: From<N>,
+{
+    fn from(coords: (N, N, N)) -> Self {
+        Self::from([coords.0, coords.1, coords.2])
+    }
+}
+
+impl<const D: usize> std::ops::Add for PointN<D> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<const D: usize> std::ops::AddAssign for PointN<D> {
+    fn add_assign(&mut self, rhs: Self) {
+        for i in 0..D {
+            self[i] += rhs[i];
+        }
+    }
+}
+
+impl<const D: usize> std::ops::Sub for PointN<D> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const D: usize> std::ops::SubAssign for PointN<D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for i in 0..D {
+            self[i] -= rhs[i];
+        }
+    }
+}
+
+impl<const D: usize> std::ops::Neg for PointN<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coords: self.coords.map(|v| -v),
+        }
+    }
+}
+
+impl<T, const D: usize> std::ops::Mul<T> for PointN<D>
+where
+    T: Into<isize>,
+{
+    type Output = Self;
+
+    fn mul(mut self, rhs: T) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T, const D: usize> std::ops::MulAssign<T> for PointN<D>
+where
+    T: Into<isize>,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        let rhs = rhs.into();
+        for coord in &mut self.coords {
+            *coord *= rhs;
+        }
+    }
+}
+
+impl<T, const D: usize> std::ops::Div<T> for PointN<D>
+where
+    T: Into<isize>,
+{
+    type Output = Self;
+
+    fn div(mut self, rhs: T) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl<T, const D: usize> std::ops::DivAssign<T> for PointN<D>
+where
+    T: Into<isize>,
+{
+    fn div_assign(&mut self, rhs: T) {
+        let rhs = rhs.into();
+        for coord in &mut self.coords {
+            *coord /= rhs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point3_str() {
+        let p: Point3 = "1,-2,3".parse().unwrap();
+        assert_eq!((p.x(), p.y(), p.z()), (1, -2, 3));
+        let p: Point3 = " ( 4 , 5,6 ) ".parse().unwrap();
+        assert_eq!(p, Point3::new(4, 5, 6));
+        assert_eq!(p.to_string(), "(4, 5, 6)");
+        let error = "1,2".parse::<Point3>().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UnexpectedEnd { column: 4, .. }
+        ));
+        let error = "1, x,3".parse::<Point3>().unwrap_err();
+        assert_eq!(error.position(), (1, 4));
+        let error = "1,2,3,4".parse::<Point3>().unwrap_err();
+        assert_eq!(error.position(), (1, 7));
+        assert!("1,2,3,4".parse::<PointN<4>>().is_ok());
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point3::new(0, 0, 0);
+        let b = Point3::new(1, -2, 2);
+        assert_eq!(a.manhattan_distance(&b), 5);
+        assert_eq!(a.chebyshev_distance(&b), 2);
+        assert_eq!(a.squared_distance(&b), 9);
+        assert!((a.distance(&b) - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Point3::new(1, 1, 1);
+        let faces: Vec<_> = p.face_neighbors().collect();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|n| n.manhattan_distance(&p) == 1));
+        assert_eq!(p.edge_neighbors().count(), 12);
+        assert_eq!(p.corner_neighbors().count(), 8);
+        assert!(p.corner_neighbors().all(|n| n.manhattan_distance(&p) == 3));
+        assert_eq!(p.neighbors().count(), 26);
+        let p = PointN::<2>::default();
+        assert_eq!(p.face_neighbors().count(), 4);
+        assert_eq!(p.corner_neighbors().count(), 4);
+    }
+
+    #[test]
+    fn arithmetic_and_bounds() {
+        let mut p = Point3::new(1, 2, 3);
+        p += Point3::new(1, 1, 1);
+        assert_eq!(p, Point3::new(2, 3, 4));
+        assert_eq!(-p, Point3::new(-2, -3, -4));
+        assert_eq!(p * 3_i8 / 2_i8, Point3::new(3, 4, 6));
+        p[2] = 0;
+        assert_eq!(p - Point3::from((2_i8, 3, 0)), Point3::default());
+        let points = [Point3::new(1, 5, -1), Point3::new(3, 0, 2)];
+        let (min, max) = PointN::bounding_box(&points).unwrap();
+        assert_eq!((min, max), (Point3::new(1, 0, -1), Point3::new(3, 5, 2)));
+        assert!(Point3::new(2, 5, 0).is_within(&min, &max));
+        assert!(!Point3::new(2, 6, 0).is_within(&min, &max));
+        assert!(PointN::<3>::bounding_box(&[]).is_none());
+    }
+}
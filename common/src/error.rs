@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+/// An error from parsing puzzle input, with the position of the problem and what was expected
+/// there instead.
+///
+/// Lines and columns are 1-based and count characters, not bytes. Parsers of small pieces (like a
+/// single `char`) report their errors at `1:1`, and the parsers that call them move the error to
+/// where that piece started in the bigger input with [`Error::at`].
+///
+/// It implements [`std::error::Error`], so `?` converts it to an `anyhow::Error` or a
+/// `Box<dyn std::error::Error>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A character that is not one of the expected ones
+    InvalidChar {
+        line: usize,
+        column: usize,
+        found: char,
+        expected: Cow<'static, str>,
+    },
+    /// A token (a number, a word, ...) that could not be parsed
+    InvalidToken {
+        line: usize,
+        column: usize,
+        found: String,
+        expected: Cow<'static, str>,
+    },
+    /// The input ended before something that was expected
+    UnexpectedEnd {
+        line: usize,
+        column: usize,
+        expected: Cow<'static, str>,
+    },
+}
+
+impl Error {
+    #[must_use]
+    pub fn invalid_char(found: char, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidChar {
+            line: 1,
+            column: 1,
+            found,
+            expected: expected.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn invalid_token(found: impl Into<String>, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidToken {
+            line: 1,
+            column: 1,
+            found: found.into(),
+            expected: expected.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn unexpected_end(expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::UnexpectedEnd {
+            line: 1,
+            column: 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// The (line, column) of the problem
+    #[must_use]
+    pub const fn position(&self) -> (usize, usize) {
+        match self {
+            Self::InvalidChar { line, column, .. }
+            | Self::InvalidToken { line, column, .. }
+            | Self::UnexpectedEnd { line, column, .. } => (*line, *column),
+        }
+    }
+
+    /// A description of what was expected instead
+    #[must_use]
+    pub fn expected(&self) -> &str {
+        match self {
+            Self::InvalidChar { expected, .. }
+            | Self::InvalidToken { expected, .. }
+            | Self::UnexpectedEnd { expected, .. } => expected,
+        }
+    }
+
+    /// Moves the error from a piece of input to a bigger input, where that piece starts at
+    /// (`line`, `column`)
+    #[must_use]
+    pub const fn at(mut self, line: usize, column: usize) -> Self {
+        let (Self::InvalidChar {
+            line: own_line,
+            column: own_column,
+            ..
+        }
+        | Self::InvalidToken {
+            line: own_line,
+            column: own_column,
+            ..
+        }
+        | Self::UnexpectedEnd {
+            line: own_line,
+            column: own_column,
+            ..
+        }) = &mut self;
+        // Columns only shift on the first line of the piece
+        if *own_line == 1 {
+            *own_column += column - 1;
+        }
+        *own_line += line - 1;
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (line, column) = self.position();
+        write!(f, "line {line}, column {column}: ")?;
+        match self {
+            Self::InvalidChar {
+                found, expected, ..
+            } => write!(f, "expected {expected}, found {found:?}"),
+            Self::InvalidToken {
+                found, expected, ..
+            } => write!(f, "expected {expected}, found {found:?}"),
+            Self::UnexpectedEnd { expected, .. } => {
+                write!(f, "expected {expected}, found the end of the input")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parsing a `char` into a `char` never fails, this allows `Grid<char>` to share the error type
+impl From<std::convert::Infallible> for Error {
+    fn from(value: std::convert::Infallible) -> Self {
+        match value {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let error = Error::invalid_char('x', "a digit");
+        assert_eq!(error.position(), (1, 1));
+        let error = error.at(1, 4);
+        assert_eq!(error.position(), (1, 4));
+        let error = error.at(3, 2);
+        assert_eq!(error.position(), (3, 5));
+        // Only the first line of the inner piece is shifted right
+        let error = Error::unexpected_end("a suit").at(2, 1).at(5, 10);
+        assert_eq!(error.position(), (6, 1));
+    }
+
+    #[test]
+    fn test_display() {
+        let error = Error::invalid_char('x', "one of '.', '#'").at(2, 3);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected one of '.', '#', found 'x'"
+        );
+        let error = Error::invalid_token("1a", "an integer");
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected an integer, found \"1a\""
+        );
+        let error: Box<dyn std::error::Error> = Error::unexpected_end("a suit").into();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected a suit, found the end of the input"
+        );
+        assert_eq!(
+            error.downcast_ref::<Error>().map(Error::expected),
+            Some("a suit")
+        );
+    }
+}
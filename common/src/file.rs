@@ -1,19 +1,40 @@
 use crate::v2::V2;
 
+pub mod parse;
+
 /// Split on lines breaks and trim whitespace from lines
 pub fn split_lines(s: &str) -> Vec<String> {
-    s.split('\n').map(String::from).collect()
+    iter_lines(s).map(String::from).collect()
 }
 
 /// Same as `split_lines` but trims whitespace from start and end of input and from every line
 #[must_use]
 pub fn split_lines_trim(s: &str) -> Vec<String> {
-    split_lines(s.trim())
-        .iter()
+    iter_lines(s.trim())
         .map(|row| row.trim().to_string())
         .collect()
 }
 
+/// Same as `split_lines`, but borrows the lines from the input instead of allocating them
+pub fn iter_lines(s: &str) -> impl Iterator<Item = &str> {
+    s.split('\n')
+}
+
+/// Same as `split_lines_trim`, but borrows the lines from the input instead of allocating them.
+/// An empty (or all whitespace) input has no lines.
+pub fn iter_lines_trim(s: &str) -> impl Iterator<Item = &str> {
+    s.trim().lines().map(str::trim)
+}
+
+/// Same as `lines_as_blocks`, but borrows the lines from the input
+pub fn iter_blocks(s: &str) -> impl Iterator<Item = Vec<&str>> {
+    let mut lines = iter_lines_trim(s).peekable();
+    std::iter::from_fn(move || {
+        lines.peek()?;
+        Some(lines.by_ref().take_while(|line| !line.is_empty()).collect())
+    })
+}
+
 /// Convert lines of strings into a Vector based on given function
 ///
 /// # Errors
@@ -109,5 +130,9 @@ mod tests {
         assert_eq!(result[0].len(), 2);
         assert_eq!(result[1].len(), 1);
         assert_eq!(result[1][0], "789");
+        let blocks: Vec<_> = iter_blocks(lines).collect();
+        assert_eq!(blocks, [vec!["123", "456"], vec!["789"]]);
+        assert_eq!(iter_blocks("a\n\n\nb").count(), 3);
+        assert_eq!(iter_blocks(" \n").count(), 0);
     }
 }
@@ -0,0 +1,689 @@
+//! Small parser combinators, to declare the shape of an input instead of splitting it by hand.
+//!
+//! Parsers work on the whole input and a byte offset into it, so that failures can be reported
+//! with the line and column where they happened. Lines are trimmed of surrounding spaces, which
+//! keeps indented examples in tests working.
+//!
+//! ```
+//! use advent_of_code_common::file::parse::{labelled, line, separated, signed, Parser};
+//!
+//! let robot = line(
+//!     labelled("p=", separated(signed::<i32>(), ","))
+//!         .then(labelled("v=", separated(signed::<i32>(), ","))),
+//! );
+//! assert_eq!(robot.parse("p=0,4 v=3,-3"), Ok((vec![0, 4], vec![3, -3])));
+//!
+//! let error = robot.parse("p=0,4 v=3,x").unwrap_err();
+//! assert_eq!((error.line, error.column), (1, 11));
+//! assert_eq!(error.to_string(), "line 1, column 11: expected a number, found \"x\"");
+//! ```
+use crate::v2::V2;
+use std::{fmt, str::FromStr};
+
+/// Where and why parsing failed, with 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {:?}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// So `?` works in functions that return the crate's [`Error`](crate::Error)
+impl From<ParseError> for crate::Error {
+    fn from(error: ParseError) -> Self {
+        Self::invalid_token(error.found, error.expected).at(error.line, error.column)
+    }
+}
+
+/// A failure at a byte offset of the input, before it is turned into a [`ParseError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub offset: usize,
+    pub expected: String,
+}
+
+impl Failure {
+    #[must_use]
+    pub fn new(offset: usize, expected: impl Into<String>) -> Self {
+        Self {
+            offset,
+            expected: expected.into(),
+        }
+    }
+
+    /// The line and column of the failure in `input`, and the token found there
+    #[must_use]
+    pub fn locate(self, input: &str) -> ParseError {
+        let before = &input[..self.offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let rest = input[self.offset..].lines().next().unwrap_or_default();
+        let found = rest.split_whitespace().next().unwrap_or_default();
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: self.expected,
+            found: if self.offset == input.len() {
+                "end of input".to_string()
+            } else {
+                found.to_string()
+            },
+        }
+    }
+}
+
+/// The parsed value and the offset right after it
+pub type PResult<T> = Result<(T, usize), Failure>;
+
+/// Anything that can parse a `T` from some offset of an input.
+///
+/// Implemented for all functions `Fn(&str, usize) -> PResult<T>`, so new parsers are just
+/// closures.
+pub trait Parser<T> {
+    /// Parses a value starting at byte `offset` of `input`
+    ///
+    /// # Errors
+    ///
+    /// A [`Failure`] at the offset where the input doesn't match
+    fn parse_at(&self, input: &str, offset: usize) -> PResult<T>;
+
+    /// Parses the whole `input`, which may only have whitespace left after the value
+    ///
+    /// # Errors
+    ///
+    /// A [`ParseError`] at the line and column where the input doesn't match
+    fn parse(&self, input: &str) -> Result<T, ParseError> {
+        let (value, end) = self
+            .parse_at(input, 0)
+            .map_err(|failure| failure.locate(input))?;
+        let rest = &input[end..];
+        if rest.trim().is_empty() {
+            Ok(value)
+        } else {
+            let offset = input.len() - rest.trim_start().len();
+            Err(Failure::new(offset, "end of input").locate(input))
+        }
+    }
+
+    /// Transforms the parsed value
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |input: &str, offset| {
+            self.parse_at(input, offset)
+                .map(|(value, end)| (f(value), end))
+        }
+    }
+
+    /// Transforms the parsed value, failing with `expected` where `f` returns `None`
+    fn try_map<U>(self, expected: &str, f: impl Fn(T) -> Option<U>) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |input: &str, offset| {
+            let (value, end) = self.parse_at(input, offset)?;
+            f(value)
+                .map(|mapped| (mapped, end))
+                .ok_or_else(|| Failure::new(offset, expected))
+        }
+    }
+
+    /// Parses `self`, then `next`, and returns both values
+    fn then<U>(self, next: impl Parser<U>) -> impl Parser<(T, U)>
+    where
+        Self: Sized,
+    {
+        move |input: &str, offset| {
+            let (first, offset) = self.parse_at(input, offset)?;
+            let (second, offset) = next.parse_at(input, offset)?;
+            Ok(((first, second), offset))
+        }
+    }
+
+    /// Parses `self`, then `next`, and only keeps the value of `self`
+    fn skip<U>(self, next: impl Parser<U>) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        self.then(next).map(|(value, _)| value)
+    }
+
+    /// Succeeds with `None` if `self` doesn't match, without consuming anything
+    fn optional(self) -> impl Parser<Option<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &str, offset| match self.parse_at(input, offset) {
+            Ok((value, end)) => Ok((Some(value), end)),
+            Err(_) => Ok((None, offset)),
+        }
+    }
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&str, usize) -> PResult<T>,
+{
+    fn parse_at(&self, input: &str, offset: usize) -> PResult<T> {
+        self(input, offset)
+    }
+}
+
+/// The offset after any spaces or tabs (but not line breaks) from `offset`
+fn skip_spaces(input: &str, offset: usize) -> usize {
+    let rest = &input[offset..];
+    offset + rest.len() - rest.trim_start_matches([' ', '\t', '\r']).len()
+}
+
+/// The offset of the end of the line at `offset`, before the line break
+fn line_end(input: &str, offset: usize) -> usize {
+    input[offset..]
+        .find('\n')
+        .map_or(input.len(), |i| offset + i)
+}
+
+fn is_blank_line(input: &str, offset: usize) -> bool {
+    input[offset..line_end(input, offset)].trim().is_empty()
+}
+
+/// The offset of the start of the next line, or the end of the input
+fn next_line(input: &str, offset: usize) -> usize {
+    (line_end(input, offset) + 1).min(input.len())
+}
+
+/// Parses the longest run of characters matching `predicate`, failing if it is empty
+fn take_while(
+    input: &str,
+    offset: usize,
+    expected: &str,
+    predicate: impl Fn(char) -> bool,
+) -> PResult<String> {
+    let rest = &input[offset..];
+    let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+    if len == 0 {
+        Err(Failure::new(offset, expected))
+    } else {
+        Ok((rest[..len].to_string(), offset + len))
+    }
+}
+
+/// Exactly `text`
+#[must_use]
+pub fn literal(text: &str) -> impl Parser<()> + '_ {
+    move |input: &str, offset: usize| {
+        if input[offset..].starts_with(text) {
+            Ok(((), offset + text.len()))
+        } else {
+            Err(Failure::new(offset, format!("{text:?}")))
+        }
+    }
+}
+
+/// Any number of spaces or tabs, including none
+#[must_use]
+pub fn spaces() -> impl Parser<()> {
+    |input: &str, offset| Ok(((), skip_spaces(input, offset)))
+}
+
+/// A run of non-whitespace characters
+#[must_use]
+pub fn word() -> impl Parser<String> {
+    |input: &str, offset| take_while(input, offset, "a word", |c| !c.is_whitespace())
+}
+
+/// A non-negative number, made of digits only
+#[must_use]
+pub fn unsigned<T: FromStr>() -> impl Parser<T> {
+    |input: &str, offset| {
+        let (digits, end) = take_while(input, offset, "a number", |c| c.is_ascii_digit())?;
+        let value = digits
+            .parse()
+            .map_err(|_| Failure::new(offset, "a number in range"))?;
+        Ok((value, end))
+    }
+}
+
+/// A number with an optional `-` or `+` sign
+#[must_use]
+pub fn signed<T: FromStr>() -> impl Parser<T> {
+    |input: &str, offset| {
+        let sign = usize::from(input[offset..].starts_with(['-', '+']));
+        let (_, end) = take_while(input, offset + sign, "a number", |c| c.is_ascii_digit())
+            .map_err(|failure| Failure::new(offset, failure.expected))?;
+        let value = input[offset..end]
+            .parse()
+            .map_err(|_| Failure::new(offset, "a number in range"))?;
+        Ok((value, end))
+    }
+}
+
+/// One of the given keywords, mapped to its value
+#[must_use]
+pub fn choice<'a, T: Clone>(options: &'a [(&'static str, T)]) -> impl Parser<T> + 'a {
+    move |input: &str, offset| {
+        options
+            .iter()
+            .find(|(keyword, _)| input[offset..].starts_with(keyword))
+            .map(|(keyword, value)| (value.clone(), offset + keyword.len()))
+            .ok_or_else(|| {
+                let keywords: Vec<_> = options.iter().map(|(k, _)| format!("{k:?}")).collect();
+                Failure::new(offset, format!("one of {}", keywords.join(", ")))
+            })
+    }
+}
+
+/// A field like `"Register A: 729"`: the `label` and then the value, with optional spaces before
+/// either of them
+#[must_use]
+pub fn labelled<'a, T: 'a>(label: &'a str, value: impl Parser<T> + 'a) -> impl Parser<T> + 'a {
+    spaces()
+        .then(literal(label))
+        .then(spaces())
+        .then(value)
+        .map(|(_, v)| v)
+}
+
+/// One or more values with `separator` (and optional spaces) between them. A blank separator
+/// means values separated by spaces, up to the first thing that isn't a value (or is an empty one).
+#[must_use]
+pub fn separated<'a, T: 'a>(
+    item: impl Parser<T> + 'a,
+    separator: &'a str,
+) -> impl Parser<Vec<T>> + 'a {
+    let by_spaces = separator.trim().is_empty();
+    move |input: &str, offset| {
+        let (first, mut offset) = item.parse_at(input, offset)?;
+        let mut items = vec![first];
+        loop {
+            let after_spaces = skip_spaces(input, offset);
+            let next = if by_spaces && after_spaces > offset {
+                after_spaces
+            } else if !by_spaces && input[after_spaces..].starts_with(separator) {
+                skip_spaces(input, after_spaces + separator.len())
+            } else {
+                return Ok((items, offset));
+            };
+            match item.parse_at(input, next) {
+                // Without a separator to consume, an empty value would be followed by another one
+                Ok((_, end)) if by_spaces && end == next => return Ok((items, offset)),
+                Ok((value, end)) => {
+                    items.push(value);
+                    offset = end;
+                }
+                Err(_) if by_spaces => return Ok((items, offset)),
+                Err(failure) => return Err(failure),
+            }
+        }
+    }
+}
+
+/// A whole line holding `content` (and surrounding spaces), up to and including the line break
+#[must_use]
+pub fn line<T>(content: impl Parser<T>) -> impl Parser<T> {
+    move |input: &str, offset| {
+        let (value, end) = content.parse_at(input, skip_spaces(input, offset))?;
+        let end = skip_spaces(input, end);
+        match input[end..].chars().next() {
+            None => Ok((value, end)),
+            Some('\n') => Ok((value, end + 1)),
+            Some(_) => Err(Failure::new(end, "end of line")),
+        }
+    }
+}
+
+/// Skips any number of lines that are empty or only have whitespace
+#[must_use]
+pub fn blank_lines() -> impl Parser<()> {
+    |input: &str, mut offset| {
+        while offset < input.len() && is_blank_line(input, offset) {
+            offset = next_line(input, offset);
+        }
+        Ok(((), offset))
+    }
+}
+
+/// Consecutive lines that each hold `content`, up to a blank line or the end of the input
+#[must_use]
+pub fn lines<T>(content: impl Parser<T>) -> impl Parser<Vec<T>> {
+    let content = line(content);
+    move |input: &str, mut offset| {
+        let mut values = Vec::new();
+        while offset < input.len() && !is_blank_line(input, offset) {
+            let (value, end) = content.parse_at(input, offset)?;
+            values.push(value);
+            offset = end;
+        }
+        Ok((values, offset))
+    }
+}
+
+/// Blocks of lines separated by blank lines, where `block` parses each of them (and should stop
+/// at a blank line, like [`lines`] and [`grid`] do)
+#[must_use]
+pub fn blocks<T>(block: impl Parser<T>) -> impl Parser<Vec<T>> {
+    let skip = blank_lines();
+    move |input: &str, offset| {
+        let ((), mut offset) = skip.parse_at(input, offset)?;
+        let mut values = Vec::new();
+        while offset < input.len() {
+            let (value, end) = block.parse_at(input, offset)?;
+            if end == offset {
+                // The same empty block would be found here forever
+                return Err(Failure::new(offset, "a block"));
+            }
+            values.push(value);
+            ((), offset) = skip.parse_at(input, end)?;
+        }
+        Ok((values, offset))
+    }
+}
+
+/// Rows of characters up to a blank line, each converted by `cell`. All rows need the same width.
+#[must_use]
+pub fn grid<T>(cell: impl Fn(char) -> Option<T>) -> impl Parser<V2<T>> {
+    move |input: &str, mut offset| {
+        let mut rows: V2<T> = Vec::new();
+        while offset < input.len() && !is_blank_line(input, offset) {
+            let start = skip_spaces(input, offset);
+            let text = input[start..line_end(input, offset)].trim_end();
+            let row = text
+                .char_indices()
+                .map(|(i, c)| cell(c).ok_or_else(|| Failure::new(start + i, "a grid cell")))
+                .collect::<Result<Vec<T>, _>>()?;
+            if rows.first().is_some_and(|first| first.len() != row.len()) {
+                return Err(Failure::new(start, "a row as wide as the first one"));
+            }
+            rows.push(row);
+            offset = next_line(input, offset);
+        }
+        Ok((rows, offset))
+    }
+}
+
+/// A piece of a sentence template, as generated by `#[derive(FromInput)]` from a format like
+/// `"p={x},{y} v={vx},{vy}"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment {
+    /// Text that must be there
+    Literal(&'static str),
+    /// Text that may be there, like the plural `[s]`
+    Optional(&'static str),
+    /// A value, up to the next literal text
+    Field,
+    /// Values with a separator between them, up to the next literal text
+    List(&'static str),
+}
+
+/// The offset and text of each field of `template` in `input`, which is trimmed first
+///
+/// # Errors
+///
+/// If a literal part of the template is missing, or a field is empty
+pub fn match_template<'a>(
+    input: &'a str,
+    template: &[Segment],
+) -> Result<Vec<(usize, &'a str)>, ParseError> {
+    let end = input.trim_end().len();
+    let mut offset = input.len() - input.trim_start().len();
+    let mut fields = Vec::new();
+    for (i, segment) in template.iter().enumerate() {
+        let rest = &input[offset..end];
+        match segment {
+            Segment::Literal(text) => {
+                if !rest.starts_with(text) {
+                    return Err(Failure::new(offset, format!("{text:?}")).locate(input));
+                }
+                offset += text.len();
+            }
+            Segment::Optional(text) => {
+                if rest.starts_with(text) {
+                    offset += text.len();
+                }
+            }
+            Segment::Field | Segment::List(_) => {
+                let after = &template[i + 1..];
+                let len = field_length(rest, after).ok_or_else(|| {
+                    let missing = after.iter().find_map(|segment| match segment {
+                        Segment::Literal(text) => Some(*text),
+                        _ => None,
+                    });
+                    Failure::new(end, format!("{:?}", missing.unwrap_or_default())).locate(input)
+                })?;
+                if len == 0 {
+                    return Err(Failure::new(offset, "a value").locate(input));
+                }
+                fields.push((offset, &rest[..len]));
+                offset += len;
+            }
+        }
+    }
+    if offset < end {
+        return Err(Failure::new(offset, "end of line").locate(input));
+    }
+    Ok(fields)
+}
+
+/// How much of `rest` a field takes: everything up to the first place where the literal text
+/// after it matches (including an optional part), or `None` if that text is missing
+fn field_length(rest: &str, after: &[Segment]) -> Option<usize> {
+    let mut literals = after.iter().map_while(|segment| match segment {
+        Segment::Literal(text) | Segment::Optional(text) => Some((segment, *text)),
+        Segment::Field | Segment::List(_) => None,
+    });
+    match literals.next() {
+        None => Some(rest.len()),
+        Some((Segment::Optional(optional), _)) => {
+            let required = literals
+                .find_map(|(segment, text)| matches!(segment, Segment::Literal(_)).then_some(text));
+            let Some(required) = required else {
+                return Some(rest.strip_suffix(optional).unwrap_or(rest).len());
+            };
+            let with_optional = rest.find(&format!("{optional}{required}"));
+            let without = rest.find(required);
+            with_optional.into_iter().chain(without).min()
+        }
+        Some((_, required)) => rest.find(required),
+    }
+}
+
+/// Parses a field found by [`match_template`], reporting failures at its place in `input`
+///
+/// # Errors
+///
+/// If the text of the field doesn't parse as a `T`
+pub fn parse_field<T>(
+    input: &str,
+    (offset, text): (usize, &str),
+    name: &str,
+) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.trim()
+        .parse()
+        .map_err(|e| Failure::new(offset, format!("a valid {name} ({e})")).locate(input))
+}
+
+/// Parses a [`Segment::List`] field found by [`match_template`], one value between each
+/// `separator`
+///
+/// # Errors
+///
+/// If any of the values doesn't parse as a `T`
+pub fn parse_list<T>(
+    input: &str,
+    (offset, text): (usize, &str),
+    separator: &str,
+    name: &str,
+) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut start = offset;
+    text.split(separator)
+        .map(|item| {
+            let value = parse_field(input, (start, item), name);
+            start += item.len() + separator.len();
+            value
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(unsigned::<u8>().parse("42"), Ok(42));
+        assert_eq!(signed::<i64>().parse("-17"), Ok(-17));
+        assert_eq!(signed::<i64>().parse("+3"), Ok(3));
+        let error = unsigned::<u8>().parse("300").unwrap_err();
+        assert_eq!(error.expected, "a number in range");
+        let error = unsigned::<u8>().parse("-1").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (1, "-1"));
+        let error = signed::<i32>().parse("12 13").unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (4, "end of input"));
+    }
+
+    #[test]
+    fn test_fields_and_lists() {
+        let register = line(labelled("Register A:", unsigned::<u64>()));
+        assert_eq!(register.parse("  Register A: 729  "), Ok(729));
+        let program = labelled("Program:", separated(unsigned::<u8>(), ","));
+        assert_eq!(program.parse("Program: 0,1, 5,4"), Ok(vec![0, 1, 5, 4]));
+        let error = program.parse("Program: 0,1,").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (14, "end of input"));
+        let error = program.parse("Programme: 0").unwrap_err();
+        assert_eq!(error.expected, "\"Program:\"");
+        let parse_program = |s| -> Result<Vec<u8>, crate::Error> { Ok(program.parse(s)?) };
+        let common = parse_program("Program: 0,1,x").unwrap_err();
+        assert_eq!(common.position(), (1, 14));
+        assert_eq!(
+            common.to_string(),
+            program.parse("Program: 0,1,x").unwrap_err().to_string()
+        );
+        let toggle = choice(&[("on", true), ("off", false)])
+            .skip(spaces())
+            .then(word());
+        assert_eq!(toggle.parse("off x=1"), Ok((false, "x=1".to_string())));
+        assert_eq!(
+            toggle.parse("toggle").unwrap_err().expected,
+            "one of \"on\", \"off\""
+        );
+        let maybe = literal("-").optional().then(unsigned::<u8>());
+        assert_eq!(maybe.parse("-5"), Ok((Some(()), 5)));
+        assert_eq!(maybe.parse("5"), Ok((None, 5)));
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let input = "1 2
+                     3 4
+
+                     5";
+        let numbers = blocks(lines(separated(unsigned::<u32>(), " ")));
+        assert_eq!(
+            numbers.parse(input),
+            Ok(vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5]]])
+        );
+        let error = blocks(lines(unsigned::<u32>())).parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.expected, "end of line");
+        let error = lines(unsigned::<u32>()).parse(input).unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(lines(word()).parse("").unwrap(), Vec::<String>::new());
+        let error = blocks(spaces()).parse(input).unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (1, "a block"));
+        let dashes = separated(literal("-").optional(), " ");
+        assert_eq!(
+            dashes.parse_at("- -  x", 0),
+            Ok((vec![Some(()), Some(())], 3))
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "#.#
+                     ..#
+
+                     ##";
+        let walls = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grids = blocks(grid(walls)).parse(input).unwrap();
+        assert_eq!(grids.len(), 2);
+        assert_eq!(grids[0], [[true, false, true], [false, false, true]]);
+        let error = grid(walls).parse("#.#\n.x#").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 2, "x#")
+        );
+        let error = grid(walls).parse("#.#\n.#").unwrap_err();
+        assert_eq!(error.expected, "a row as wide as the first one");
+    }
+
+    #[test]
+    fn test_match_template() {
+        use Segment::{Field, List, Literal, Optional};
+        let template = [
+            Field,
+            Literal(" can fly "),
+            Field,
+            Literal(" km/s for "),
+            Field,
+            Literal(" second"),
+            Optional("s"),
+            Literal("."),
+        ];
+        let line = "  Comet can fly 14 km/s for 1 second.";
+        let fields = match_template(line, &template).unwrap();
+        assert_eq!(fields, [(2, "Comet"), (16, "14"), (28, "1")]);
+        assert_eq!(parse_field::<u32>(line, fields[1], "speed"), Ok(14));
+        let line = "Comet can fly x km/s for 10 seconds.";
+        let fields = match_template(line, &template).unwrap();
+        let error = parse_field::<u32>(line, fields[1], "speed").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (15, "x"));
+        assert_eq!(
+            error.expected,
+            "a valid speed (invalid digit found in string)"
+        );
+        let error = match_template("Comet can run", &template).unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (14, "\" can fly \"")
+        );
+        // A field right before an optional suffix at the end
+        let template = [Field, Literal(" unit"), Optional("s")];
+        assert_eq!(match_template("3 units", &template), Ok(vec![(0, "3")]));
+        assert_eq!(match_template("1 unit", &template), Ok(vec![(0, "1")]));
+        let template = [Literal("{"), List(","), Literal("}")];
+        let line = "{3, 5,4}";
+        let fields = match_template(line, &template).unwrap();
+        assert_eq!(
+            parse_list(line, fields[0], ",", "jolt"),
+            Ok(vec![3_u8, 5, 4])
+        );
+        let line = "{3, 5,x}";
+        let fields = match_template(line, &template).unwrap();
+        let error = parse_list::<u8>(line, fields[0], ",", "jolt").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (7, "x}"));
+    }
+}
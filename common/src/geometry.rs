@@ -0,0 +1,579 @@
+//! Segments, rays and polygons over integer [`Point`]s, with exact arithmetic.
+//!
+//! Nothing here goes through floats: crossings are decided with cross products, and the points
+//! halfway between lattice points are handled by doubling the coordinates.
+use crate::coords::Point;
+use crate::math::polygon;
+use num::integer;
+
+/// The z component of the cross product of `a` and `b`: positive if `b` is clockwise from `a`
+/// on screen (where y goes down)
+const fn cross(a: Point, b: Point) -> isize {
+    a.x * b.y - a.y * b.x
+}
+
+const fn dot(a: Point, b: Point) -> isize {
+    a.x * b.x + a.y * b.y
+}
+
+/// The side of the line through `a` and `b` that `p` is on, as the sign of a cross product
+const fn orientation(a: Point, b: Point, p: Point) -> isize {
+    cross(
+        Point {
+            x: b.x - a.x,
+            y: b.y - a.y,
+        },
+        Point {
+            x: p.x - a.x,
+            y: p.y - a.y,
+        },
+    )
+    .signum()
+}
+
+/// A line segment between two points, both ends included
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Where two segments meet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intersection {
+    /// They cross or touch at a single point
+    Point(Point),
+    /// They are collinear and share this part, which goes in the direction of the first one
+    Overlap(Segment),
+}
+
+impl Segment {
+    #[must_use]
+    pub const fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub const fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    #[must_use]
+    pub const fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// Horizontal or vertical
+    #[must_use]
+    pub const fn is_axis_aligned(&self) -> bool {
+        self.is_horizontal() || self.is_vertical()
+    }
+
+    /// At exactly 45 degrees, like the vents of 2021 day 5
+    #[must_use]
+    pub const fn is_diagonal(&self) -> bool {
+        let (dx, dy) = (self.end.x - self.start.x, self.end.y - self.start.y);
+        dx != 0 && dx.abs() == dy.abs()
+    }
+
+    /// The offset from `start` to `end`
+    #[must_use]
+    pub fn delta(&self) -> Point {
+        self.end - self.start
+    }
+
+    /// The smallest step from one lattice point of the segment to the next, or `(0, 0)` if the
+    /// segment is a single point
+    #[must_use]
+    pub fn step(&self) -> Point {
+        let delta = self.delta();
+        match self.len() {
+            0 => Point::default(),
+            n => delta / n,
+        }
+    }
+
+    /// The number of steps between lattice points from `start` to `end`. This is the Manhattan
+    /// length of horizontal and vertical segments, and the Chebyshev length of diagonal ones.
+    #[must_use]
+    pub fn len(&self) -> isize {
+        let delta = self.delta();
+        integer::gcd(delta.x, delta.y)
+    }
+
+    /// Whether the segment is a single point
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The lattice points on the segment, from `start` to `end`. For horizontal, vertical and
+    /// diagonal segments these are all the grid cells they cover.
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::Point;
+    /// use advent_of_code_common::geometry::Segment;
+    /// let segment = Segment::new(Point { x: 0, y: 0 }, Point { x: 6, y: 3 });
+    /// let points: Vec<_> = segment.points().map(|p| (p.x, p.y)).collect();
+    /// assert_eq!(points, [(0, 0), (2, 1), (4, 2), (6, 3)]);
+    /// ```
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<> {
+        let (start, step) = (self.start, self.step());
+        (0..=self.len()).map(move |i| start + step * i)
+    }
+
+    /// The same segment, going the other way
+    #[must_use]
+    pub const fn reverse(&self) -> Self {
+        Self::new(self.end, self.start)
+    }
+
+    /// Whether `p` is on the segment, ends included
+    #[must_use]
+    pub fn contains(&self, p: &Point) -> bool {
+        orientation(self.start, self.end, *p) == 0
+            && (self.start.x.min(self.end.x)..=self.start.x.max(self.end.x)).contains(&p.x)
+            && (self.start.y.min(self.end.y)..=self.start.y.max(self.end.y)).contains(&p.y)
+    }
+
+    /// Whether the segments share at least one point, even if it is not a lattice point
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+        let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+        if o1 * o2 < 0 && o3 * o4 < 0 {
+            return true;
+        }
+        self.contains(&c) || self.contains(&d) || other.contains(&a) || other.contains(&b)
+    }
+
+    /// Whether the segments cross at a single point that is not the end of either of them
+    #[must_use]
+    pub const fn crosses(&self, other: &Self) -> bool {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        orientation(a, b, c) * orientation(a, b, d) < 0
+            && orientation(c, d, a) * orientation(c, d, b) < 0
+    }
+
+    /// Where the segments meet.
+    ///
+    /// Segments that cross between lattice points, like the two diagonals of a 1x1 square, have
+    /// no [`Intersection::Point`] to report and return `None`. Use [`Self::intersects`] for
+    /// those.
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::Point;
+    /// use advent_of_code_common::geometry::{Intersection, Segment};
+    /// let a = Segment::new(Point { x: 0, y: 0 }, Point { x: 8, y: 0 });
+    /// let b = Segment::new(Point { x: 3, y: -2 }, Point { x: 3, y: 5 });
+    /// assert_eq!(a.intersection(&b), Some(Intersection::Point(Point { x: 3, y: 0 })));
+    /// let c = Segment::new(Point { x: 10, y: 0 }, Point { x: 5, y: 0 });
+    /// let overlap = Segment::new(Point { x: 5, y: 0 }, Point { x: 8, y: 0 });
+    /// assert_eq!(a.intersection(&c), Some(Intersection::Overlap(overlap)));
+    /// ```
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Intersection> {
+        if other.is_empty() {
+            return self
+                .contains(&other.start)
+                .then_some(Intersection::Point(other.start));
+        }
+        if self.is_empty() {
+            return other
+                .contains(&self.start)
+                .then_some(Intersection::Point(self.start));
+        }
+        let (d1, d2) = (self.delta(), other.delta());
+        let offset = other.start - self.start;
+        let denominator = cross(d1, d2);
+        if denominator == 0 {
+            if cross(offset, d1) != 0 {
+                return None; // Parallel
+            }
+            // Collinear: order both segments along `self` and keep what they share
+            let along = |p: &Point| dot(*p - self.start, d1);
+            let (first, last) = if along(&other.start) <= along(&other.end) {
+                (other.start, other.end)
+            } else {
+                (other.end, other.start)
+            };
+            let start = if along(&first) > 0 { first } else { self.start };
+            let end = if along(&last) < along(&self.end) {
+                last
+            } else {
+                self.end
+            };
+            return match along(&start).cmp(&along(&end)) {
+                std::cmp::Ordering::Greater => None,
+                std::cmp::Ordering::Equal => Some(Intersection::Point(start)),
+                std::cmp::Ordering::Less => Some(Intersection::Overlap(Self::new(start, end))),
+            };
+        }
+        // `self.start + d1 * t / denominator` and `other.start + d2 * u / denominator`
+        let sign = denominator.signum();
+        let (t, u) = (cross(offset, d2) * sign, cross(offset, d1) * sign);
+        let denominator = denominator.abs();
+        if !(0..=denominator).contains(&t) || !(0..=denominator).contains(&u) {
+            return None;
+        }
+        let (x, y) = (d1.x * t, d1.y * t);
+        (x % denominator == 0 && y % denominator == 0).then(|| {
+            Intersection::Point(
+                self.start
+                    + Point {
+                        x: x / denominator,
+                        y: y / denominator,
+                    },
+            )
+        })
+    }
+}
+
+impl From<(Point, Point)> for Segment {
+    fn from((start, end): (Point, Point)) -> Self {
+        Self::new(start, end)
+    }
+}
+
+/// A half-line from `origin`, in the direction of `step`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub origin: Point,
+    pub step: Point,
+}
+
+impl Ray {
+    /// A ray through `through`, with the step reduced to the next lattice point, so that `(0, 0)`
+    /// to `(4, 2)` steps by `(2, 1)`
+    #[must_use]
+    pub fn new(origin: Point, through: Point) -> Self {
+        Self {
+            origin,
+            step: Segment::new(origin, through).step(),
+        }
+    }
+
+    /// The lattice points of the ray, starting with the origin. Endless, unless the step is
+    /// `(0, 0)`, in which case it is just the origin.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<> {
+        let (origin, step) = (self.origin, self.step);
+        let count = if step == Point::default() {
+            1
+        } else {
+            usize::MAX
+        };
+        (0_isize..).map(move |i| origin + step * i).take(count)
+    }
+
+    /// Whether the ray passes through `p`
+    #[must_use]
+    pub fn contains(&self, p: &Point) -> bool {
+        let offset = *p - self.origin;
+        cross(self.step, offset) == 0 && dot(self.step, offset) >= 0
+    }
+
+    /// Whether the ray hits the segment, even between lattice points
+    #[must_use]
+    pub fn intersects(&self, segment: &Segment) -> bool {
+        let (d, f) = (self.step, segment.delta());
+        let offset = segment.start - self.origin;
+        let denominator = cross(d, f);
+        if denominator == 0 {
+            // Parallel: only collinear segments can be hit, at their nearest end
+            return self.contains(&segment.start) || self.contains(&segment.end);
+        }
+        let sign = denominator.signum();
+        let (t, u) = (cross(offset, f) * sign, cross(offset, d) * sign);
+        t >= 0 && (0..=denominator.abs()).contains(&u)
+    }
+}
+
+/// A simple polygon: its vertices in order, clockwise or counter-clockwise, without repeating the
+/// first one at the end. Points on the edges count as inside.
+///
+/// The areas and lattice point counts are those of [`crate::math::polygon`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    pub vertices: Vec<Point>,
+}
+
+impl Polygon {
+    #[must_use]
+    pub const fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    /// The edges, from each vertex to the next, the last one closing the polygon
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices
+            .iter()
+            .zip(next)
+            .map(|(&start, &end)| Segment::new(start, end))
+    }
+
+    /// Twice the signed area, see [`polygon::twice_signed_area`]
+    #[must_use]
+    pub fn twice_signed_area(&self) -> isize {
+        polygon::twice_signed_area(&self.vertices)
+    }
+
+    /// The area, rounded down if it ends in a half
+    #[must_use]
+    pub fn area(&self) -> isize {
+        polygon::area(&self.vertices)
+    }
+
+    /// The number of lattice points on the edges
+    #[must_use]
+    pub fn boundary_points(&self) -> isize {
+        polygon::boundary_points(&self.vertices)
+    }
+
+    /// The number of lattice points strictly inside
+    #[must_use]
+    pub fn interior_points(&self) -> isize {
+        polygon::interior_points(&self.vertices)
+    }
+
+    /// The number of lattice points inside or on the edges
+    #[must_use]
+    pub fn lattice_points(&self) -> isize {
+        polygon::lattice_points(&self.vertices)
+    }
+
+    /// Whether `p` is on one of the edges
+    #[must_use]
+    pub fn on_boundary(&self, p: &Point) -> bool {
+        self.edges().any(|edge| edge.contains(p))
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::Point;
+    /// use advent_of_code_common::geometry::Polygon;
+    /// let triangle = Polygon::from_iter([(0, 0), (4, 0), (0, 4)].map(|(x, y)| Point { x, y }));
+    /// assert!(triangle.contains(&Point { x: 1, y: 1 }));
+    /// assert!(triangle.contains(&Point { x: 2, y: 2 }));
+    /// assert!(!triangle.contains(&Point { x: 3, y: 2 }));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, p: &Point) -> bool {
+        self.contains_scaled(*p, 1)
+    }
+
+    /// Whether `p` is inside or on the boundary of the polygon scaled up by `scale`, so that
+    /// doubling checks the points halfway between lattice points
+    fn contains_scaled(&self, p: Point, scale: isize) -> bool {
+        let mut inside = false;
+        for edge in self.edges() {
+            let (a, b) = (edge.start * scale, edge.end * scale);
+            if Segment::new(a, b).contains(&p) {
+                return true;
+            }
+            // Cast a ray to the east and count the edges it crosses, each one once by taking
+            // their lower end but not their upper one
+            if (a.y > p.y) != (b.y > p.y) {
+                let side = cross(b - a, p - a);
+                if (side > 0) == (b.y > a.y) {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Whether the whole segment is inside the polygon (touching the boundary is allowed)
+    #[must_use]
+    pub fn contains_segment(&self, segment: &Segment) -> bool {
+        if self.edges().any(|edge| edge.crosses(segment)) {
+            return false;
+        }
+        // Anything else that reaches the segment is a vertex, so in between two of them the
+        // segment is either all inside or all outside
+        let along = |p: &Point| dot(*p - segment.start, segment.delta());
+        let mut stops: Vec<Point> = self
+            .vertices
+            .iter()
+            .filter(|v| segment.contains(v))
+            .copied()
+            .chain([segment.start, segment.end])
+            .collect();
+        stops.sort_unstable_by_key(along);
+        stops.dedup();
+        self.contains(&segment.start)
+            && self.contains(&segment.end)
+            && stops
+                .windows(2)
+                .all(|w| self.contains_scaled(w[0] + w[1], 2))
+    }
+
+    /// Whether the axis-aligned rectangle with opposite corners `a` and `b` is inside the polygon
+    /// (touching the boundary is allowed).
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::coords::Point;
+    /// use advent_of_code_common::geometry::Polygon;
+    /// // An L shape
+    /// let shape = [(0, 0), (6, 0), (6, 2), (2, 2), (2, 5), (0, 5)];
+    /// let shape = Polygon::from_iter(shape.map(|(x, y)| Point { x, y }));
+    /// assert!(shape.contains_rectangle(&Point { x: 0, y: 0 }, &Point { x: 6, y: 2 }));
+    /// assert!(shape.contains_rectangle(&Point { x: 2, y: 0 }, &Point { x: 0, y: 5 }));
+    /// assert!(!shape.contains_rectangle(&Point { x: 0, y: 0 }, &Point { x: 6, y: 5 }));
+    /// ```
+    #[must_use]
+    pub fn contains_rectangle(&self, a: &Point, b: &Point) -> bool {
+        let min = Point {
+            x: a.x.min(b.x),
+            y: a.y.min(b.y),
+        };
+        let max = Point {
+            x: a.x.max(b.x),
+            y: a.y.max(b.y),
+        };
+        let corners = [
+            min,
+            Point { x: max.x, y: min.y },
+            max,
+            Point { x: min.x, y: max.y },
+        ];
+        if min.x == max.x || min.y == max.y {
+            return self.contains_segment(&Segment::new(min, max));
+        }
+        // An edge inside the rectangle would leave the outside of the polygon next to it
+        let reaches_inside = |edge: Segment| {
+            let (lo, hi) = (edge.start.x.min(edge.end.x), edge.start.x.max(edge.end.x));
+            let (top, bottom) = (edge.start.y.min(edge.end.y), edge.start.y.max(edge.end.y));
+            let sides = corners.map(|c| orientation(edge.start, edge.end, c));
+            hi > min.x
+                && lo < max.x
+                && bottom > min.y
+                && top < max.y
+                && !(sides.iter().all(|&s| s >= 0) || sides.iter().all(|&s| s <= 0))
+        };
+        !self.edges().any(reaches_inside)
+            && corners
+                .iter()
+                .zip(corners.iter().cycle().skip(1))
+                .all(|(&c, &d)| self.contains_segment(&Segment::new(c, d)))
+            && self.contains_scaled(min + max, 2)
+    }
+}
+
+impl From<Vec<Point>> for Polygon {
+    fn from(vertices: Vec<Point>) -> Self {
+        Self::new(vertices)
+    }
+}
+
+impl FromIterator<Point> for Polygon {
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    fn segment(a: (isize, isize), b: (isize, isize)) -> Segment {
+        Segment::new(a.into(), b.into())
+    }
+
+    fn polygon(vertices: &[(isize, isize)]) -> Polygon {
+        vertices.iter().map(Point::from).collect()
+    }
+
+    #[test]
+    fn segments() {
+        let diagonal = segment((5, 5), (8, 2));
+        assert!(diagonal.is_diagonal());
+        assert_eq!(diagonal.len(), 3);
+        let points: Vec<(isize, isize)> = diagonal.points().map(Into::into).collect();
+        assert_eq!(points, [(5, 5), (6, 4), (7, 3), (8, 2)]);
+        assert!(diagonal.contains(&Point { x: 7, y: 3 }));
+        assert!(!diagonal.contains(&Point { x: 9, y: 1 }));
+        assert_eq!(segment((1, 1), (1, 1)).points().count(), 1);
+
+        // The diagonals of a square cross in the middle, which is not a lattice point
+        let (a, b) = (segment((0, 0), (1, 1)), segment((1, 0), (0, 1)));
+        assert!(a.intersects(&b) && a.crosses(&b));
+        assert_eq!(a.intersection(&b), None);
+        // Touching at an end is not crossing
+        let (a, b) = (segment((0, 0), (4, 0)), segment((4, 0), (4, 4)));
+        assert!(a.intersects(&b) && !a.crosses(&b));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Intersection::Point(Point { x: 4, y: 0 }))
+        );
+        assert_eq!(a.intersection(&segment((5, 0), (9, 0))), None);
+        assert_eq!(a.intersection(&segment((0, 1), (4, 1))), None);
+        assert_eq!(
+            a.reverse().intersection(&segment((-2, 0), (2, 0))),
+            Some(Intersection::Overlap(segment((2, 0), (0, 0))))
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let ray = Ray::new(Point { x: 1, y: 1 }, Point { x: 5, y: 3 });
+        assert_eq!(ray.step, Point { x: 2, y: 1 });
+        assert_eq!(ray.points().nth(3), Some(Point { x: 7, y: 4 }));
+        assert!(ray.contains(&Point { x: 9, y: 5 }));
+        assert!(!ray.contains(&Point { x: -1, y: 0 }));
+        assert!(ray.intersects(&segment((6, 0), (6, 10))));
+        assert!(!ray.intersects(&segment((0, 0), (0, 10))));
+        assert!(ray.intersects(&segment((11, 6), (13, 7))));
+    }
+
+    #[test]
+    fn polygons() {
+        // A U shape, with a brute force check of every point and rectangle
+        let shape = polygon(&[
+            (0, 0),
+            (2, 0),
+            (2, 3),
+            (4, 3),
+            (4, 0),
+            (6, 0),
+            (6, 5),
+            (0, 5),
+        ]);
+        let inside = |x: isize, y: isize| {
+            (0..=6).contains(&x) && (3..=5).contains(&y)
+                || ((0..=2).contains(&x) || (4..=6).contains(&x)) && (0..=5).contains(&y)
+        };
+        for (x, y) in iproduct!(-1..8, -1..7) {
+            assert_eq!(shape.contains(&Point { x, y }), inside(x, y), "{x},{y}");
+        }
+        assert_eq!(
+            shape.lattice_points(),
+            iproduct!(-1..8, -1..7)
+                .filter(|&(x, y)| inside(x, y))
+                .count()
+                .try_into()
+                .unwrap()
+        );
+        let points: Vec<Point> = iproduct!(-1_isize..8, -1_isize..7)
+            .map(Point::from)
+            .collect();
+        for (a, b) in iproduct!(&points, &points) {
+            // The shape is made of whole unit squares, so a rectangle is inside exactly when
+            // all its lattice points are
+            let all_inside = iproduct!(a.x.min(b.x)..=a.x.max(b.x), a.y.min(b.y)..=a.y.max(b.y))
+                .all(|(x, y)| inside(x, y));
+            assert_eq!(shape.contains_rectangle(a, b), all_inside, "{a} {b}");
+        }
+        // A segment that leaves and comes back through a vertex
+        let notch = polygon(&[(0, 0), (4, 0), (4, 4), (2, 2), (0, 4)]);
+        assert!(!notch.contains_segment(&segment((0, 3), (4, 3))));
+        assert!(notch.contains_segment(&segment((0, 2), (4, 2))));
+    }
+}
@@ -1,6 +1,11 @@
 pub use crate::coords::{Coords, Direction, Point};
 use std::collections::BTreeMap;
 
+mod compressed;
+mod pattern;
+
+pub use compressed::{CompressedGrid, Region};
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct GridCell<T = char> {
     pub point: Point,
@@ -115,11 +120,18 @@ impl<T> std::ops::DerefMut for Grid<T> {
     }
 }
 
+/// Parse a grid with one cell per character, where `(0, 0)` is the first character of the first
+/// line.
+///
+/// # Errors
+///
+/// The error of the first character that is not a valid `T`, moved to its line and column
 impl<T> std::str::FromStr for Grid<T>
 where
     T: TryFrom<char>,
+    T::Error: Into<crate::Error>,
 {
-    type Err = &'static str;
+    type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut grid = Self {
             x_range: 0..0,
@@ -128,15 +140,16 @@ where
         };
         let mut height = 0;
         let mut width = 0;
-        for (y, line) in s.lines().enumerate() {
+        for (y, line) in (0..).zip(s.lines()) {
             height += 1;
             let mut line_width = 0;
-            for (x, c) in line.chars().enumerate() {
+            for (x, c) in (0..).zip(line.chars()) {
                 line_width += 1;
-                let x = isize::try_from(x).map_err(|_| "Invalid x coordinate")?;
-                let y = isize::try_from(y).map_err(|_| "Invalid y coordinate")?;
-                let point = (x, y).as_point();
-                let data = T::try_from(c).map_err(|_| "Invalid character in input")?;
+                let point = Point { x, y };
+                let data = T::try_from(c).map_err(|e| {
+                    let (line, column) = (y.unsigned_abs() + 1, x.unsigned_abs() + 1);
+                    e.into().at(line, column)
+                })?;
                 grid.insert(point, GridCell::new(&point, data));
             }
             width = width.max(line_width);
@@ -235,30 +248,21 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Replaces the grid with `f(grid)` until that stops changing it, or for at most `limit`
+    /// steps, and returns the number of steps taken (including the last one, that changed nothing)
     pub fn apply_steps_until(&mut self, f: impl Fn(&Self) -> Self, limit: Option<usize>) -> usize
     where
         T: PartialEq,
     {
-        let mut steps = 0;
-        loop {
-            let new_grid = f(self);
-            steps += 1;
-            if new_grid == *self || limit.is_some_and(|limit| steps >= limit) {
-                *self = new_grid;
-                return steps;
-            }
-            *self = new_grid;
-        }
+        apply_steps_until(self, f, limit)
     }
 
+    /// Replaces the grid with `f(grid)`, and returns whether that changed anything
     pub fn apply_step(&mut self, f: impl Fn(&Self) -> Self) -> bool
     where
         T: PartialEq,
     {
-        let new_grid = f(self);
-        let changed = new_grid != *self;
-        *self = new_grid;
-        changed
+        apply_step(self, f)
     }
 
     /// Get the neighbor cell in a given [`Direction`], at distance `n`.
@@ -470,6 +474,30 @@ where
     }
 }
 
+/// Replaces `state` with `f(state)` until that stops changing it, or for at most `limit` steps,
+/// shared by the grids that evolve as a whole
+pub(crate) fn apply_steps_until<G: PartialEq>(
+    state: &mut G,
+    f: impl Fn(&G) -> G,
+    limit: Option<usize>,
+) -> usize {
+    let mut steps = 0;
+    loop {
+        steps += 1;
+        if !apply_step(state, &f) || limit.is_some_and(|limit| steps >= limit) {
+            return steps;
+        }
+    }
+}
+
+/// Replaces `state` with `f(state)`, and returns whether that changed anything
+pub(crate) fn apply_step<G: PartialEq>(state: &mut G, f: impl Fn(&G) -> G) -> bool {
+    let new_state = f(state);
+    let changed = new_state != *state;
+    *state = new_state;
+    changed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -492,14 +520,14 @@ L.LLLLL.LL";
     }
 
     impl TryFrom<char> for State {
-        type Error = &'static str;
+        type Error = crate::Error;
 
         fn try_from(value: char) -> Result<Self, Self::Error> {
             match value {
                 '.' => Ok(Self::Floor),
                 'L' => Ok(Self::Empty),
                 '#' => Ok(Self::Occupied),
-                _ => Err("Invalid character in input"),
+                _ => Err(crate::Error::invalid_char(value, "one of '.', 'L', '#'")),
             }
         }
     }
@@ -510,6 +538,9 @@ L.LLLLL.LL";
         assert_eq!(grid.x_range, 0..10);
         assert_eq!(grid.y_range, 0..10);
         assert_eq!(grid.cells.len(), 100);
+        let error = "L.L\n.#x".parse::<Grid<State>>().unwrap_err();
+        assert_eq!(error.position(), (2, 3));
+        assert_eq!(error.expected(), "one of '.', 'L', '#'");
     }
 
     #[test]
@@ -0,0 +1,272 @@
+use super::{Grid, GridCell, Point};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+/// A cell of a [`CompressedGrid`], covering a whole rectangle of the original coordinates
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Region<T> {
+    pub data: T,
+    pub width: isize,
+    pub height: isize,
+}
+
+impl<T> Region<T> {
+    /// The number of original cells covered by the region
+    #[must_use]
+    pub const fn area(&self) -> isize {
+        self.width * self.height
+    }
+}
+
+/// A compact [`Grid`] for a few shapes spread over huge coordinates, like lagoon edges millions of
+/// meters long.
+///
+/// The original coordinates are cut at sorted distinct breakpoints: column `i` of the grid covers
+/// the original `x` values `xs[i]..xs[i + 1]`, and the same goes for rows and `ys`. Everything
+/// outside the first and last breakpoints is left out.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct CompressedGrid<T> {
+    pub xs: Vec<isize>,
+    pub ys: Vec<isize>,
+    pub grid: Grid<Region<T>>,
+}
+
+impl<T> CompressedGrid<T> {
+    /// A grid cut at the given breakpoints, in any order and with repeats, with `data` everywhere
+    pub fn new(
+        xs: impl IntoIterator<Item = isize>,
+        ys: impl IntoIterator<Item = isize>,
+        data: T,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut xs: Vec<isize> = xs.into_iter().collect();
+        let mut ys: Vec<isize> = ys.into_iter().collect();
+        for breakpoints in [&mut xs, &mut ys] {
+            breakpoints.sort_unstable();
+            breakpoints.dedup();
+        }
+        let mut cells = BTreeMap::new();
+        for (y, rows) in (0..).zip(ys.windows(2)) {
+            for (x, columns) in (0..).zip(xs.windows(2)) {
+                let point = Point { x, y };
+                let region = Region {
+                    data: data.clone(),
+                    width: columns[1] - columns[0],
+                    height: rows[1] - rows[0],
+                };
+                cells.insert(point, GridCell::new(&point, region));
+            }
+        }
+        let bands = |breakpoints: &[isize]| {
+            isize::try_from(breakpoints.len().saturating_sub(1)).unwrap_or(isize::MAX)
+        };
+        let grid = Grid {
+            x_range: 0..bands(&xs),
+            y_range: 0..bands(&ys),
+            cells,
+        };
+        Self { xs, ys, grid }
+    }
+
+    /// A grid where each of the points is a cell of its own, with room to go around all of them.
+    ///
+    /// Each point is cut out as a 1x1 region, and there is a 1 wide border of regions around the
+    /// bounding box, so that a flood fill from `(0, 0)` reaches everything that is outside.
+    pub fn around<'p>(points: impl IntoIterator<Item = &'p Point>, data: T) -> Self
+    where
+        T: Clone,
+    {
+        let points: Vec<&Point> = points.into_iter().collect();
+        let border = |values: Vec<isize>| {
+            let min = values.iter().min().copied().unwrap_or_default();
+            let max = values.iter().max().copied().unwrap_or_default();
+            values
+                .into_iter()
+                .flat_map(|v| [v, v + 1])
+                .chain([min - 1, max + 2])
+                .collect::<Vec<_>>()
+        };
+        Self::new(
+            border(points.iter().map(|p| p.x).collect()),
+            border(points.iter().map(|p| p.y).collect()),
+            data,
+        )
+    }
+
+    /// The column of the compact grid that covers the original `x`
+    #[must_use]
+    pub fn column(&self, x: isize) -> Option<isize> {
+        band(&self.xs, x)
+    }
+
+    /// The row of the compact grid that covers the original `y`
+    #[must_use]
+    pub fn row(&self, y: isize) -> Option<isize> {
+        band(&self.ys, y)
+    }
+
+    /// The cell of the compact grid that covers the original point
+    #[must_use]
+    pub fn compressed(&self, p: &Point) -> Option<Point> {
+        Some(Point {
+            x: self.column(p.x)?,
+            y: self.row(p.y)?,
+        })
+    }
+
+    /// The original top left corner of a cell of the compact grid
+    #[must_use]
+    pub fn original(&self, cell: &Point) -> Option<Point> {
+        Some(Point {
+            x: *self.xs.get(usize::try_from(cell.x).ok()?)?,
+            y: *self.ys.get(usize::try_from(cell.y).ok()?)?,
+        })
+    }
+
+    /// The region at a cell of the compact grid
+    #[must_use]
+    pub fn get(&self, cell: &Point) -> Option<&Region<T>> {
+        self.grid.get(cell).map(|cell| &cell.data)
+    }
+
+    /// The region at a cell of the compact grid
+    pub fn get_mut(&mut self, cell: &Point) -> Option<&mut Region<T>> {
+        self.grid.get_mut(cell).map(|cell| &mut cell.data)
+    }
+
+    /// Set `data` in every region that overlaps the original rectangle with opposite corners `a`
+    /// and `b`, both included.
+    ///
+    /// This is exact when the rectangle starts at breakpoints and ends right before them, as for
+    /// the segments between points given to [`Self::around`].
+    pub fn fill_rectangle(&mut self, a: &Point, b: &Point, data: &T)
+    where
+        T: Clone,
+    {
+        let columns = overlapping(&self.xs, a.x.min(b.x), a.x.max(b.x));
+        let rows = overlapping(&self.ys, a.y.min(b.y), a.y.max(b.y));
+        let (Some(columns), Some(rows)) = (columns, rows) else {
+            return;
+        };
+        for y in rows {
+            for x in columns.clone() {
+                if let Some(region) = self.get_mut(&Point { x, y }) {
+                    region.data = data.clone();
+                }
+            }
+        }
+    }
+
+    /// The cells of the compact grid that can be reached from `start` by orthogonal steps,
+    /// through the regions where `passable` holds
+    pub fn flood_fill(&self, start: &Point, passable: impl Fn(&T) -> bool) -> BTreeSet<Point> {
+        let mut reached = BTreeSet::new();
+        let mut queue: Vec<Point> = self
+            .get(start)
+            .filter(|region| passable(&region.data))
+            .map(|_| *start)
+            .into_iter()
+            .collect();
+        while let Some(cell) = queue.pop() {
+            if !reached.insert(cell) {
+                continue;
+            }
+            queue.extend(
+                self.grid
+                    .neighbors_orthogonal(&cell)
+                    .into_iter()
+                    .flatten()
+                    .filter(|n| passable(&n.data.data) && !reached.contains(&n.point))
+                    .map(|n| n.point),
+            );
+        }
+        reached
+    }
+
+    /// The total original area of the given cells of the compact grid
+    pub fn area_of<'p>(&self, cells: impl IntoIterator<Item = &'p Point>) -> isize {
+        cells
+            .into_iter()
+            .filter_map(|cell| self.get(cell))
+            .map(Region::area)
+            .sum()
+    }
+
+    /// The total original area of the regions where `f` holds
+    pub fn area_where(&self, f: impl Fn(&T) -> bool) -> isize {
+        self.grid
+            .values()
+            .filter(|cell| f(&cell.data.data))
+            .map(|cell| cell.data.area())
+            .sum()
+    }
+}
+
+/// The index of the band between two breakpoints that contains `v`
+fn band(breakpoints: &[isize], v: isize) -> Option<isize> {
+    let next = breakpoints.partition_point(|&b| b <= v);
+    (next > 0 && next < breakpoints.len())
+        .then(|| isize::try_from(next - 1).ok())
+        .flatten()
+}
+
+/// The bands that overlap the original values `lo..=hi`
+fn overlapping(breakpoints: &[isize], lo: isize, hi: isize) -> Option<RangeInclusive<isize>> {
+    let (&first, &last) = (breakpoints.first()?, breakpoints.last()?);
+    if hi < first || lo >= last {
+        return None;
+    }
+    Some(band(breakpoints, lo.max(first))?..=band(breakpoints, hi.min(last - 1))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress() {
+        let grid = CompressedGrid::new([0, 10, 1_000_000, 10], [-5, 5], ());
+        assert_eq!(grid.xs, [0, 10, 1_000_000]);
+        assert_eq!((grid.grid.width(), grid.grid.height()), (2, 1));
+        assert_eq!(grid.column(9), Some(0));
+        assert_eq!(grid.column(10), Some(1));
+        assert_eq!(grid.column(1_000_000), None);
+        assert_eq!(grid.row(-6), None);
+        assert_eq!(
+            grid.get(&Point { x: 1, y: 0 }).map(Region::area),
+            Some(999_990 * 10)
+        );
+        assert_eq!(
+            grid.original(&Point { x: 1, y: 0 }),
+            Some(Point { x: 10, y: -5 })
+        );
+        assert_eq!(grid.area_where(|()| true), 1_000_000 * 10);
+    }
+
+    #[test]
+    fn lagoon() {
+        // Two nested square trenches, like the edges of the 2023 day 18 lagoon
+        let square = |min: isize, max: isize| {
+            [(min, min), (max, min), (max, max), (min, max)].map(Point::from)
+        };
+        let (outer, inner) = (square(0, 999), square(2, 997));
+        let mut grid = CompressedGrid::around(outer.iter().chain(&inner), false);
+        assert_eq!(grid.grid.width(), 9);
+        for corners in [outer, inner] {
+            for (a, b) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+                grid.fill_rectangle(a, b, &true);
+            }
+        }
+        assert_eq!(grid.area_where(|&dug| dug), 4 * 999 + 4 * 995);
+        let outside = grid.flood_fill(&Point::default(), |&dug| !dug);
+        let inside = grid.flood_fill(
+            &grid.compressed(&Point { x: 500, y: 500 }).unwrap(),
+            |&dug| !dug,
+        );
+        assert_eq!(grid.area_of(&inside), 994 * 994);
+        let total = 1002 * 1002;
+        assert_eq!(total - grid.area_of(&outside), 1000 * 1000);
+    }
+}
@@ -0,0 +1,232 @@
+use super::{Grid, GridCell, Point};
+use crate::symmetry::{Orient, Orientation};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+impl<T> Grid<T> {
+    /// The cells in `x_range` and `y_range`, clipped to the grid, moved so that the result starts
+    /// at `(0, 0)` like a pattern would
+    #[must_use]
+    pub fn subgrid(&self, x_range: Range<isize>, y_range: Range<isize>) -> Self
+    where
+        T: Clone,
+    {
+        let x_range = x_range.start.max(self.x_range.start)..x_range.end.min(self.x_range.end);
+        let y_range = y_range.start.max(self.y_range.start)..y_range.end.min(self.y_range.end);
+        let (x0, y0) = (x_range.start, y_range.start);
+        let mut cells = BTreeMap::new();
+        for y in y_range.clone() {
+            for x in x_range.clone() {
+                if let Some(cell) = self.get(&Point { x, y }) {
+                    let point = Point {
+                        x: x - x0,
+                        y: y - y0,
+                    };
+                    cells.insert(point, GridCell::new(&point, cell.data.clone()));
+                }
+            }
+        }
+        Self {
+            x_range: 0..x_range.len().try_into().unwrap_or_default(),
+            y_range: 0..y_range.len().try_into().unwrap_or_default(),
+            cells,
+        }
+    }
+
+    /// Every `width` by `height` part of the grid, with its top left corner, row by row
+    pub fn windows(&self, width: isize, height: isize) -> impl Iterator<Item = (Point, Self)> + '_
+    where
+        T: Clone,
+    {
+        let xs = self.x_range.start..=self.x_range.end - width;
+        let ys = self.y_range.start..=self.y_range.end - height;
+        ys.flat_map(move |y| xs.clone().map(move |x| Point { x, y }))
+            .map(move |p| (p, self.subgrid(p.x..p.x + width, p.y..p.y + height)))
+    }
+
+    /// The data of each row, from the top. Missing cells are left out.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.y_range.clone().map(|y| {
+            self.line(
+                Point {
+                    x: self.x_range.start,
+                    y,
+                },
+                Point { x: 1, y: 0 },
+            )
+        })
+    }
+
+    /// The data of each column, from the left. Missing cells are left out.
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.x_range.clone().map(|x| {
+            self.line(
+                Point {
+                    x,
+                    y: self.y_range.start,
+                },
+                Point { x: 0, y: 1 },
+            )
+        })
+    }
+
+    /// The data of each diagonal going down to the right, from the bottom left corner to the top
+    /// right one
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        let (x0, y0) = (self.x_range.start, self.y_range.start);
+        let left = self.y_range.clone().rev().map(move |y| Point { x: x0, y });
+        let top = self
+            .x_range
+            .clone()
+            .skip(1)
+            .map(move |x| Point { x, y: y0 });
+        left.chain(top)
+            .map(|start| self.line(start, Point { x: 1, y: 1 }))
+    }
+
+    /// The data of each diagonal going down to the left, from the top left corner to the bottom
+    /// right one
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        let (x1, y0) = (self.x_range.end - 1, self.y_range.start);
+        let top = self.x_range.clone().map(move |x| Point { x, y: y0 });
+        let right = self
+            .y_range
+            .clone()
+            .skip(1)
+            .map(move |y| Point { x: x1, y });
+        top.chain(right)
+            .map(|start| self.line(start, Point { x: -1, y: 1 }))
+    }
+
+    fn line(&self, start: Point, step: Point) -> Vec<&T> {
+        std::iter::successors(Some(start), |&p| Some(p + step))
+            .take_while(|p| self.is_in_range(p))
+            .filter_map(|p| self.get(&p).map(|cell| &cell.data))
+            .collect()
+    }
+
+    /// The top left corners of the places where `pattern` matches, row by row. The cells of the
+    /// pattern that hold `wildcard`, or that are missing, match anything.
+    #[must_use]
+    pub fn find_pattern(&self, pattern: &Self, wildcard: Option<&T>) -> Vec<Point>
+    where
+        T: PartialEq,
+    {
+        let (px, py) = (pattern.x_range.start, pattern.y_range.start);
+        let wanted: Vec<(Point, &T)> = pattern
+            .values()
+            .filter(|cell| wildcard != Some(&cell.data))
+            .map(|cell| {
+                let offset = Point {
+                    x: cell.point.x - px,
+                    y: cell.point.y - py,
+                };
+                (offset, &cell.data)
+            })
+            .collect();
+        let xs = self.x_range.start..=self.x_range.end - pattern.width();
+        let ys = self.y_range.start..=self.y_range.end - pattern.height();
+        ys.flat_map(|y| xs.clone().map(move |x| Point { x, y }))
+            .filter(|&corner| {
+                wanted.iter().all(|&(offset, data)| {
+                    self.get(&(corner + offset))
+                        .is_some_and(|cell| &cell.data == data)
+                })
+            })
+            .collect()
+    }
+
+    /// Like [`Self::find_pattern`], for each distinct orientation of the pattern. Each match comes
+    /// with the first [`Orientation`] that gives that pattern.
+    #[must_use]
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &Self,
+        wildcard: Option<&T>,
+    ) -> Vec<(Point, Orientation)>
+    where
+        T: PartialEq + Clone,
+    {
+        let mut patterns: Vec<(Self, Orientation)> = Vec::with_capacity(8);
+        for orientation in Orientation::all() {
+            let oriented = pattern.orient(orientation);
+            if patterns.iter().all(|(p, _)| *p != oriented) {
+                patterns.push((oriented, orientation));
+            }
+        }
+        patterns
+            .iter()
+            .flat_map(|(oriented, orientation)| {
+                self.find_pattern(oriented, wildcard)
+                    .into_iter()
+                    .map(|p| (p, *orientation))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text<'a>(lines: impl Iterator<Item = Vec<&'a char>>) -> Vec<String> {
+        lines.map(|line| line.into_iter().collect()).collect()
+    }
+
+    #[test]
+    fn lines() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!(text(grid.rows()), ["abc", "def"]);
+        assert_eq!(text(grid.columns()), ["ad", "be", "cf"]);
+        assert_eq!(text(grid.diagonals()), ["d", "ae", "bf", "c"]);
+        assert_eq!(text(grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn subgrids() {
+        let grid: Grid<char> = "abcd\nefgh\nijkl".parse().unwrap();
+        let sub = grid.subgrid(1..3, 1..5);
+        assert_eq!(sub.to_string(), "fg\njk");
+        assert_eq!((sub.x_range, sub.y_range), (0..2, 0..2));
+        assert_eq!(grid.subgrid(-1..1, -1..1).to_string(), "a");
+        let windows: Vec<(Point, String)> = grid
+            .windows(3, 2)
+            .map(|(p, window)| (p, window.to_string()))
+            .collect();
+        assert_eq!(
+            windows,
+            [
+                (Point { x: 0, y: 0 }, "abc\nefg".to_string()),
+                (Point { x: 1, y: 0 }, "bcd\nfgh".to_string()),
+                (Point { x: 0, y: 1 }, "efg\nijk".to_string()),
+                (Point { x: 1, y: 1 }, "fgh\njkl".to_string()),
+            ]
+        );
+        assert_eq!(grid.windows(5, 1).count(), 0);
+    }
+
+    #[test]
+    fn patterns() {
+        let grid: Grid<char> = "#..#\n.##.\n#..#\n.#..".parse().unwrap();
+        let pattern: Grid<char> = "#?\n?#".parse().unwrap();
+        assert_eq!(
+            grid.find_pattern(&pattern, Some(&'?')),
+            [
+                Point { x: 0, y: 0 },
+                Point { x: 2, y: 1 },
+                Point { x: 0, y: 2 }
+            ]
+        );
+        let exact: Grid<char> = "#.\n.#".parse().unwrap();
+        assert_eq!(grid.find_pattern(&exact, None).len(), 3);
+        let corner = grid.subgrid(0..3, 0..3);
+        assert_eq!(grid.find_pattern(&corner, None), [Point { x: 0, y: 0 }]);
+        // The diagonal pattern only has 2 distinct orientations
+        let found = grid.find_pattern_oriented(&pattern, Some(&'?'));
+        assert_eq!(found.len(), 5);
+        assert_eq!(
+            found.last(),
+            Some(&(Point { x: 0, y: 1 }, Orientation::new(false, 1)))
+        );
+    }
+}
@@ -0,0 +1,424 @@
+//! Hexagonal grids, in axial coordinates.
+//!
+//! The hexagons are "pointy topped", so each one has neighbors to the east and west and four
+//! diagonal ones, which are the `e`, `se`, `sw`, `w`, `nw` and `ne` steps of puzzles like the hex
+//! tiles of 2020 day 24. `r` grows to the south, as `y` does for [`crate::coords::Point`].
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A hexagon in axial coordinates. The third cube coordinate is `s = -q - r`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// One of the 6 directions between neighboring hexagons, clockwise starting from `East`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    /// Get all 6 directions, clockwise starting from `East`.
+    #[must_use]
+    pub const fn all() -> [Self; 6] {
+        [
+            Self::East,
+            Self::SouthEast,
+            Self::SouthWest,
+            Self::West,
+            Self::NorthWest,
+            Self::NorthEast,
+        ]
+    }
+
+    /// The next direction clockwise
+    #[must_use]
+    pub const fn turn_cw_60(&self) -> Self {
+        match self {
+            Self::East => Self::SouthEast,
+            Self::SouthEast => Self::SouthWest,
+            Self::SouthWest => Self::West,
+            Self::West => Self::NorthWest,
+            Self::NorthWest => Self::NorthEast,
+            Self::NorthEast => Self::East,
+        }
+    }
+
+    /// Parse a sequence of steps written without separators, like `"esenee"`.
+    ///
+    /// # Errors
+    ///
+    /// At the first letter that doesn't start a step, or if the input ends in the middle of one
+    ///
+    /// # Example
+    /// ```rust
+    /// use advent_of_code_common::hex::HexDirection;
+    /// let steps = HexDirection::parse_sequence("nwwswee").unwrap();
+    /// assert_eq!(steps.len(), 5);
+    /// assert_eq!(steps[0], HexDirection::NorthWest);
+    /// ```
+    pub fn parse_sequence(s: &str) -> Result<Vec<Self>, crate::Error> {
+        let mut steps = Vec::new();
+        // Columns count from the start of `s`, including the whitespace that is skipped
+        let leading = s.chars().count() - s.trim_start().chars().count();
+        let mut chars = s.trim().chars().enumerate().map(|(i, c)| (i + leading, c));
+        while let Some((i, c)) = chars.next() {
+            let step = match c {
+                'e' => Self::East,
+                'w' => Self::West,
+                'n' | 's' => {
+                    let Some((j, next)) = chars.next() else {
+                        return Err(crate::Error::unexpected_end("'e' or 'w'").at(1, i + 2));
+                    };
+                    match (c, next) {
+                        ('n', 'e') => Self::NorthEast,
+                        ('n', 'w') => Self::NorthWest,
+                        ('s', 'e') => Self::SouthEast,
+                        ('s', 'w') => Self::SouthWest,
+                        _ => {
+                            return Err(crate::Error::invalid_char(next, "'e' or 'w'").at(1, j + 1))
+                        }
+                    }
+                }
+                _ => {
+                    let expected = "a hex direction (e, se, sw, w, nw or ne)";
+                    return Err(crate::Error::invalid_char(c, expected).at(1, i + 1));
+                }
+            };
+            steps.push(step);
+        }
+        Ok(steps)
+    }
+}
+
+impl std::fmt::Display for HexDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::East => "e",
+            Self::SouthEast => "se",
+            Self::SouthWest => "sw",
+            Self::West => "w",
+            Self::NorthWest => "nw",
+            Self::NorthEast => "ne",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl std::str::FromStr for HexDirection {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::parse_sequence(s)?.as_slice() {
+            [step] => Ok(*step),
+            [] => Err(crate::Error::unexpected_end("a hex direction")),
+            _ => Err(crate::Error::invalid_token(s, "a single hex direction")),
+        }
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(direction: HexDirection) -> Self {
+        let (q, r) = match direction {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        };
+        Self { q, r }
+    }
+}
+
+impl Hex {
+    #[must_use]
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// The third cube coordinate, so that `q + r + s == 0`
+    #[must_use]
+    pub const fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    /// The number of steps to another hexagon
+    #[must_use]
+    pub const fn distance(&self, other: &Self) -> isize {
+        let dq = (self.q - other.q).abs();
+        let dr = (self.r - other.r).abs();
+        let ds = (self.s() - other.s()).abs();
+        (dq + dr + ds) / 2
+    }
+
+    #[must_use]
+    pub fn neighbor(&self, direction: HexDirection) -> Self {
+        *self + Self::from(direction)
+    }
+
+    /// Get the 6 neighbors, clockwise starting from `East`
+    #[must_use]
+    pub fn neighbors(&self) -> [Self; 6] {
+        HexDirection::all().map(|direction| self.neighbor(direction))
+    }
+
+    /// The hexagon reached by following `steps` from this one
+    #[must_use]
+    pub fn walk(&self, steps: &[HexDirection]) -> Self {
+        steps.iter().fold(*self, |hex, &step| hex.neighbor(step))
+    }
+
+    /// The `6 * radius` hexagons at exactly `radius` steps, clockwise starting from the one
+    /// `radius` steps to the west. A ring of radius 0 is just this hexagon.
+    pub fn ring(&self, radius: usize) -> impl Iterator<Item = Self> {
+        let start = *self + Self::from(HexDirection::West) * radius.cast_signed();
+        let sides = if radius == 0 { 0 } else { 6 };
+        let first = (radius == 0).then_some(*self);
+        // Walking clockwise from the west corner, the first side goes north-east
+        let sides = HexDirection::all()
+            .into_iter()
+            .cycle()
+            .skip(5)
+            .take(sides)
+            .flat_map(move |direction| std::iter::repeat_n(direction, radius))
+            .scan(start, |hex, direction| {
+                let current = *hex;
+                *hex = hex.neighbor(direction);
+                Some(current)
+            });
+        first.into_iter().chain(sides)
+    }
+
+    /// All hexagons up to `radius` steps away, ring by ring from the center
+    pub fn spiral(&self, radius: usize) -> impl Iterator<Item = Self> {
+        let center = *self;
+        (0..=radius).flat_map(move |r| center.ring(r))
+    }
+}
+
+impl std::fmt::Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
+
+/// Parse the hexagon at the end of a path of steps from `(0, 0)`, like `"esew"`.
+impl std::str::FromStr for Hex {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::default().walk(&HexDirection::parse_sequence(s)?))
+    }
+}
+
+impl std::ops::Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q + rhs.q,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl std::ops::AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            q: self.q - rhs.q,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+impl std::ops::Mul<isize> for Hex {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self {
+            q: self.q * rhs,
+            r: self.r * rhs,
+        }
+    }
+}
+
+/// A sparse grid of hexagons, where only the hexagons that have data are stored.
+///
+/// Like [`crate::grid::Grid`], it can be evolved with [`HexGrid::apply_step`] and
+/// [`HexGrid::apply_steps_until`], and [`HexGrid::life_step`] is the step function of a cellular
+/// automaton that grows as needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    pub cells: BTreeMap<Hex, T>,
+}
+
+impl<T> Default for HexGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> std::ops::Deref for HexGrid<T> {
+    type Target = BTreeMap<Hex, T>;
+    fn deref(&self) -> &Self::Target {
+        &self.cells
+    }
+}
+
+impl<T> std::ops::DerefMut for HexGrid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cells
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> HexGrid<T> {
+    /// Get the data of the 6 neighbors, clockwise starting from `East`
+    #[must_use]
+    pub fn neighbors(&self, hex: &Hex) -> [Option<&T>; 6] {
+        hex.neighbors().map(|neighbor| self.cells.get(&neighbor))
+    }
+
+    pub fn count_neighbors(&self, hex: &Hex, data: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.neighbors(hex)
+            .iter()
+            .flatten()
+            .filter(|&&d| d == data)
+            .count()
+    }
+
+    /// One step of a cellular automaton: `rule` gets the current data of every stored hexagon and
+    /// of every neighbor of one (`None` where nothing is stored) along with the data of its
+    /// neighbors, and returns the new data, or `None` to leave it empty.
+    #[must_use]
+    pub fn life_step(&self, rule: impl Fn(Option<&T>, [Option<&T>; 6]) -> Option<T>) -> Self {
+        let candidates: BTreeSet<Hex> = self
+            .cells
+            .keys()
+            .flat_map(|hex| std::iter::once(*hex).chain(hex.neighbors()))
+            .collect();
+        candidates
+            .into_iter()
+            .filter_map(|hex| rule(self.cells.get(&hex), self.neighbors(&hex)).map(|d| (hex, d)))
+            .collect()
+    }
+
+    /// Replaces the grid with `f(grid)` until that stops changing it, or for at most `limit`
+    /// steps, and returns the number of steps taken (including the last one, that changed nothing)
+    pub fn apply_steps_until(&mut self, f: impl Fn(&Self) -> Self, limit: Option<usize>) -> usize
+    where
+        T: PartialEq,
+    {
+        crate::grid::apply_steps_until(self, f, limit)
+    }
+
+    /// Replaces the grid with `f(grid)`, and returns whether that changed anything
+    pub fn apply_step(&mut self, f: impl Fn(&Self) -> Self) -> bool
+    where
+        T: PartialEq,
+    {
+        crate::grid::apply_step(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_paths() {
+        assert_eq!("esew".parse::<Hex>().unwrap(), Hex::new(0, 1));
+        assert_eq!("nwwswee".parse::<Hex>().unwrap(), Hex::default());
+        assert_eq!(
+            "sw".parse::<HexDirection>().unwrap(),
+            HexDirection::SouthWest
+        );
+        let error = "enx".parse::<Hex>().unwrap_err();
+        assert_eq!(error.position(), (1, 3));
+        let error = "eew".parse::<HexDirection>().unwrap_err();
+        assert!(matches!(error, crate::Error::InvalidToken { .. }));
+        let error = "ees".parse::<Hex>().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UnexpectedEnd { column: 4, .. }
+        ));
+        let error = " eq".parse::<Hex>().unwrap_err();
+        assert_eq!(error.position(), (1, 3));
+        let round_trip: String = HexDirection::all()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(round_trip, "eseswwnwne");
+    }
+
+    #[test]
+    fn distances_and_rings() {
+        let origin = Hex::default();
+        for direction in HexDirection::all() {
+            assert_eq!(origin.neighbor(direction).distance(&origin), 1);
+            let opposite = direction.turn_cw_60().turn_cw_60().turn_cw_60();
+            assert_eq!(Hex::from(opposite), Hex::from(direction) * -1);
+        }
+        assert_eq!(Hex::new(3, -1).distance(&Hex::new(-1, 2)), 4);
+        for radius in 0..5 {
+            let ring: Vec<Hex> = origin.ring(radius).collect();
+            assert_eq!(ring.len(), if radius == 0 { 1 } else { 6 * radius });
+            let unique: BTreeSet<Hex> = ring.iter().copied().collect();
+            assert_eq!(unique.len(), ring.len());
+            let radius = isize::try_from(radius).unwrap();
+            assert!(ring.iter().all(|hex| hex.distance(&origin) == radius));
+        }
+        assert_eq!(origin.spiral(2).count(), 1 + 6 + 12);
+        assert_eq!(Hex::new(2, 2).ring(1).collect::<BTreeSet<_>>().len(), 6);
+    }
+
+    #[test]
+    fn life() {
+        // The rules of the 2020 day 24 hex tiles, where stored tiles are black
+        let rule = |tile: Option<&()>, neighbors: [Option<&()>; 6]| {
+            let black = neighbors.iter().flatten().count();
+            match tile {
+                Some(()) if black == 0 || black > 2 => None,
+                None if black != 2 => None,
+                _ => Some(()),
+            }
+        };
+        let mut grid: HexGrid<()> = std::iter::once((Hex::default(), ())).collect();
+        assert!(grid.apply_step(|g| g.life_step(rule)));
+        assert!(grid.is_empty());
+        // Two neighbors survive and their two common neighbors turn black
+        let mut grid: HexGrid<()> = [(Hex::default(), ()), (Hex::new(1, 0), ())]
+            .into_iter()
+            .collect();
+        grid.apply_step(|g| g.life_step(rule));
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid.count_neighbors(&Hex::default(), &()), 3);
+    }
+}
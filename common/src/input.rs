@@ -0,0 +1,168 @@
+//! A borrowed view of puzzle input, that splits it into lines, blocks or grid rows once and hands
+//! out slices of the original text.
+//!
+//! Nothing here copies the input, so a [`Solver<'a>`](crate::Solver) can return `&'a str`s that
+//! point into it, and any buffer that lives long enough can back it: a `String` read from disk, an
+//! `include_str!`, or bytes from anywhere else (see [`Input::from_bytes`]).
+use std::cell::OnceCell;
+
+/// Puzzle input borrowed for `'a`, with lazily computed and cached views
+///
+/// ```
+/// use advent_of_code_common::input::Input;
+///
+/// let input = Input::new("#.#\n..#\n\nlast block\n");
+/// assert_eq!(input.lines(), ["#.#", "..#", "", "last block"]);
+/// assert_eq!(input.blocks().len(), 2);
+/// assert_eq!(input.blocks()[1], ["last block"]);
+/// assert_eq!(input.get(2, 1), Some(b'#'));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Input<'a> {
+    text: &'a str,
+    lines: OnceCell<Vec<&'a str>>,
+    blocks: OnceCell<Vec<Vec<&'a str>>>,
+    grid: OnceCell<Vec<&'a [u8]>>,
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(text: &'a str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<'a> Input<'a> {
+    #[must_use]
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            lines: OnceCell::new(),
+            blocks: OnceCell::new(),
+            grid: OnceCell::new(),
+        }
+    }
+
+    /// Borrow input from raw bytes, like the contents of a memory mapped file
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not valid UTF-8
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, std::str::Utf8Error> {
+        std::str::from_utf8(bytes).map(Self::new)
+    }
+
+    /// The whole input, as it was given
+    #[must_use]
+    pub const fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// The whole input as bytes, for inputs that are known to be ASCII
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.text.as_bytes()
+    }
+
+    /// The part of the input between the `index`th and the next `separator`, trimmed. This is the
+    /// borrowed version of [`Solver::read_file_chunk`](crate::Solver::read_file_chunk).
+    #[must_use]
+    pub fn chunk(&self, separator: &str, index: usize) -> Option<Self> {
+        self.text
+            .split(separator)
+            .nth(index)
+            .map(|chunk| Self::new(chunk.trim()))
+    }
+
+    /// The lines of the input, trimmed, without the leading and trailing empty lines
+    pub fn lines(&self) -> &[&'a str] {
+        self.lines
+            .get_or_init(|| crate::file::iter_lines_trim(self.text).collect())
+    }
+
+    /// The lines of the input, grouped into blocks that are separated by empty lines
+    pub fn blocks(&self) -> &[Vec<&'a str>] {
+        self.blocks
+            .get_or_init(|| crate::file::iter_blocks(self.text).collect())
+    }
+
+    /// The rows of the input as bytes, for ASCII grids that are indexed by `grid()[y][x]`
+    ///
+    /// Unlike [`lines`](Self::lines), rows are not trimmed, as leading spaces are part of some
+    /// grids. Only the blank lines before and after the grid are left out.
+    pub fn grid(&self) -> &[&'a [u8]] {
+        self.grid.get_or_init(|| {
+            let rows: Vec<&'a [u8]> = self.text.lines().map(str::as_bytes).collect();
+            let is_blank = |row: &&[u8]| row.iter().all(u8::is_ascii_whitespace);
+            let start = rows.iter().position(|row| !is_blank(row)).unwrap_or(0);
+            let end = rows
+                .iter()
+                .rposition(|row| !is_blank(row))
+                .map_or(0, |end| end + 1);
+            rows[start..end.max(start)].to_vec()
+        })
+    }
+
+    /// The byte at (`x`, `y`) of the [`grid`](Self::grid), or `None` if that is outside of it
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        self.grid().get(y)?.get(x).copied()
+    }
+
+    /// The (width, height) of the [`grid`](Self::grid), where the width is that of the longest row
+    pub fn dimensions(&self) -> (usize, usize) {
+        let grid = self.grid();
+        let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+        (width, grid.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "
+        abc
+        de
+
+        fgh
+    ";
+
+    #[test]
+    fn test_views_borrow_the_input() {
+        let text = String::from(EXAMPLE);
+        let input = Input::new(&text);
+        assert_eq!(input.lines(), ["abc", "de", "", "fgh"]);
+        assert_eq!(input.blocks(), [vec!["abc", "de"], vec!["fgh"]]);
+        assert_eq!(input.grid()[1], b"        de");
+        assert_eq!(input.get(9, 1), Some(b'e'));
+        assert_eq!(input.get(10, 1), None);
+        assert_eq!(input.dimensions(), (11, 4));
+        // Every line points into the original text
+        let range = text.as_bytes().as_ptr_range();
+        assert!(input
+            .lines()
+            .iter()
+            .all(|line| range.contains(&line.as_ptr())));
+    }
+
+    #[test]
+    fn test_grid_keeps_leading_spaces() {
+        let input = Input::new("\n  #\n # \n#\n\n");
+        assert_eq!(input.grid(), [&b"  #"[..], b" # ", b"#"]);
+        assert_eq!(input.lines(), ["#", "#", "#"]);
+        assert_eq!(input.get(0, 0), Some(b' '));
+        assert_eq!(input.dimensions(), (3, 3));
+        assert!(Input::new(" \n\n").grid().is_empty());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let input = Input::new("first\n------\n second \n------\nthird");
+        assert_eq!(input.chunk("------", 1).unwrap().as_str(), "second");
+        assert_eq!(input.chunk("------", 2).unwrap().lines(), ["third"]);
+        assert!(input.chunk("------", 3).is_none());
+        assert!(Input::from_bytes(b"ok").is_ok());
+        assert!(Input::from_bytes(&[0xff, 0xfe]).is_err());
+        assert!(Input::default().lines().is_empty());
+        assert!(Input::default().blocks().is_empty());
+    }
+}
@@ -20,7 +20,7 @@ impl FromIterator<u8> for BitsIterator {
 }
 
 impl std::str::FromStr for BitsIterator {
-    type Err = &'static str;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Process two characters at a time as hexadecimals
@@ -31,7 +31,10 @@ impl std::str::FromStr for BitsIterator {
                 '0'..='9' => c as u8 - b'0',
                 'a'..='f' => c as u8 - b'a' + 10,
                 'A'..='F' => c as u8 - b'A' + 10,
-                _ => return Err("Invalid character"),
+                _ => {
+                    let column = s[..s.len() - s.trim_start().len()].chars().count() + i + 1;
+                    return Err(crate::Error::invalid_char(c, "a hexadecimal digit").at(1, column));
+                }
             };
             let is_last = i == s.len() - 1;
             if i % 2 == 1 || is_last {
@@ -150,6 +153,8 @@ mod tests {
     #[test]
     fn parse_hex() {
         assert!("a5a5aq".parse::<BitsIterator>().is_err(), "Bad character");
+        let error = " a5g".parse::<BitsIterator>().unwrap_err();
+        assert_eq!(error.position(), (1, 4));
         assert!(
             "Abc123"
                 .parse::<BitsIterator>()
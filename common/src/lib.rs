@@ -1,12 +1,23 @@
 pub mod algorithms;
+pub mod boxes;
 pub mod cards;
+pub mod circuit;
 pub mod coords;
+pub mod error;
 pub mod file;
+pub mod geometry;
 pub mod grid;
+pub mod hex;
+pub mod input;
 pub mod iterators;
 pub mod math;
 pub mod range;
+pub mod render;
+pub mod symmetry;
 pub mod v2;
+pub mod vm;
+
+pub use error::Error;
 
 /// A trait for solving problems that are defined by an input text file.
 ///
@@ -67,6 +78,20 @@ pub trait Solver<'a> {
     /// or if the chunk index is out of bounds.
     fn read_file_chunk(&self, chunk_index: usize) -> Result<String, std::io::Error> {
         let content = self.read_file()?;
+        self.file_chunk(&content, chunk_index).map(String::from)
+    }
+
+    /// Same as [`Self::read_file_chunk`], but borrows the chunk from `content` that was already
+    /// read, so that reading many chunks (or benchmarking) doesn't copy the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the chunk index is out of bounds.
+    fn file_chunk<'c>(
+        &self,
+        content: &'c str,
+        chunk_index: usize,
+    ) -> Result<&'c str, std::io::Error> {
         content
             .split(self.file_chunk_separator())
             .nth(chunk_index)
@@ -76,9 +101,16 @@ pub trait Solver<'a> {
                     tracing::error!(msg);
                     Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg))
                 },
-                |chunk| Ok(chunk.trim().to_string()),
+                |chunk| Ok(chunk.trim()),
             )
     }
+
+    /// Solves borrowed [`input::Input`]. The input is borrowed for `'a` like the text given to
+    /// [`Self::solve`], so its cached lines, blocks and grid rows can be part of
+    /// [`Self::Output`]. Defaults to calling [`Self::solve`] with the whole text.
+    fn solve_input(&self, input: &'a input::Input<'a>) -> Self::Output {
+        self.solve(input.as_str())
+    }
 }
 
 /// A shorthand macro to avoid the common boilerplate when testing the solution of a given solver
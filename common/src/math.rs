@@ -1,8 +1,12 @@
 use itertools::Itertools;
-use num::{integer, Integer, Num, PrimInt, Unsigned, Zero};
+use num::{integer, Integer, Num, PrimInt, Signed, Unsigned, Zero};
 use std::collections::HashMap;
 use std::hash::Hash;
 
+pub mod linear;
+pub mod polygon;
+pub mod polynomial;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
     DivisionByZero,
@@ -92,6 +96,202 @@ pub fn counts_of_counts<T: Eq + Hash>(v: &[T]) -> HashMap<usize, usize> {
     v.iter().counts().values().copied().counts()
 }
 
+/// Extended Euclidean algorithm, returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is
+/// the (non-negative) greatest common divisor of `a` and `b`
+#[allow(clippy::many_single_char_names)] // Textbook names
+pub fn egcd<T>(a: T, b: T) -> (T, T, T)
+where
+    T: Integer + Signed + Copy,
+{
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. Returns `None` if `a` and `m` are not coprime
+pub fn mod_inverse<T>(a: T, m: T) -> Option<T>
+where
+    T: Integer + Signed + Copy,
+{
+    let (g, x, _) = egcd(a, m);
+    g.is_one().then(|| x.mod_floor(&m))
+}
+
+/// Chinese remainder theorem for moduli that don't have to be coprime.
+///
+/// Solves a system of congruences `x ≡ residue (mod modulus)`. Returns the smallest non-negative
+/// solution and the modulus it repeats with (the LCM of all moduli), or `None` if the
+/// congruences contradict each other.
+///
+/// Intermediate values can reach the square of the combined modulus, so it should fit in half of
+/// the bits of `T`.
+pub fn crt<T>(congruences: &[(T, T)]) -> Option<(T, T)>
+where
+    T: Integer + Signed + Copy,
+{
+    congruences
+        .iter()
+        .try_fold((T::zero(), T::one()), |(residue, modulus), &(r, m)| {
+            let (g, p, _) = egcd(modulus, m);
+            let diff = r - residue;
+            if !(diff % g).is_zero() {
+                return None;
+            }
+            let lcm = modulus / g * m;
+            let step = (diff / g * p).mod_floor(&(m / g));
+            Some(((residue + modulus * step).mod_floor(&lcm), lcm))
+        })
+}
+
+/// The least common multiple of all values (1 for no values)
+pub fn lcm_all<T, I>(values: I) -> T
+where
+    T: Integer + Copy,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::one(), |acc, v| acc.lcm(&v))
+}
+
+/// `a * b % m` without overflowing, even when the product doesn't fit in 128 bits
+#[must_use]
+pub const fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (a, mut b) = (a % m, b % m);
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    // Double-and-add, keeping every intermediate value below `m`
+    let mut result = 0;
+    let mut a = a;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// `a + b % m` for `a, b < m`, without overflowing
+const fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `base ^ exp % modulus` by repeated squaring
+#[must_use]
+pub const fn mod_pow(base: u128, exp: u128, modulus: u128) -> u128 {
+    if modulus == 1 {
+        return 0;
+    }
+    let (mut base, mut exp) = (base % modulus, exp);
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Miller-Rabin primality test. Deterministic for all `n` below 3.3 * 10^24 (including all of
+/// `u64`), and probabilistic with a negligible error rate above that.
+#[must_use]
+pub fn is_prime(n: u128) -> bool {
+    const BASES: [u128; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = BASES.iter().find(|&&p| n.is_multiple_of(p)) {
+        return n == p;
+    }
+    let d = (n - 1) >> (n - 1).trailing_zeros();
+    BASES.iter().all(|&a| {
+        let mut x = mod_pow(a, d, n);
+        let mut d = d;
+        while d != n - 1 && x != 1 && x != n - 1 {
+            x = mul_mod(x, x, n);
+            d <<= 1;
+        }
+        x == n - 1 || d & 1 == 1
+    })
+}
+
+/// Finds a non-trivial divisor of the odd composite `n`, with Pollard's rho (Brent's variant)
+fn pollard_rho(n: u128) -> u128 {
+    for increment in 1.. {
+        let f = |x: u128| add_mod(mul_mod(x, x, n), increment % n, n);
+        let (mut x, mut y, mut divisor) = (2, 2, 1);
+        let mut power = 1;
+        let mut length = 0;
+        while divisor == 1 {
+            if length == power {
+                x = y;
+                power <<= 1;
+                length = 0;
+            }
+            y = f(y);
+            length += 1;
+            divisor = integer::gcd(x.abs_diff(y), n);
+        }
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!("some increment finds a divisor")
+}
+
+/// The prime factors of `n` (with repetitions) in increasing order, using Pollard's rho.
+///
+/// Much faster than [`prime_factors`] for numbers with large prime factors.
+///
+/// # Panics
+///
+/// Never, as every factor fits in the type of `n`.
+pub fn factorize<T>(n: T) -> Vec<T>
+where
+    T: Into<u128> + TryFrom<u128>,
+    <T as TryFrom<u128>>::Error: std::fmt::Debug,
+{
+    let mut pending = vec![n.into()];
+    let mut factors = Vec::new();
+    while let Some(n) = pending.pop() {
+        if n < 2 {
+            continue;
+        }
+        if n.is_multiple_of(2) {
+            factors.push(2);
+            pending.push(n / 2);
+        } else if is_prime(n) {
+            factors.push(n);
+        } else {
+            let d = pollard_rho(n);
+            pending.extend([d, n / d]);
+        }
+    }
+    factors.sort_unstable();
+    factors
+        .into_iter()
+        .map(|f| T::try_from(f).expect("factors are not larger than n"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +323,81 @@ mod tests {
         assert!(quadratic_roots_real(0, 5, 3).is_err());
         assert_eq!(quadratic_roots_real(1, 0, -16).unwrap(), (4., -4.));
     }
+
+    #[test]
+    fn test_egcd() {
+        assert_eq!(egcd(240, 46), (2, -9, 47));
+        assert_eq!(egcd(-240, 46), (2, 9, 47));
+        assert_eq!(egcd(0_i64, 5), (5, 0, 1));
+        for (a, b) in [(3_i32, 7), (12, 18), (-5, 35), (17, -4)] {
+            let (g, x, y) = egcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(g, integer::gcd(a, b));
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        for a in 1..13_i64 {
+            assert_eq!(mod_inverse(a, 13).map(|inv| inv * a % 13), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(0, 17), (-2, 13), (-3, 19)]), Some((3417, 4199)));
+        // Non-coprime moduli
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_lcm_all() {
+        assert_eq!(lcm_all([4, 6, 10]), 60);
+        assert_eq!(lcm_all([7_u128]), 7);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        // Fermat's little theorem with a 127-bit prime, where products overflow u128
+        let p = (1 << 127) - 1;
+        assert_eq!(mod_pow(3, p - 1, p), 1);
+        assert_eq!(mul_mod(p - 1, p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let small: Vec<u128> = (0..50).filter(|&n| is_prime(n)).collect();
+        assert_eq!(
+            small,
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+        );
+        assert!(is_prime(18_446_744_073_709_551_557)); // Largest u64 prime
+        assert!(!is_prime(3_215_031_751)); // Strong pseudoprime to bases 2, 3, 5, 7
+        assert!(is_prime((1 << 127) - 1));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(8211_u16), [3, 7, 17, 23]);
+        assert_eq!(factorize(1_u64), []);
+        assert_eq!(factorize(600_851_475_143_u64), [71, 839, 1471, 6857]);
+        assert_eq!(
+            factorize(4_294_967_291_u64 * 4_294_967_279),
+            [4_294_967_279, 4_294_967_291]
+        );
+        assert_eq!(
+            factorize(u128::from(u64::MAX) * 1024),
+            [2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 5, 17, 257, 641, 65537, 6_700_417]
+        );
+    }
 }
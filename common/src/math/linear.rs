@@ -0,0 +1,473 @@
+//! Exact linear algebra over the rationals.
+//!
+//! Meant for the small integer systems that puzzles reduce to (button presses, claw machines,
+//! ...). Everything is computed with [`Rational`]s, so there are
+//! no rounding errors to worry about when checking whether a solution is integral.
+use num::rational::Ratio;
+use num::{Integer, One, Zero};
+use std::ops::{Index, IndexMut};
+
+pub type Rational = Ratio<i128>;
+
+/// A dense matrix of [`Rational`]s, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Rational>,
+}
+
+/// All the solutions of a linear system: `particular` plus any combination of `nullspace`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub particular: Vec<Rational>,
+    pub nullspace: Vec<Vec<Rational>>,
+}
+
+impl Solution {
+    #[must_use]
+    pub const fn is_unique(&self) -> bool {
+        self.nullspace.is_empty()
+    }
+
+    /// The solution as integers, if it is unique and integral
+    #[must_use]
+    pub fn integer(&self) -> Option<Vec<i128>> {
+        self.is_unique()
+            .then(|| to_integers(&self.particular))
+            .flatten()
+    }
+}
+
+/// Converts rationals to integers, or `None` if any of them has a fractional part
+#[must_use]
+pub fn to_integers(values: &[Rational]) -> Option<Vec<i128>> {
+    values
+        .iter()
+        .map(|v| v.is_integer().then(|| v.to_integer()))
+        .collect()
+}
+
+impl Matrix {
+    #[must_use]
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![Rational::zero(); rows * cols],
+        }
+    }
+
+    #[must_use]
+    pub fn identity(size: usize) -> Self {
+        let mut matrix = Self::zeros(size, size);
+        for i in 0..size {
+            matrix[(i, i)] = Rational::one();
+        }
+        matrix
+    }
+
+    /// A matrix from integer rows
+    ///
+    /// # Panics
+    ///
+    /// If the rows don't all have the same length
+    pub fn from_rows<R, T>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Into<i128>,
+    {
+        let mut data = Vec::new();
+        let mut row_count = 0;
+        let mut cols = None;
+        for row in rows {
+            let before = data.len();
+            data.extend(row.into_iter().map(|v| Rational::from_integer(v.into())));
+            let len = data.len() - before;
+            assert_eq!(*cols.get_or_insert(len), len, "Rows of different lengths");
+            row_count += 1;
+        }
+        Self {
+            rows: row_count,
+            cols: cols.unwrap_or(0),
+            data,
+        }
+    }
+
+    #[must_use]
+    pub const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub const fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[Rational] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut result = Self::zeros(self.cols, self.rows);
+        for (r, c) in itertools::iproduct!(0..self.rows, 0..self.cols) {
+            result[(c, r)] = self[(r, c)];
+        }
+        result
+    }
+
+    /// The matrix-vector product `self * v`
+    ///
+    /// # Panics
+    ///
+    /// If `v` doesn't have one value per column
+    #[must_use]
+    pub fn mul_vec(&self, v: &[Rational]) -> Vec<Rational> {
+        assert_eq!(v.len(), self.cols, "Vector length doesn't match columns");
+        (0..self.rows)
+            .map(|r| self.row(r).iter().zip(v).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// The matrix with `column` appended as its last column
+    ///
+    /// # Panics
+    ///
+    /// If `column` doesn't have one value per row
+    #[must_use]
+    pub fn augment(&self, column: &[Rational]) -> Self {
+        assert_eq!(column.len(), self.rows, "Column length doesn't match rows");
+        let data = (0..self.rows)
+            .flat_map(|r| self.row(r).iter().chain([&column[r]]).copied())
+            .collect();
+        Self {
+            rows: self.rows,
+            cols: self.cols + 1,
+            data,
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for c in 0..self.cols {
+            self.data.swap(a * self.cols + c, b * self.cols + c);
+        }
+    }
+
+    /// Gauss-Jordan elimination: the reduced row echelon form, and the column of each pivot
+    #[must_use]
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = Vec::new();
+        for col in 0..m.cols {
+            let row = pivots.len();
+            if row == m.rows {
+                break;
+            }
+            let Some(pivot) = (row..m.rows).find(|&r| !m[(r, col)].is_zero()) else {
+                continue;
+            };
+            m.swap_rows(row, pivot);
+            let scale = m[(row, col)].recip();
+            for c in col..m.cols {
+                m[(row, c)] *= scale;
+            }
+            for r in (0..m.rows).filter(|&r| r != row) {
+                let factor = m[(r, col)];
+                if !factor.is_zero() {
+                    for c in col..m.cols {
+                        let value = m[(row, c)];
+                        m[(r, c)] -= factor * value;
+                    }
+                }
+            }
+            pivots.push(col);
+        }
+        (m, pivots)
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    /// A basis of the vectors `v` with `self * v = 0`
+    #[must_use]
+    pub fn nullspace(&self) -> Vec<Vec<Rational>> {
+        let (reduced, pivots) = self.rref();
+        Self::nullspace_of(&reduced, &pivots, self.cols)
+    }
+
+    /// One basis vector per free column (one that has no pivot), among the first `cols` columns
+    fn nullspace_of(reduced: &Self, pivots: &[usize], cols: usize) -> Vec<Vec<Rational>> {
+        (0..cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut v = vec![Rational::zero(); cols];
+                v[free] = Rational::one();
+                for (row, &p) in pivots.iter().enumerate() {
+                    v[p] = -reduced[(row, free)];
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// All solutions of `self * x = rhs`, or `None` if there are none
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` doesn't have one value per row
+    #[must_use]
+    pub fn solve(&self, rhs: &[Rational]) -> Option<Solution> {
+        let (reduced, pivots) = self.augment(rhs).rref();
+        if pivots.last() == Some(&self.cols) {
+            // A row reading 0 = 1
+            return None;
+        }
+        let mut particular = vec![Rational::zero(); self.cols];
+        for (row, &p) in pivots.iter().enumerate() {
+            particular[p] = reduced[(row, self.cols)];
+        }
+        let nullspace = Self::nullspace_of(&reduced, &pivots, self.cols);
+        Some(Solution {
+            particular,
+            nullspace,
+        })
+    }
+
+    /// The cheapest solution of `self * x = rhs` in non-negative integers with `x[i] <= bounds[i]`,
+    /// where each unit of `x[i]` costs `costs[i]` (which must not be negative). Returns the cost
+    /// and the solution, or `None` if there is no such solution.
+    ///
+    /// The free variables of the system are searched exhaustively within their bounds, so this is
+    /// meant for systems that are close to fully determined.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` doesn't have one value per row, or `bounds` and `costs` one value per column
+    #[must_use]
+    pub fn min_cost_integer_solution(
+        &self,
+        rhs: &[i128],
+        bounds: &[i128],
+        costs: &[i128],
+    ) -> Option<(i128, Vec<i128>)> {
+        assert_eq!(bounds.len(), self.cols, "One bound per variable");
+        assert_eq!(costs.len(), self.cols, "One cost per variable");
+        let rhs: Vec<_> = rhs.iter().copied().map(Rational::from_integer).collect();
+        let (reduced, pivots) = self.augment(&rhs).rref();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+        let free: Vec<usize> = (0..self.cols).filter(|c| !pivots.contains(c)).collect();
+        // Each pivot row scaled to integers: `scale * x[pivot] = constant - sum(coef * x[free])`
+        let rows: Vec<PivotRow> = pivots
+            .iter()
+            .enumerate()
+            .map(|(row, &pivot)| {
+                let values: Vec<Rational> = free
+                    .iter()
+                    .chain([&self.cols])
+                    .map(|&c| reduced[(row, c)])
+                    .collect();
+                let scale = values.iter().fold(1, |acc, v| acc.lcm(v.denom()));
+                let mut scaled = values.iter().map(|v| (v * scale).to_integer());
+                PivotRow {
+                    pivot,
+                    coefficients: scaled.by_ref().take(free.len()).collect(),
+                    constant: scaled.next().expect("The rhs column"),
+                    scale,
+                }
+            })
+            .collect();
+        let mut search = IntegerSearch {
+            rows: &rows,
+            free: &free,
+            bounds,
+            costs,
+            values: vec![0; self.cols],
+            best: None,
+        };
+        search.run(0, 0);
+        search.best
+    }
+}
+
+struct PivotRow {
+    pivot: usize,
+    coefficients: Vec<i128>,
+    constant: i128,
+    scale: i128,
+}
+
+/// Depth-first search over the values of the free variables, with branch and bound on the cost
+struct IntegerSearch<'a> {
+    rows: &'a [PivotRow],
+    free: &'a [usize],
+    bounds: &'a [i128],
+    costs: &'a [i128],
+    values: Vec<i128>,
+    best: Option<(i128, Vec<i128>)>,
+}
+
+impl IntegerSearch<'_> {
+    fn is_worse(&self, cost: i128) -> bool {
+        self.best.as_ref().is_some_and(|(best, _)| cost >= *best)
+    }
+
+    fn run(&mut self, depth: usize, cost: i128) {
+        if self.is_worse(cost) {
+            return;
+        }
+        let Some(&var) = self.free.get(depth) else {
+            self.complete(cost);
+            return;
+        };
+        for value in 0..=self.bounds[var] {
+            self.values[var] = value;
+            let partial = cost + value * self.costs[var];
+            if self.is_worse(partial) {
+                break;
+            }
+            self.run(depth + 1, partial);
+        }
+        self.values[var] = 0;
+    }
+
+    /// Derives the pivot variables from the free ones, and records the solution if it is valid
+    fn complete(&mut self, mut cost: i128) {
+        for row in self.rows {
+            let total = row.constant
+                - row
+                    .coefficients
+                    .iter()
+                    .zip(self.free)
+                    .map(|(c, &f)| c * self.values[f])
+                    .sum::<i128>();
+            if !total.is_multiple_of(&row.scale) {
+                return;
+            }
+            let value = total / row.scale;
+            if !(0..=self.bounds[row.pivot]).contains(&value) {
+                return;
+            }
+            self.values[row.pivot] = value;
+            cost += value * self.costs[row.pivot];
+        }
+        if !self.is_worse(cost) {
+            self.best = Some((cost, self.values.clone()));
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = Rational;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(values: &[i128]) -> Vec<Rational> {
+        values.iter().copied().map(Rational::from_integer).collect()
+    }
+
+    #[test]
+    fn test_rref_and_rank() {
+        let m = Matrix::from_rows([[1, 2, 3], [2, 4, 6], [1, 0, 1]]);
+        let (reduced, pivots) = m.rref();
+        assert_eq!(pivots, [0, 1]);
+        assert_eq!(
+            reduced,
+            Matrix::from_rows([[1, 0, 1], [0, 1, 1], [0, 0, 0]])
+        );
+        assert_eq!(m.rank(), 2);
+        assert_eq!(Matrix::identity(4).rank(), 4);
+        assert_eq!(Matrix::zeros(2, 3).rank(), 0);
+        assert_eq!(m.transpose().rank(), 2);
+    }
+
+    #[test]
+    fn test_nullspace() {
+        let m = Matrix::from_rows([[1, 2, 3], [2, 4, 6], [1, 0, 1]]);
+        let nullspace = m.nullspace();
+        assert_eq!(nullspace, [rationals(&[-1, -1, 1])]);
+        let m = Matrix::from_rows([[1, 1, 1, 1]]);
+        let nullspace = m.nullspace();
+        assert_eq!(nullspace.len(), 3);
+        for v in nullspace {
+            assert_eq!(m.mul_vec(&v), rationals(&[0]));
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        // The first claw machine of 2024 day 13
+        let m = Matrix::from_rows([[94, 22], [34, 67]]);
+        let solution = m.solve(&rationals(&[8400, 5400])).unwrap();
+        assert!(solution.is_unique());
+        assert_eq!(solution.integer(), Some(vec![80, 40]));
+        // The second one has a solution, but not in integers
+        let m = Matrix::from_rows([[26, 67], [66, 21]]);
+        let solution = m.solve(&rationals(&[12748, 12176])).unwrap();
+        assert_eq!(solution.integer(), None);
+        assert_eq!(m.mul_vec(&solution.particular), rationals(&[12748, 12176]));
+        // Dependent rows: consistent or not
+        let m = Matrix::from_rows([[1, 2], [2, 4]]);
+        assert_eq!(m.solve(&rationals(&[1, 3])), None);
+        let solution = m.solve(&rationals(&[1, 2])).unwrap();
+        assert_eq!(solution.particular, rationals(&[1, 0]));
+        assert_eq!(solution.nullspace, [rationals(&[-2, 1])]);
+        assert_eq!(solution.integer(), None);
+    }
+
+    #[test]
+    fn test_min_cost_integer_solution() {
+        // Counters 0..4 and the buttons that increment them, from 2025 day 10
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let m = Matrix::from_rows(
+            (0..4).map(|counter| buttons.map(|b| i128::from(b.contains(&counter)))),
+        );
+        let target = [3, 5, 4, 7];
+        let (presses, solution) = m
+            .min_cost_integer_solution(&target, &[7; 6], &[1; 6])
+            .unwrap();
+        assert_eq!(presses, 10);
+        assert_eq!(
+            m.mul_vec(
+                &solution
+                    .iter()
+                    .copied()
+                    .map(Rational::from)
+                    .collect::<Vec<_>>()
+            ),
+            rationals(&target)
+        );
+        // Weighted costs, where the cheaper combination needs more presses
+        let m = Matrix::from_rows([[1, 2]]);
+        assert_eq!(
+            m.min_cost_integer_solution(&[4], &[4, 4], &[1, 3]),
+            Some((4, vec![4, 0]))
+        );
+        assert_eq!(
+            m.min_cost_integer_solution(&[4], &[4, 4], &[3, 1]),
+            Some((2, vec![0, 2]))
+        );
+        // Bounds too tight, and an odd target for even coefficients
+        assert_eq!(m.min_cost_integer_solution(&[4], &[1, 1], &[1, 1]), None);
+        let m = Matrix::from_rows([[2, 4]]);
+        assert_eq!(m.min_cost_integer_solution(&[5], &[9, 9], &[1, 1]), None);
+    }
+}
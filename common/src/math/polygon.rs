@@ -0,0 +1,97 @@
+//! Exact areas and point counts of polygons with integer vertices.
+//!
+//! The vertices are given in order (clockwise or counter-clockwise), without repeating the first
+//! one at the end.
+use crate::coords::Point;
+use num::integer;
+
+/// Twice the signed area, by the Shoelace formula: positive if the vertices go counter-clockwise
+/// in a y-up frame (so clockwise on screen, where y goes down)
+#[must_use]
+pub fn twice_signed_area(vertices: &[Point]) -> isize {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+/// The area of the polygon, rounded down if it ends in a half
+#[must_use]
+pub fn area(vertices: &[Point]) -> isize {
+    twice_signed_area(vertices).abs() / 2
+}
+
+/// The number of lattice points on the edges of the polygon
+#[must_use]
+pub fn boundary_points(vertices: &[Point]) -> isize {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(a, b)| integer::gcd(b.x - a.x, b.y - a.y))
+        .sum()
+}
+
+/// The number of lattice points strictly inside the polygon, by Pick's theorem
+/// (`A = i + b/2 - 1`)
+#[must_use]
+pub fn interior_points(vertices: &[Point]) -> isize {
+    // `2A - b` is always even
+    (twice_signed_area(vertices).abs() - boundary_points(vertices)) / 2 + 1
+}
+
+/// The number of lattice points inside the polygon or on its edges.
+///
+/// This is the area covered by unit tiles when the vertices are the centers of tiles, as in a dug
+/// out lagoon.
+#[must_use]
+pub fn lattice_points(vertices: &[Point]) -> isize {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(isize, isize)]) -> Vec<Point> {
+        vertices.iter().map(Point::from).collect()
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let rectangle = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(twice_signed_area(&rectangle), 24);
+        let reversed: Vec<Point> = rectangle.iter().rev().copied().collect();
+        assert_eq!(twice_signed_area(&reversed), -24);
+        assert_eq!(area(&rectangle), 12);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 6);
+        assert_eq!(lattice_points(&rectangle), 20);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let triangle = polygon(&[(0, 0), (3, 1), (1, 2)]);
+        assert_eq!(twice_signed_area(&triangle), 5);
+        assert_eq!(area(&triangle), 2);
+        assert_eq!(boundary_points(&triangle), 3);
+        assert_eq!(interior_points(&triangle), 2);
+    }
+
+    #[test]
+    fn test_concave() {
+        // An L shape, with a brute force count of the lattice points
+        let shape = polygon(&[(0, 0), (6, 0), (6, 2), (2, 2), (2, 5), (0, 5)]);
+        let inside = |x: isize, y: isize| {
+            (0..=6).contains(&x) && (0..=2).contains(&y)
+                || (0..=2).contains(&x) && (0..=5).contains(&y)
+        };
+        let count = itertools::iproduct!(-1..8, -1..7)
+            .filter(|&(x, y)| inside(x, y))
+            .count();
+        assert_eq!(lattice_points(&shape), isize::try_from(count).unwrap());
+        assert_eq!(area(&shape), 18);
+    }
+}
@@ -0,0 +1,142 @@
+//! Sequences generated by polynomials: finite difference tables and Lagrange interpolation.
+use super::linear::Rational;
+use num::{Num, Zero};
+
+/// The table of repeated differences of a sequence, down to the first row that is all zeros
+///
+/// ```
+/// use advent_of_code_common::math::polynomial::DifferenceTable;
+///
+/// let table = DifferenceTable::new(&[1, 3, 6, 10, 15, 21]);
+/// assert_eq!(table.degree(), Some(2));
+/// assert_eq!(table.next(), 28);
+/// assert_eq!(table.previous(), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceTable<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T> DifferenceTable<T>
+where
+    T: Num + Copy,
+{
+    #[must_use]
+    pub fn new(values: &[T]) -> Self {
+        let mut rows = vec![values.to_vec()];
+        while let Some(last) = rows.last().filter(|row| row.iter().any(|v| !v.is_zero())) {
+            let next: Vec<T> = last.windows(2).map(|w| w[1] - w[0]).collect();
+            rows.push(next);
+        }
+        Self { rows }
+    }
+
+    /// The sequence, then its differences, then their differences, ...
+    #[must_use]
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    /// The degree of the polynomial that generates the sequence (0 for a constant sequence), or
+    /// `None` if there aren't enough values to tell, or the sequence is all zeros
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        // The first constant row with at least two values tells the degree
+        self.rows
+            .iter()
+            .position(|row| row.len() >= 2 && row.iter().all(|&v| v == row[0]) && !row[0].is_zero())
+    }
+
+    /// The value following the sequence
+    #[must_use]
+    pub fn next(&self) -> T {
+        self.rows
+            .iter()
+            .filter_map(|row| row.last())
+            .fold(T::zero(), |acc, &v| acc + v)
+    }
+
+    /// The value preceding the sequence
+    #[must_use]
+    pub fn previous(&self) -> T {
+        self.rows
+            .iter()
+            .filter_map(|row| row.first())
+            .rev()
+            .fold(T::zero(), |acc, &v| v - acc)
+    }
+}
+
+/// The value at `x` of the lowest degree polynomial through all `points`, as an exact rational.
+///
+/// With three samples of a quadratic this extrapolates it anywhere, for example.
+///
+/// # Panics
+///
+/// If two points have the same `x`
+#[must_use]
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Rational {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points.iter().enumerate().filter(|&(j, _)| j != i).fold(
+                Rational::from_integer(yi),
+                |acc, (_, &(xj, _))| {
+                    assert_ne!(xi, xj, "Points must have distinct x");
+                    acc * Rational::new(x - xj, xi - xj)
+                },
+            )
+        })
+        .fold(Rational::zero(), |acc, term| acc + term)
+}
+
+/// Like [`lagrange`], for the polynomial that generates `samples[i]` at `x = i`, evaluated at
+/// `x = index`. Returns `None` if the result is not an integer.
+#[must_use]
+pub fn extrapolate(samples: &[i128], index: i128) -> Option<i128> {
+    let points: Vec<(i128, i128)> = (0..).zip(samples.iter().copied()).collect();
+    let value = lagrange(&points, index);
+    value.is_integer().then(|| value.to_integer())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_table() {
+        let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(table.degree(), Some(3));
+        assert_eq!(table.next(), 68);
+        assert_eq!(table.previous(), 5);
+        assert_eq!(table.rows()[3], [2, 2, 2]);
+        let table = DifferenceTable::new(&[0, 3, 6, 9, 12, 15]);
+        assert_eq!(
+            (table.degree(), table.next(), table.previous()),
+            (Some(1), 18, -3)
+        );
+        let table = DifferenceTable::new(&[7, 7, 7]);
+        assert_eq!(
+            (table.degree(), table.next(), table.previous()),
+            (Some(0), 7, 7)
+        );
+        // Not enough values to see the pattern
+        assert_eq!(DifferenceTable::new(&[1, 2, 4, 8]).degree(), None);
+        assert_eq!(DifferenceTable::<i64>::new(&[]).next(), 0);
+    }
+
+    #[test]
+    fn test_lagrange() {
+        // y = 2x^2 - 3x + 1
+        let f = |x: i128| 2 * x * x - 3 * x + 1;
+        let points = [(-1, f(-1)), (2, f(2)), (5, f(5))];
+        for x in -10..10 {
+            assert_eq!(lagrange(&points, x), Rational::from_integer(f(x)));
+        }
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Rational::new(1, 2));
+        assert_eq!(extrapolate(&[0, 1, 4, 9], 202_300), Some(202_300 * 202_300));
+        assert_eq!(extrapolate(&[0, 1], 5), Some(5));
+        assert_eq!(extrapolate(&[0, 0, 1], 1), Some(0));
+    }
+}
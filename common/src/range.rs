@@ -1,6 +1,10 @@
-use num::{Num, One};
+use num::{Num, ToPrimitive};
 use std::ops::{AddAssign, RangeInclusive, SubAssign};
 
+/// A set of values stored as a list of inclusive ranges.
+///
+/// The ranges are always kept sorted, with no overlapping or adjacent ranges (`1..=5` and
+/// `6..=9` are stored as `1..=9`), so two sets with the same values are always equal.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct MultiRange<T> {
     ranges: Vec<RangeInclusive<T>>,
@@ -15,29 +19,45 @@ impl<T> IntoIterator for MultiRange<T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a MultiRange<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = std::slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+/// True if all of `r` is below `value`, with at least one value between them
+fn ends_before<T: Num + Ord + Copy>(r: &RangeInclusive<T>, value: T) -> bool {
+    *r.end() < value && *r.end() + T::one() < value
+}
+
+/// True if all of `r` is above `value`, with at least one value between them
+fn starts_after<T: Num + Ord + Copy>(r: &RangeInclusive<T>, value: T) -> bool {
+    *r.start() > value && value + T::one() < *r.start()
+}
+
 impl<T> AddAssign<&RangeInclusive<T>> for MultiRange<T>
 where
     T: Num + Ord + Copy,
 {
+    /// Adds all the values in `other`, merging it with any ranges it overlaps or touches
     fn add_assign(&mut self, other: &RangeInclusive<T>) {
-        let index = self
-            .ranges
-            .iter()
-            .position(|r| r.contains(other.start()) || r.contains(other.end()));
-        match index {
-            Some(index) => {
-                let r = &mut self.ranges[index];
-                let r_start = *r.start();
-                let r_end = *r.end();
-                let other_start = *other.start();
-                let other_end = *other.end();
-                *r = r_start.min(other_start)..=r_end.max(other_end);
-            }
-            None => {
-                self.ranges.push(other.clone());
-            }
+        let (mut start, mut end) = (*other.start(), *other.end());
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| ends_before(r, start));
+        let last = self.ranges.partition_point(|r| !starts_after(r, end));
+        if let (Some(low), Some(high)) = (
+            self.ranges[first..last].first(),
+            self.ranges[first..last].last(),
+        ) {
+            start = start.min(*low.start());
+            end = end.max(*high.end());
         }
-        self.ranges.sort_unstable_by_key(|r| *r.start());
+        self.ranges.splice(first..last, [start..=end]);
     }
 }
 
@@ -45,60 +65,54 @@ impl<T> SubAssign<&RangeInclusive<T>> for MultiRange<T>
 where
     T: Num + Ord + Copy,
 {
+    /// Removes all the values in `other`, truncating or splitting the ranges it overlaps
     fn sub_assign(&mut self, other: &RangeInclusive<T>) {
-        let mut index = 0;
-        while index < self.ranges.len() {
-            let r = &mut self.ranges[index];
-            let r_start = *r.start();
-            let r_end = *r.end();
-            let other_start = *other.start();
-            let other_end = *other.end();
-            match (r.contains(other.start()), r.contains(other.end())) {
-                (true, true) => {
-                    // other is contained in r, split r into two ranges
-                    // r       => |--------------------|
-                    // other   =>     |--------|
-                    // result1 => |---|
-                    // result2 =>              |-------|
-                    let r1 = r_start..=(other_start - One::one());
-                    let r2 = (other_end + One::one())..=r_end;
-                    self.ranges.remove(index);
-                    self.ranges.push(r1);
-                    self.ranges.push(r2);
-                }
-                (true, false) => {
-                    // other.start() is contained in r, truncate r
-                    // r      => |--------------------|
-                    // other  =>     |------------------|
-                    // result => |---|
-                    *r = r_start..=(other_start - One::one());
-                }
-                (false, true) => {
-                    // other.end() is contained in r, truncate r
-                    //     r  =>          |------------|
-                    // other  =>       |--------|
-                    // result =>                 |-----|
-                    *r = (other_end + One::one())..=r_end;
-                }
-                (false, false) => {
-                    if r_start > other_start && r_end < other_end {
-                        // r is contained in other, remove r
-                        // r      =>     |---|
-                        // other  => |-----------|
-                        // result => None
-                        self.ranges.remove(index);
-                        continue; // skip index += 1
-                    }
-                    // If we got here, other is not contained in r, do nothing
-                    // r      => |---|
-                    // other  =>         |--------|
-                    // result => |---|
-                }
-            }
-            index += 1;
+        let (start, end) = (*other.start(), *other.end());
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| *r.end() < start);
+        let last = self.ranges.partition_point(|r| *r.start() <= end);
+        if first == last {
+            return;
+        }
+        let mut remaining = Vec::with_capacity(2);
+        let (low, high) = (&self.ranges[first], &self.ranges[last - 1]);
+        if *low.start() < start {
+            // r      => |--------------------|
+            // other  =>     |--------|
+            // result => |---|
+            remaining.push(*low.start()..=start - T::one());
+        }
+        if *high.end() > end {
+            // r      => |--------------------|
+            // other  =>     |--------|
+            // result =>              |-------|
+            remaining.push(end + T::one()..=*high.end());
+        }
+        self.ranges.splice(first..last, remaining);
+    }
+}
+
+impl<T> AddAssign<&Self> for MultiRange<T>
+where
+    T: Num + Ord + Copy,
+{
+    fn add_assign(&mut self, other: &Self) {
+        for r in other {
+            *self += r;
+        }
+    }
+}
+
+impl<T> SubAssign<&Self> for MultiRange<T>
+where
+    T: Num + Ord + Copy,
+{
+    fn sub_assign(&mut self, other: &Self) {
+        for r in other {
+            *self -= r;
         }
-        self.ranges.retain(|r| r.start() <= r.end());
-        self.ranges.sort_unstable_by_key(|r| *r.start());
     }
 }
 
@@ -115,11 +129,253 @@ where
     }
 }
 
+impl<T> From<RangeInclusive<T>> for MultiRange<T>
+where
+    T: Num + Ord + Copy,
+{
+    fn from(r: RangeInclusive<T>) -> Self {
+        std::iter::once(r).collect()
+    }
+}
+
 impl<T> MultiRange<T> {
     #[must_use]
     pub const fn new() -> Self {
         Self { ranges: vec![] }
     }
+
+    /// The ranges that make up this set, sorted, non-overlapping and non-adjacent
+    #[must_use]
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl<T> MultiRange<T>
+where
+    T: Num + Ord + Copy,
+{
+    /// The total number of values in all ranges, which may not fit in `T` (`0..=255u8` has 256
+    /// values). It saturates at [`u128::MAX`], which only all of `u128` or `i128` reaches.
+    #[must_use]
+    pub fn len(&self) -> u128
+    where
+        T: ToPrimitive,
+    {
+        self.ranges.iter().fold(0, |acc: u128, r| {
+            let span = if let (Some(start), Some(end)) = (r.start().to_u128(), r.end().to_u128()) {
+                end - start
+            } else {
+                let start = r.start().to_i128().unwrap_or(i128::MIN);
+                let end = r.end().to_i128().unwrap_or(i128::MAX);
+                end.abs_diff(start)
+            };
+            acc.saturating_add(span).saturating_add(1)
+        })
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end() < value);
+        self.ranges.get(index).is_some_and(|r| r.contains(value))
+    }
+
+    /// Iterates over every single value in all ranges, in increasing order
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges.iter().flat_map(|r| {
+            let end = *r.end();
+            std::iter::successors(Some(*r.start()), move |&v| (v < end).then(|| v + T::one()))
+        })
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result += other;
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                ranges.push(start..=end);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        // Both inputs are normalized, so the overlaps can't overlap or touch each other
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result -= other;
+        result
+    }
+
+    /// All values in `within` that are not in this set
+    #[must_use]
+    pub fn complement(&self, within: &RangeInclusive<T>) -> Self {
+        let mut result = Self::from(within.clone());
+        result -= self;
+        result
+    }
+}
+
+/// A piecewise function that moves whole ranges of values to a new start, like the
+/// `destination source length` rules of 2023 day 5. Values outside all rules are unchanged.
+///
+/// ```
+/// use advent_of_code_common::range::{IntervalMap, MultiRange};
+///
+/// let mut map = IntervalMap::new();
+/// map.insert(98..=99, 50);
+/// map.insert(50..=97, 52);
+/// assert_eq!(map.get(99), 51);
+/// assert_eq!(map.get(10), 10);
+/// let seeds = MultiRange::from(79..=92);
+/// assert_eq!(map.apply(&seeds).ranges(), [81..=94]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalMap<T> {
+    /// Source ranges (sorted and non-overlapping) with the value their start is mapped to
+    rules: Vec<(RangeInclusive<T>, T)>,
+}
+
+impl<T> IntervalMap<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// The rules as source ranges and the start of their destination, sorted by source
+    #[must_use]
+    pub fn rules(&self) -> &[(RangeInclusive<T>, T)] {
+        &self.rules
+    }
+}
+
+impl<T> IntervalMap<T>
+where
+    T: Num + Ord + Copy,
+{
+    /// Maps `source` to the range starting at `destination`. Values already covered by
+    /// previous rules keep their mapping, so the first matching rule always wins.
+    pub fn insert(&mut self, source: RangeInclusive<T>, destination: T) {
+        let mut uncovered = MultiRange::from(source.clone());
+        for (r, _) in &self.rules {
+            uncovered -= r;
+        }
+        for r in uncovered {
+            let target = destination + (*r.start() - *source.start());
+            let index = self.rules.partition_point(|(s, _)| s.start() < r.start());
+            self.rules.insert(index, (r, target));
+        }
+    }
+
+    /// Maps a single value
+    #[must_use]
+    pub fn get(&self, value: T) -> T {
+        let index = self.rules.partition_point(|(s, _)| *s.end() < value);
+        match self.rules.get(index) {
+            Some((source, destination)) if source.contains(&value) => {
+                *destination + (value - *source.start())
+            }
+            _ => value,
+        }
+    }
+
+    /// Maps every value in `values` at once, by splitting their ranges along the rules
+    #[must_use]
+    pub fn apply(&self, values: &MultiRange<T>) -> MultiRange<T> {
+        let mut unchanged = values.clone();
+        let mut result = MultiRange::new();
+        for (source, destination) in &self.rules {
+            let covered = values.intersection(&MultiRange::from(source.clone()));
+            for r in &covered {
+                let start = *destination + (*r.start() - *source.start());
+                result += &(start..=start + (*r.end() - *r.start()));
+            }
+            unchanged -= source;
+        }
+        result += &unchanged;
+        result
+    }
+
+    /// A single map equivalent to applying this map and then `next`
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let mut composed = Self::new();
+        for (source, destination) in &self.rules {
+            // Follow the part of the image that `next` moves again
+            let image_end = *destination + (*source.end() - *source.start());
+            for (next_source, next_destination) in &next.rules {
+                let start = *destination.max(next_source.start());
+                let end = image_end.min(*next_source.end());
+                if start <= end {
+                    let from = *source.start() + (start - *destination);
+                    let to = *next_destination + (start - *next_source.start());
+                    composed.insert(from..=from + (end - start), to);
+                }
+            }
+            // Whatever `next` leaves unchanged
+            composed.insert(source.clone(), *destination);
+        }
+        // Values this map leaves unchanged are only moved by `next`
+        for (source, destination) in &next.rules {
+            composed.insert(source.clone(), *destination);
+        }
+        composed
+            .rules
+            .retain(|(source, destination)| source.start() != destination);
+        composed
+    }
+
+    /// Swaps the source and destination of every rule.
+    ///
+    /// This is only the real inverse if the map is a bijection, meaning no two destination
+    /// ranges overlap and every value a rule moves away is also the destination of some rule.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self::new();
+        for (source, destination) in &self.rules {
+            let end = *destination + (*source.end() - *source.start());
+            inverse.insert(*destination..=end, *source.start());
+        }
+        inverse
+    }
+}
+
+impl<T> FromIterator<(RangeInclusive<T>, T)> for IntervalMap<T>
+where
+    T: Num + Ord + Copy,
+{
+    /// Inserts the rules in order, see [`IntervalMap::insert`]
+    fn from_iter<I: IntoIterator<Item = (RangeInclusive<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
 }
 
 #[cfg(test)]
@@ -189,4 +445,133 @@ mod tests {
         assert_eq!(m.ranges.len(), 0, "Exactly empty");
         assert_eq!(m2.ranges.len(), 0, "Over empty");
     }
+
+    #[test]
+    fn test_normalized() {
+        let m: MultiRange<i32> = [1..=5, 6..=9, 20..=25, 11..=12, 10..=10]
+            .into_iter()
+            .collect();
+        assert_eq!(m.ranges(), [1..=12, 20..=25]);
+        let m: MultiRange<i32> = [1..=2, 8..=9, 4..=5, 3..=7].into_iter().collect();
+        assert_eq!(m.ranges(), [1..=9]);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty: MultiRange<i32> = std::iter::once(5..=4).collect();
+        assert!(empty.is_empty());
+        let max: MultiRange<u8> = [0..=10, 250..=255, 12..=249].into_iter().collect();
+        assert_eq!(max.ranges(), [0..=10, 12..=255]);
+        assert_eq!(max.complement(&(0..=255)).ranges(), [11..=11]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let m: MultiRange<u64> = [3..=5, 10..=14].into_iter().collect();
+        assert_eq!(m.len(), 8);
+        assert!(m.contains(&3) && m.contains(&12) && m.contains(&14));
+        assert!(!m.contains(&2) && !m.contains(&7) && !m.contains(&15));
+        assert_eq!(
+            m.values().collect::<Vec<_>>(),
+            [3, 4, 5, 10, 11, 12, 13, 14]
+        );
+        assert_eq!(MultiRange::<u64>::new().len(), 0);
+        let bytes: MultiRange<u8> = std::iter::once(0..=255).collect();
+        assert_eq!(bytes.len(), 256);
+        let signed: MultiRange<i8> = [-128..=-1, 1..=127].into_iter().collect();
+        assert_eq!(signed.len(), 255);
+        let all: MultiRange<i128> = std::iter::once(i128::MIN..=i128::MAX).collect();
+        assert_eq!(all.len(), u128::MAX);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        use std::collections::BTreeSet;
+        let sets = [
+            vec![0..=3, 5..=9, 15..=15],
+            vec![2..=6, 9..=12],
+            vec![-3..=0, 16..=20],
+            vec![],
+            vec![-5..=25],
+        ];
+        let universe = -5..=25;
+        for a in &sets {
+            for b in &sets {
+                let ma: MultiRange<i32> = a.iter().cloned().collect();
+                let mb: MultiRange<i32> = b.iter().cloned().collect();
+                let sa: BTreeSet<i32> = a.iter().cloned().flatten().collect();
+                let sb: BTreeSet<i32> = b.iter().cloned().flatten().collect();
+                let values = |m: MultiRange<i32>| m.values().collect::<BTreeSet<_>>();
+                assert_eq!(values(ma.union(&mb)), &sa | &sb, "{a:?} | {b:?}");
+                assert_eq!(values(ma.intersection(&mb)), &sa & &sb, "{a:?} & {b:?}");
+                assert_eq!(values(ma.difference(&mb)), &sa - &sb, "{a:?} - {b:?}");
+                let all = universe.clone().collect::<BTreeSet<_>>();
+                assert_eq!(values(ma.complement(&universe)), &all - &sa, "!{a:?}");
+                // Normalized sets are equal exactly when they have the same values
+                let rebuilt: MultiRange<i32> = (&sa & &sb).into_iter().map(|v| v..=v).collect();
+                assert_eq!(ma.intersection(&mb), rebuilt);
+            }
+        }
+    }
+
+    fn example_maps() -> [IntervalMap<i64>; 2] {
+        let seed_to_soil = [(98..=99, 50), (50..=97, 52)].into_iter().collect();
+        let soil_to_fertilizer = [(15..=51, 0), (52..=53, 37), (0..=14, 39)]
+            .into_iter()
+            .collect();
+        [seed_to_soil, soil_to_fertilizer]
+    }
+
+    #[test]
+    fn test_interval_map_get() {
+        let [map, _] = example_maps();
+        let expected = (0..50).chain(52..100).chain(50..52).chain(100..110);
+        for (value, expected) in (0..110).zip(expected) {
+            assert_eq!(map.get(value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_interval_map_first_rule_wins() {
+        let map: IntervalMap<u32> = [(10..=20, 100), (15..=30, 200)].into_iter().collect();
+        assert_eq!(map.get(15), 105);
+        assert_eq!(map.get(21), 206);
+        assert_eq!(map.rules(), [(10..=20, 100), (21..=30, 206)]);
+    }
+
+    #[test]
+    fn test_interval_map_apply() {
+        let [map, next] = example_maps();
+        let values: MultiRange<i64> = [0..=10, 45..=60, 95..=120].into_iter().collect();
+        for map in [&map, &next] {
+            let expected: MultiRange<i64> =
+                values.values().map(|v| map.get(v)).map(|v| v..=v).collect();
+            assert_eq!(map.apply(&values), expected);
+        }
+    }
+
+    #[test]
+    fn test_interval_map_compose() {
+        let [map, next] = example_maps();
+        let composed = map.then(&next);
+        for value in -5..120 {
+            assert_eq!(composed.get(value), next.get(map.get(value)), "{value}");
+        }
+        let twice = composed.then(&composed);
+        for value in -5..120 {
+            assert_eq!(
+                twice.get(value),
+                composed.get(composed.get(value)),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_interval_map_inverse() {
+        let [map, next] = example_maps();
+        for map in [map.clone(), next, map.then(&map)] {
+            let inverse = map.inverse();
+            for value in -5..120 {
+                assert_eq!(inverse.get(map.get(value)), value, "{value}");
+            }
+        }
+    }
 }
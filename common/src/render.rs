@@ -0,0 +1,561 @@
+//! Text and image views of grids and point sets, to look at the state of a puzzle.
+//!
+//! A [`Canvas`] holds one [`Style`] per cell, a character with optional colors. It is made from a
+//! [`Grid`] with a style function, or from a set of points, can have paths drawn or highlighted
+//! over it, and is written as plain text, as text with ANSI colors, or as a PBM, PPM or SVG image
+//! that any image viewer or browser can open.
+use crate::coords::Point;
+use crate::grid::{Grid, GridCell};
+use std::ffi::OsStr;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+mod animation;
+
+pub use animation::{Animate, Animated, Animation, ANIMATE_VAR, FPS_VAR};
+
+/// The size of a cell in pixels, when saving SVG images
+const SVG_CELL_SIZE: isize = 16;
+
+/// A 24-bit color, for ANSI escape codes and images
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+    pub const GRAY: Self = Self::new(128, 128, 128);
+    pub const RED: Self = Self::new(220, 50, 47);
+    pub const GREEN: Self = Self::new(80, 200, 80);
+    pub const BLUE: Self = Self::new(60, 120, 230);
+    pub const YELLOW: Self = Self::new(230, 200, 40);
+    pub const CYAN: Self = Self::new(40, 200, 200);
+    pub const MAGENTA: Self = Self::new(200, 60, 200);
+
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// How a single cell looks: its character, and optionally its colors and weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub symbol: char,
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    #[must_use]
+    pub const fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            color: None,
+            background: None,
+            bold: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    #[must_use]
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// The symbol with its ANSI escape codes, whether colors are enabled or not
+    #[must_use]
+    pub fn ansi(&self) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if let Some(Color { r, g, b }) = self.color {
+            codes.push(format!("38;2;{r};{g};{b}"));
+        }
+        if let Some(Color { r, g, b }) = self.background {
+            codes.push(format!("48;2;{r};{g};{b}"));
+        }
+        if codes.is_empty() {
+            self.symbol.to_string()
+        } else {
+            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), self.symbol)
+        }
+    }
+
+    /// The color of the whole cell in an image: the background, or else the color of the symbol,
+    /// or else white for visible symbols and black for blank ones
+    #[must_use]
+    pub fn fill(&self) -> Color {
+        let plain = if self.is_blank() {
+            Color::BLACK
+        } else {
+            Color::WHITE
+        };
+        self.background.or(self.color).unwrap_or(plain)
+    }
+
+    const fn is_blank(&self) -> bool {
+        self.symbol.is_whitespace() || self.symbol == '.'
+    }
+}
+
+/// A blank cell
+impl Default for Style {
+    fn default() -> Self {
+        Self::new(' ')
+    }
+}
+
+impl From<char> for Style {
+    fn from(symbol: char) -> Self {
+        Self::new(symbol)
+    }
+}
+
+/// The symbol, with colors unless they are turned off (see [`colors_enabled`])
+impl std::fmt::Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if colors_enabled() {
+            write!(f, "{}", self.ansi())
+        } else {
+            write!(f, "{}", self.symbol)
+        }
+    }
+}
+
+/// Whether to write colors, decided once, the first time it is needed
+///
+/// Colors are written when the standard output is a terminal, unless the `NO_COLOR` environment
+/// variable is set to anything but an empty string, as suggested by <https://no-color.org>.
+/// Setting `CLICOLOR_FORCE` to anything but `0` writes them to files and pipes too.
+#[must_use]
+pub fn colors_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        wants_colors(
+            std::env::var_os("NO_COLOR").as_deref(),
+            std::env::var_os("CLICOLOR_FORCE").as_deref(),
+            std::io::stdout().is_terminal(),
+        )
+    })
+}
+
+fn wants_colors(no_color: Option<&OsStr>, force: Option<&OsStr>, terminal: bool) -> bool {
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        false
+    } else if force.is_some_and(|value| !value.is_empty() && value != "0") {
+        true
+    } else {
+        terminal
+    }
+}
+
+/// A view of a grid or of some points, one [`Style`] per cell. Cells that are missing from the
+/// grid are drawn blank.
+///
+/// # Example
+/// ```rust
+/// use advent_of_code_common::coords::Point;
+/// use advent_of_code_common::render::{Canvas, Color, Style};
+/// let walls = [Point { x: 0, y: 0 }, Point { x: 2, y: 1 }];
+/// let canvas = Canvas::new(0..3, 0..2, '.')
+///     .overlay(&walls, '#')
+///     .highlight(&[Point { x: 1, y: 1 }], Color::RED);
+/// assert_eq!(canvas.plain(), "#..\n..#");
+/// assert_eq!(canvas.grid.get(&(1_isize, 1)).unwrap().data, Style::new('.').background(Color::RED));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    pub grid: Grid<Style>,
+    labels: bool,
+}
+
+impl Canvas {
+    /// A canvas over `x_range` and `y_range`, with `fill` everywhere
+    pub fn new(x_range: Range<isize>, y_range: Range<isize>, fill: impl Into<Style>) -> Self {
+        let fill = fill.into();
+        let mut grid = Grid {
+            x_range: x_range.clone(),
+            y_range: y_range.clone(),
+            cells: std::collections::BTreeMap::new(),
+        };
+        for y in y_range {
+            for x in x_range.clone() {
+                grid.set(&Point { x, y }, fill);
+            }
+        }
+        Self {
+            grid,
+            labels: false,
+        }
+    }
+
+    /// A canvas over the same ranges as `grid`, with the style of each cell
+    pub fn from_grid<T>(grid: &Grid<T>, style: impl Fn(&GridCell<T>) -> Style) -> Self {
+        let mut canvas = Self {
+            grid: Grid {
+                x_range: grid.x_range.clone(),
+                y_range: grid.y_range.clone(),
+                cells: std::collections::BTreeMap::new(),
+            },
+            labels: false,
+        };
+        for cell in grid.values() {
+            canvas.grid.set(&cell.point, style(cell));
+        }
+        canvas
+    }
+
+    /// A canvas over the bounding box of `points`, with `on` at the points and `off` elsewhere
+    pub fn from_points<'p>(
+        points: impl IntoIterator<Item = &'p Point>,
+        on: impl Into<Style>,
+        off: impl Into<Style>,
+    ) -> Self {
+        let points: Vec<&Point> = points.into_iter().collect();
+        let range = |values: Vec<isize>| {
+            let min = values.iter().min().copied().unwrap_or_default();
+            let max = values.iter().max().map_or(min, |&max| max + 1);
+            min..max
+        };
+        let x_range = range(points.iter().map(|p| p.x).collect());
+        let y_range = range(points.iter().map(|p| p.y).collect());
+        Self::new(x_range, y_range, off).overlay(points, on)
+    }
+
+    /// Draw `style` at `p`. Points outside of the canvas are left out.
+    pub fn set(&mut self, p: &Point, style: impl Into<Style>) {
+        if self.grid.is_in_range(p) {
+            self.grid.set(p, style.into());
+        }
+    }
+
+    /// Draw `style` at each of the points, like a path over the grid
+    #[must_use]
+    pub fn overlay<'p>(
+        mut self,
+        points: impl IntoIterator<Item = &'p Point>,
+        style: impl Into<Style>,
+    ) -> Self {
+        let style = style.into();
+        for p in points {
+            self.set(p, style);
+        }
+        self
+    }
+
+    /// Give each of the points a `color` background, keeping what is drawn there
+    #[must_use]
+    pub fn highlight<'p>(
+        mut self,
+        points: impl IntoIterator<Item = &'p Point>,
+        color: Color,
+    ) -> Self {
+        for p in points {
+            if let Some(cell) = self.grid.get_mut(p) {
+                cell.data.background = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Write the `x` coordinates above the columns, top to bottom, and the `y` coordinates left of
+    /// the rows, which helps when the ranges don't start at 0
+    #[must_use]
+    pub const fn with_labels(mut self) -> Self {
+        self.labels = true;
+        self
+    }
+
+    /// The symbols only, one line per row
+    #[must_use]
+    pub fn plain(&self) -> String {
+        self.text(|style| style.symbol.to_string())
+    }
+
+    /// The symbols with their ANSI colors, one line per row
+    #[must_use]
+    pub fn ansi(&self) -> String {
+        self.text(Style::ansi)
+    }
+
+    fn text(&self, draw: impl Fn(&Style) -> String) -> String {
+        let mut lines = Vec::new();
+        let row_label = |y: isize| y.to_string();
+        let margin = if self.labels {
+            self.grid
+                .y_range
+                .clone()
+                .map(|y| row_label(y).len())
+                .max()
+                .unwrap_or(0)
+                + 1
+        } else {
+            0
+        };
+        if self.labels {
+            let xs: Vec<String> = self.grid.x_range.clone().map(|x| x.to_string()).collect();
+            let digits = xs.iter().map(String::len).max().unwrap_or(0);
+            for line in 0..digits {
+                let header: String = xs
+                    .iter()
+                    .map(|x| {
+                        let padding = digits - x.len();
+                        x.chars().nth(line.wrapping_sub(padding)).unwrap_or(' ')
+                    })
+                    .collect();
+                lines.push(format!("{:margin$}{header}", "").trim_end().to_string());
+            }
+        }
+        for y in self.grid.y_range.clone() {
+            let mut line = String::new();
+            if self.labels {
+                let _ = write!(line, "{:>width$} ", row_label(y), width = margin - 1);
+            }
+            for x in self.grid.x_range.clone() {
+                match self.grid.get(&Point { x, y }) {
+                    Some(cell) => line.push_str(&draw(&cell.data)),
+                    None => line.push(' '),
+                }
+            }
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    fn fills(&self) -> impl Iterator<Item = Vec<Color>> + '_ {
+        self.grid.y_range.clone().map(|y| {
+            self.grid
+                .x_range
+                .clone()
+                .map(|x| {
+                    self.grid
+                        .get(&Point { x, y })
+                        .map_or(Color::BLACK, |cell| cell.data.fill())
+                })
+                .collect()
+        })
+    }
+
+    /// A black and white PBM image (plain format), where black pixels are the cells where `on`
+    /// holds
+    #[must_use]
+    pub fn to_pbm(&self, on: impl Fn(&Style) -> bool) -> String {
+        let mut image = format!("P1\n{} {}\n", self.grid.width(), self.grid.height());
+        for y in self.grid.y_range.clone() {
+            let row: Vec<&str> = self
+                .grid
+                .x_range
+                .clone()
+                .map(|x| match self.grid.get(&Point { x, y }) {
+                    Some(cell) if on(&cell.data) => "1",
+                    _ => "0",
+                })
+                .collect();
+            image.push_str(&row.join(" "));
+            image.push('\n');
+        }
+        image
+    }
+
+    /// A color PPM image (plain format), one pixel per cell, with the [`Style::fill`] of the cell
+    #[must_use]
+    pub fn to_ppm(&self) -> String {
+        let mut image = format!("P3\n{} {}\n255\n", self.grid.width(), self.grid.height());
+        for row in self.fills() {
+            let row: Vec<String> = row
+                .iter()
+                .map(|Color { r, g, b }| format!("{r} {g} {b}"))
+                .collect();
+            image.push_str(&row.join(" "));
+            image.push('\n');
+        }
+        image
+    }
+
+    /// An SVG image with squares of `cell_size` pixels: the background of each cell, and its
+    /// symbol in its color
+    #[must_use]
+    pub fn to_svg(&self, cell_size: isize) -> String {
+        let mut image = svg_open(self.grid.width(), self.grid.height(), cell_size);
+        image.push_str(&self.svg_cells(cell_size));
+        image.push_str("</svg>\n");
+        image
+    }
+
+    /// The SVG elements of the cells, on a black background, without the `<svg>` element around
+    /// them
+    fn svg_cells(&self, cell_size: isize) -> String {
+        let (width, height) = (
+            self.grid.width() * cell_size,
+            self.grid.height() * cell_size,
+        );
+        let mut image = format!("<rect width=\"{width}\" height=\"{height}\" fill=\"#000000\"/>\n");
+        for cell in self.grid.values() {
+            let x = (cell.point.x - self.grid.x_range.start) * cell_size;
+            let y = (cell.point.y - self.grid.y_range.start) * cell_size;
+            let style = &cell.data;
+            if let Some(background) = style.background {
+                let _ = writeln!(
+                    image,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{cell_size}\" height=\"{cell_size}\" fill=\"{}\"/>",
+                    background.hex()
+                );
+            }
+            if !style.symbol.is_whitespace() {
+                let symbol = match style.symbol {
+                    '<' => "&lt;".to_string(),
+                    '>' => "&gt;".to_string(),
+                    '&' => "&amp;".to_string(),
+                    c => c.to_string(),
+                };
+                let weight = if style.bold {
+                    " font-weight=\"bold\""
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    image,
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"{}\"{weight}>{symbol}</text>",
+                    x + cell_size / 2,
+                    y + cell_size * 4 / 5,
+                    style.color.unwrap_or(Color::WHITE).hex()
+                );
+            }
+        }
+        image
+    }
+
+    /// Write the canvas to a file, as an image if the extension is `pbm`, `ppm` or `svg`, and as
+    /// plain text otherwise. PBM images have black pixels for the visible symbols.
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be written
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let contents = match path.extension().and_then(|e| e.to_str()) {
+            Some("pbm") => self.to_pbm(|style| !style.is_blank()),
+            Some("ppm") => self.to_ppm(),
+            Some("svg") => self.to_svg(SVG_CELL_SIZE),
+            _ => self.plain() + "\n",
+        };
+        std::fs::write(path, contents)
+    }
+}
+
+/// The opening `<svg>` element for `width` by `height` cells
+fn svg_open(width: isize, height: isize, cell_size: isize) -> String {
+    let (width, height) = (width * cell_size, height * cell_size);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{cell_size}\">\n"
+    )
+}
+
+/// The canvas with its colors, unless they are turned off (see [`colors_enabled`])
+impl std::fmt::Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if colors_enabled() {
+            write!(f, "{}", self.ansi())
+        } else {
+            write!(f, "{}", self.plain())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_enabled_by_env() {
+        let set = Some(OsStr::new("1"));
+        assert!(wants_colors(None, None, true));
+        assert!(!wants_colors(None, None, false));
+        assert!(!wants_colors(set, None, true));
+        assert!(wants_colors(Some(OsStr::new("")), None, true));
+        assert!(wants_colors(None, set, false));
+        assert!(!wants_colors(None, Some(OsStr::new("0")), false));
+        assert!(!wants_colors(set, set, true));
+    }
+
+    #[test]
+    fn text() {
+        let grid: Grid<u8> = Grid::new(3, 2, 0);
+        let canvas = Canvas::from_grid(&grid, |cell| {
+            Style::new(if cell.point.x == 1 { '#' } else { '.' })
+        });
+        assert_eq!(canvas.plain(), ".#.\n.#.");
+        let path = [
+            Point { x: 0, y: 1 },
+            Point { x: 2, y: 1 },
+            Point { x: 5, y: 5 },
+        ];
+        assert_eq!(canvas.overlay(&path, 'o').plain(), ".#.\no#o");
+        let points = [Point { x: 8, y: -1 }, Point { x: 11, y: 1 }];
+        let labeled = Canvas::from_points(&points, '#', '.').with_labels();
+        assert_eq!(
+            labeled.plain(),
+            "     11\n   8901\n-1 #...\n 0 ....\n 1 ...#"
+        );
+    }
+
+    #[test]
+    fn colors() {
+        let style = Style::new('x').color(Color::RED).bold();
+        assert_eq!(style.ansi(), "\x1b[1;38;2;220;50;47mx\x1b[0m");
+        assert_eq!(Style::new('x').ansi(), "x");
+        let canvas = Canvas::new(0..2, 0..1, '.').highlight(&[Point { x: 1, y: 0 }], Color::BLUE);
+        assert_eq!(canvas.ansi(), ".\x1b[48;2;60;120;230m.\x1b[0m");
+        assert_eq!(canvas.plain(), "..");
+    }
+
+    #[test]
+    fn images() {
+        let canvas = Canvas::new(0..3, 0..2, '.')
+            .overlay(&[Point { x: 0, y: 0 }], '#')
+            .overlay(&[Point { x: 2, y: 1 }], Style::new('<').color(Color::GREEN));
+        assert_eq!(
+            canvas.to_pbm(|style| style.symbol == '#'),
+            "P1\n3 2\n1 0 0\n0 0 0\n"
+        );
+        assert_eq!(
+            canvas.to_ppm(),
+            "P3\n3 2\n255\n255 255 255 0 0 0 0 0 0\n0 0 0 0 0 0 80 200 80\n"
+        );
+        let svg = canvas.to_svg(10);
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\"")
+        );
+        assert!(svg.contains(
+            "<text x=\"25\" y=\"18\" text-anchor=\"middle\" fill=\"#50c850\">&lt;</text>"
+        ));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,324 @@
+use super::{svg_open, Canvas, SVG_CELL_SIZE};
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::warn;
+
+/// The environment variable that turns animations on, see [`Animation::from_env`]
+pub const ANIMATE_VAR: &str = "AOC_ANIMATE";
+
+/// The environment variable with the frames per second of animations, 10 if it's not set
+pub const FPS_VAR: &str = "AOC_ANIMATE_FPS";
+
+const DEFAULT_FPS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Output {
+    Off,
+    /// Redraw the terminal in place
+    Terminal,
+    /// One numbered SVG image per frame, in a directory
+    Frames(PathBuf),
+    /// A single SVG image that shows the frames one after the other
+    Svg(PathBuf),
+}
+
+/// Successive frames of a simulation, shown as they come.
+///
+/// Frames go to the terminal, where each one replaces the previous one, to a directory of
+/// numbered SVG images, or to a single animated SVG image that is written when the animation is
+/// dropped. A failure to write stops the animation with a warning rather than the simulation, as
+/// this is only a way to look at it.
+///
+/// Simulations call [`Self::frame`] at each step, which costs nothing when the animation is off,
+/// as it is unless the [`ANIMATE_VAR`] environment variable says otherwise. Iterators of states
+/// can use [`Animate::animate`] instead.
+#[derive(Debug)]
+pub struct Animation {
+    output: Output,
+    delay: Duration,
+    frames: usize,
+    svg_frames: Vec<(isize, isize, String)>,
+}
+
+impl Animation {
+    const fn new(output: Output) -> Self {
+        Self {
+            output,
+            delay: Duration::from_millis(1000 / DEFAULT_FPS as u64),
+            frames: 0,
+            svg_frames: Vec::new(),
+        }
+    }
+
+    /// An animation that shows nothing
+    #[must_use]
+    pub const fn off() -> Self {
+        Self::new(Output::Off)
+    }
+
+    /// Redraw the terminal for each frame, with colors if [`super::colors_enabled`]. Frames are
+    /// printed with [`println!`], so tests only show them with `--nocapture`.
+    #[must_use]
+    pub const fn terminal() -> Self {
+        Self::new(Output::Terminal)
+    }
+
+    /// Save each frame as `frame_00000.svg`, `frame_00001.svg` and so on, in `directory`
+    #[must_use]
+    pub fn frames(directory: impl Into<PathBuf>) -> Self {
+        Self::new(Output::Frames(directory.into()))
+    }
+
+    /// Save all the frames as one animated SVG image at `path`, when the animation is dropped
+    #[must_use]
+    pub fn svg(path: impl Into<PathBuf>) -> Self {
+        Self::new(Output::Svg(path.into()))
+    }
+
+    /// The animation set by the [`ANIMATE_VAR`] environment variable:
+    /// - `terminal` to redraw the terminal
+    /// - a path that ends with `.svg` for an animated SVG image
+    /// - any other path for a directory of frames
+    ///
+    /// The animation is off when the variable is not set or empty. The frame rate comes from
+    /// [`FPS_VAR`].
+    #[must_use]
+    pub fn from_env() -> Self {
+        let animation = match std::env::var(ANIMATE_VAR) {
+            Ok(value) if value == "terminal" => Self::terminal(),
+            Ok(value)
+                if std::path::Path::new(&value)
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("svg")) =>
+            {
+                Self::svg(value)
+            }
+            Ok(value) if !value.is_empty() => Self::frames(value),
+            _ => return Self::off(),
+        };
+        let fps = std::env::var(FPS_VAR)
+            .ok()
+            .and_then(|fps| fps.parse().ok())
+            .unwrap_or(DEFAULT_FPS);
+        animation.with_fps(fps)
+    }
+
+    /// Show `fps` frames per second, in the terminal and in animated SVG images
+    #[must_use]
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.delay = Duration::from_secs(1) / fps.max(1);
+        self
+    }
+
+    #[must_use]
+    pub fn is_on(&self) -> bool {
+        self.output != Output::Off
+    }
+
+    /// The number of frames shown so far
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.frames
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Show the frame that `draw` makes, only calling it if the animation is on
+    pub fn frame(&mut self, draw: impl FnOnce() -> Canvas) {
+        if self.is_on() {
+            self.push(&draw());
+        }
+    }
+
+    /// Show a frame
+    pub fn push(&mut self, canvas: &Canvas) {
+        if let Err(error) = self.try_push(canvas) {
+            warn!(
+                "Stopping the animation after {} frames: {error}",
+                self.frames
+            );
+            self.output = Output::Off;
+        }
+    }
+
+    fn try_push(&mut self, canvas: &Canvas) -> std::io::Result<()> {
+        match &self.output {
+            Output::Off => return Ok(()),
+            Output::Terminal => {
+                // Move to the top left corner and clear the screen
+                println!("\x1b[H\x1b[2J{canvas}");
+                std::io::stdout().flush()?;
+                std::thread::sleep(self.delay);
+            }
+            Output::Frames(directory) => {
+                if self.frames == 0 {
+                    std::fs::create_dir_all(directory)?;
+                }
+                canvas.save(directory.join(format!("frame_{:05}.svg", self.frames)))?;
+            }
+            Output::Svg(_) => {
+                let cells = canvas.svg_cells(SVG_CELL_SIZE);
+                let (width, height) = (canvas.grid.width(), canvas.grid.height());
+                self.svg_frames.push((width, height, cells));
+            }
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// The animated SVG image of the frames so far: each frame is hidden until its turn, and the
+    /// last one stays
+    fn animated_svg(&self) -> String {
+        let width = self.svg_frames.iter().map(|f| f.0).max().unwrap_or(0);
+        let height = self.svg_frames.iter().map(|f| f.1).max().unwrap_or(0);
+        let mut image = svg_open(width, height, SVG_CELL_SIZE);
+        let delay = self.delay.as_secs_f64();
+        let last = self.svg_frames.len().saturating_sub(1);
+        for (i, (_, _, cells)) in self.svg_frames.iter().enumerate() {
+            #[expect(clippy::cast_precision_loss)] // Not that many frames
+            let begin = i as f64 * delay;
+            let end = if i == last {
+                "fill=\"freeze\"".to_string()
+            } else {
+                format!("dur=\"{delay}s\"")
+            };
+            let _ = writeln!(
+                image,
+                "<g visibility=\"hidden\">\n<set attributeName=\"visibility\" to=\"visible\" \
+                 begin=\"{begin}s\" {end}/>\n{cells}</g>"
+            );
+        }
+        image.push_str("</svg>\n");
+        image
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self::off()
+    }
+}
+
+/// Animated SVG images are written once all the frames are in
+impl Drop for Animation {
+    fn drop(&mut self) {
+        if let Output::Svg(path) = &self.output {
+            if let Err(error) = std::fs::write(path, self.animated_svg()) {
+                warn!(
+                    "Failed to save the animation to {}: {error}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+/// Animate the states of a simulation as they are iterated
+pub trait Animate: Iterator + Sized {
+    /// Show each item through [`Animation::from_env`], as drawn by `draw`, and pass it on. The
+    /// items are not drawn at all when the animation is off.
+    fn animate<F>(self, draw: F) -> Animated<Self, F>
+    where
+        F: FnMut(&Self::Item) -> Canvas,
+    {
+        self.animate_with(Animation::from_env(), draw)
+    }
+
+    /// Like [`Self::animate`], with a given animation
+    fn animate_with<F>(self, animation: Animation, draw: F) -> Animated<Self, F>
+    where
+        F: FnMut(&Self::Item) -> Canvas,
+    {
+        Animated {
+            iter: self,
+            draw,
+            animation,
+        }
+    }
+}
+
+impl<I: Iterator> Animate for I {}
+
+/// The iterator returned by [`Animate::animate`]
+#[derive(Debug)]
+pub struct Animated<I, F> {
+    iter: I,
+    draw: F,
+    animation: Animation,
+}
+
+impl<I, F> Animated<I, F> {
+    /// The animation, with the frames shown so far
+    pub const fn animation(&self) -> &Animation {
+        &self.animation
+    }
+}
+
+impl<I, F> Iterator for Animated<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> Canvas,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let draw = &mut self.draw;
+        self.animation.frame(|| draw(&item));
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::Point;
+
+    fn frames() -> impl Iterator<Item = Point> {
+        (0..3).map(|x| Point { x, y: 0 })
+    }
+
+    fn draw(p: &Point) -> Canvas {
+        Canvas::new(0..3, 0..1, '.').overlay([p], '#')
+    }
+
+    #[test]
+    fn off() {
+        let mut drawn = 0;
+        let mut animated = frames().animate_with(Animation::off(), |p| {
+            drawn += 1;
+            draw(p)
+        });
+        assert_eq!(animated.by_ref().count(), 3);
+        assert!(animated.animation().is_empty());
+        drop(animated);
+        assert_eq!(drawn, 0);
+    }
+
+    #[test]
+    fn files() {
+        let directory = std::env::temp_dir().join(format!("aoc_animation_{}", std::process::id()));
+        let frames_directory = directory.join("frames");
+        let mut animated = frames().animate_with(Animation::frames(&frames_directory), draw);
+        assert_eq!(animated.by_ref().last(), Some(Point { x: 2, y: 0 }));
+        assert_eq!(animated.animation().len(), 3);
+        let last = std::fs::read_to_string(frames_directory.join("frame_00002.svg")).unwrap();
+        assert!(last.starts_with("<svg"));
+
+        let path = directory.join("animation.svg");
+        let mut animation = Animation::svg(&path).with_fps(4);
+        for p in frames() {
+            animation.frame(|| draw(&p));
+        }
+        drop(animation);
+        let svg = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(svg.matches("<g visibility=\"hidden\">").count(), 3);
+        assert!(svg.contains("begin=\"0.5s\" fill=\"freeze\""));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
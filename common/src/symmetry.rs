@@ -0,0 +1,333 @@
+//! Rotations and reflections of grids, 2D shapes and 3D point clouds.
+//!
+//! The 8 symmetries of a square are [`Orientation`]s, applied through the [`Orient`] trait, which
+//! [`Grid`] and sets of [`Point`]s implement. The 24 rotations of a cube are [`Rotation3`]s.
+use crate::coords::{Point, Point3, PointN};
+use crate::grid::{Grid, GridCell};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One of the 8 ways to turn and flip a square: flipped left to right first if `flipped`, then
+/// turned clockwise `quarter_turns` times
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    #[must_use]
+    pub const fn new(flipped: bool, quarter_turns: u8) -> Self {
+        Self {
+            flipped,
+            quarter_turns,
+        }
+    }
+
+    /// All 8 orientations, starting with the one that changes nothing
+    #[must_use]
+    pub const fn all() -> [Self; 8] {
+        [
+            Self::new(false, 0),
+            Self::new(false, 1),
+            Self::new(false, 2),
+            Self::new(false, 3),
+            Self::new(true, 0),
+            Self::new(true, 1),
+            Self::new(true, 2),
+            Self::new(true, 3),
+        ]
+    }
+
+    /// Apply the orientation to a point, around `(0, 0)`. As for [`Point::rotate`], `y` grows to
+    /// the south, so clockwise is clockwise on screen.
+    #[must_use]
+    pub const fn apply(&self, p: &Point) -> Point {
+        let mut p = if self.flipped {
+            Point { x: -p.x, y: p.y }
+        } else {
+            *p
+        };
+        let mut turns = self.quarter_turns % 4;
+        while turns > 0 {
+            p = Point { x: -p.y, y: p.x };
+            turns -= 1;
+        }
+        p
+    }
+}
+
+/// Shapes that can be turned and flipped. Only [`Self::transpose`] and [`Self::flip_horizontal`]
+/// are needed, the other symmetries are made of those two.
+pub trait Orient: Clone {
+    /// Mirror along the diagonal from the top left corner, swapping `x` and `y`
+    #[must_use]
+    fn transpose(&self) -> Self;
+
+    /// Mirror left to right
+    #[must_use]
+    fn flip_horizontal(&self) -> Self;
+
+    /// Mirror top to bottom
+    #[must_use]
+    fn flip_vertical(&self) -> Self {
+        self.transpose().flip_horizontal().transpose()
+    }
+
+    /// Turn a quarter clockwise
+    #[must_use]
+    fn rotate_cw(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Turn a quarter counter-clockwise
+    #[must_use]
+    fn rotate_ccw(&self) -> Self {
+        self.flip_horizontal().transpose()
+    }
+
+    /// Turn upside down
+    #[must_use]
+    fn rotate_180(&self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// Apply one of the 8 [`Orientation`]s
+    #[must_use]
+    fn orient(&self, orientation: Orientation) -> Self {
+        let flipped = if orientation.flipped {
+            self.flip_horizontal()
+        } else {
+            self.clone()
+        };
+        (0..orientation.quarter_turns % 4).fold(flipped, |shape, _| shape.rotate_cw())
+    }
+
+    /// All the distinct orientations of the shape, starting with itself. Symmetric shapes have
+    /// fewer than 8.
+    #[must_use]
+    fn orientations(&self) -> Vec<Self>
+    where
+        Self: PartialEq,
+    {
+        let mut distinct: Vec<Self> = Vec::with_capacity(8);
+        for orientation in Orientation::all() {
+            let shape = self.orient(orientation);
+            if !distinct.contains(&shape) {
+                distinct.push(shape);
+            }
+        }
+        distinct
+    }
+}
+
+/// The grid is moved so that it starts at `(0, 0)`, whatever its ranges were
+impl<T: Clone> Orient for Grid<T> {
+    fn transpose(&self) -> Self {
+        let (x0, y0) = (self.x_range.start, self.y_range.start);
+        self.remap(0..self.height(), 0..self.width(), |p| Point {
+            x: p.y - y0,
+            y: p.x - x0,
+        })
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let (x0, y0) = (self.x_range.start, self.y_range.start);
+        let last = self.width() - 1;
+        self.remap(0..self.width(), 0..self.height(), |p| Point {
+            x: last - (p.x - x0),
+            y: p.y - y0,
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    fn remap(
+        &self,
+        x_range: std::ops::Range<isize>,
+        y_range: std::ops::Range<isize>,
+        f: impl Fn(&Point) -> Point,
+    ) -> Self {
+        let cells: BTreeMap<Point, GridCell<T>> = self
+            .cells
+            .values()
+            .map(|cell| {
+                let point = f(&cell.point);
+                (point, GridCell::new(&point, cell.data.clone()))
+            })
+            .collect();
+        Self {
+            x_range,
+            y_range,
+            cells,
+        }
+    }
+}
+
+/// A shape made of points, like a puzzle piece. The top left corner of its bounding box stays in
+/// place, so that shapes compare equal when they have the same orientation.
+impl Orient for BTreeSet<Point> {
+    fn transpose(&self) -> Self {
+        let (x0, y0) = top_left(self);
+        self.iter()
+            .map(|p| Point {
+                x: x0 + p.y - y0,
+                y: y0 + p.x - x0,
+            })
+            .collect()
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let (x0, _) = top_left(self);
+        let x1 = self.iter().map(|p| p.x).max().unwrap_or_default();
+        self.iter()
+            .map(|p| Point {
+                x: x0 + x1 - p.x,
+                y: p.y,
+            })
+            .collect()
+    }
+}
+
+fn top_left(points: &BTreeSet<Point>) -> (isize, isize) {
+    let x = points.iter().map(|p| p.x).min().unwrap_or_default();
+    let y = points.iter().map(|p| p.y).min().unwrap_or_default();
+    (x, y)
+}
+
+/// One of the 24 rotations of a cube: each axis of the result is one of the original axes, maybe
+/// reversed, without mirroring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation3 {
+    axes: [usize; 3],
+    signs: [isize; 3],
+}
+
+impl Rotation3 {
+    /// The rotation that changes nothing
+    pub const IDENTITY: Self = Self {
+        axes: [0, 1, 2],
+        signs: [1, 1, 1],
+    };
+
+    /// All 24 rotations, starting with [`Self::IDENTITY`]
+    #[must_use]
+    pub fn all() -> Vec<Self> {
+        // Even permutations keep their handedness, odd ones need an odd number of reversed axes
+        let permutations = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+        let signs = [-1, 1];
+        let mut all = Vec::with_capacity(24);
+        for (axes, parity) in permutations {
+            for (sx, sy, sz) in itertools::iproduct!(signs, signs, signs) {
+                if sx * sy * sz == parity {
+                    all.push(Self {
+                        axes,
+                        signs: [sx, sy, sz],
+                    });
+                }
+            }
+        }
+        all.sort_by_key(|r| *r != Self::IDENTITY);
+        all
+    }
+
+    #[must_use]
+    pub const fn apply(&self, p: &Point3) -> Point3 {
+        PointN {
+            coords: [
+                self.signs[0] * p.coords[self.axes[0]],
+                self.signs[1] * p.coords[self.axes[1]],
+                self.signs[2] * p.coords[self.axes[2]],
+            ],
+        }
+    }
+
+    /// Rotate a whole point cloud, around `(0, 0, 0)`
+    #[must_use]
+    pub fn apply_all(&self, points: &[Point3]) -> Vec<Point3> {
+        points.iter().map(|p| self.apply(p)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grids() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_180().to_string(), "fed\ncba");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_cw().x_range, 0..2);
+        assert_eq!(grid.orientations().len(), 8);
+        let symmetric: Grid<char> = "#.#\n.#.\n#.#".parse().unwrap();
+        assert_eq!(symmetric.orientations().len(), 1);
+        for orientation in Orientation::all() {
+            let oriented = grid.orient(orientation);
+            for cell in grid.values() {
+                // The same as turning the centers of the cells around (0, 0), at double scale,
+                // and moving them back in the grid
+                let center = cell.point * 2_isize + Point { x: 1, y: 1 };
+                let p = orientation.apply(&center);
+                let x = (p.x.rem_euclid(oriented.width() * 2) - 1) / 2;
+                let y = (p.y.rem_euclid(oriented.height() * 2) - 1) / 2;
+                assert_eq!(oriented.get(&(x, y)).unwrap().data, cell.data);
+            }
+        }
+    }
+
+    #[test]
+    fn shapes() {
+        // An L tromino and an S tetromino have 4 orientations, an F pentomino has all 8
+        let shape = |points: &[(isize, isize)]| -> BTreeSet<Point> {
+            points.iter().map(Point::from).collect()
+        };
+        assert_eq!(shape(&[(5, 5), (5, 6), (6, 6)]).orientations().len(), 4);
+        assert_eq!(
+            shape(&[(1, 0), (2, 0), (0, 1), (1, 1)])
+                .orientations()
+                .len(),
+            4
+        );
+        let f_pentomino = shape(&[(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)]);
+        assert_eq!(f_pentomino.orientations().len(), 8);
+        assert_eq!(
+            f_pentomino.rotate_cw(),
+            shape(&[(1, 0), (0, 1), (1, 1), (2, 1), (2, 2)])
+        );
+    }
+
+    #[test]
+    fn rotations() {
+        let all = Rotation3::all();
+        assert_eq!(all.len(), 24);
+        assert_eq!(all[0], Rotation3::IDENTITY);
+        let p = Point3::new(1, 2, 3);
+        let images: BTreeSet<Point3> = all.iter().map(|r| r.apply(&p)).collect();
+        assert_eq!(images.len(), 24);
+        // A rotation keeps the handedness: x × y = z
+        for r in all {
+            let (x, y, z) = (
+                r.apply(&Point3::new(1, 0, 0)),
+                r.apply(&Point3::new(0, 1, 0)),
+                r.apply(&Point3::new(0, 0, 1)),
+            );
+            let cross = Point3::new(
+                x.y() * y.z() - x.z() * y.y(),
+                x.z() * y.x() - x.x() * y.z(),
+                x.x() * y.y() - x.y() * y.x(),
+            );
+            assert_eq!(cross, z);
+        }
+    }
+}
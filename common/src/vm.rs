@@ -0,0 +1,318 @@
+//! A generic executor for the small register machines that show up in many puzzles.
+//!
+//! Each puzzle only defines its instructions and what they do to the machine state (by
+//! implementing [`InstructionSet`]), and [`Vm`] takes care of the program counter, cycle
+//! counting, infinite loop detection and tracing.
+
+/// What the program counter should do after an instruction was executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Continue to the next instruction
+    Next,
+    /// Jump relative to the current instruction
+    Jump(isize),
+    /// Jump to an absolute instruction index
+    Goto(usize),
+    /// Stop the machine
+    Halt,
+}
+
+/// The reason a [`Vm`] stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    /// The program counter moved outside of the program (including a jump to a negative index)
+    OutOfBounds,
+    /// An instruction returned [`Flow::Halt`]
+    Halted,
+    /// Loop detection is enabled and the instruction at `pc` was about to run a second time
+    InfiniteLoop { pc: usize },
+}
+
+/// A set of instructions that can be executed by a [`Vm`]
+pub trait InstructionSet: std::fmt::Debug {
+    /// Registers, accumulators, outputs, or anything else instructions read or modify
+    type State: std::fmt::Debug;
+
+    /// Applies this instruction to the machine state
+    fn execute(&self, state: &mut Self::State) -> Flow;
+
+    /// The number of cycles this instruction takes to complete. Defaults to 1
+    fn cycles(&self) -> usize {
+        1
+    }
+}
+
+/// Runs a program made of instructions from a single [`InstructionSet`]
+///
+/// ```
+/// use advent_of_code_common::vm::{Exit, Flow, InstructionSet, Vm};
+///
+/// #[derive(Debug)]
+/// enum Op {
+///     Add(i32),
+///     Jump(isize),
+/// }
+///
+/// impl InstructionSet for Op {
+///     type State = i32;
+///
+///     fn execute(&self, acc: &mut i32) -> Flow {
+///         match self {
+///             Self::Add(n) => *acc += n,
+///             Self::Jump(offset) => return Flow::Jump(*offset),
+///         }
+///         Flow::Next
+///     }
+/// }
+///
+/// let program = [Op::Add(1), Op::Add(2), Op::Jump(-2)];
+/// let mut vm = Vm::new(&program, 0).with_loop_detection();
+/// assert_eq!(vm.run(), Exit::InfiniteLoop { pc: 0 });
+/// assert_eq!(vm.state, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vm<'a, I: InstructionSet> {
+    program: &'a [I],
+    pub state: I::State,
+    /// Index of the next instruction to execute
+    pub pc: usize,
+    /// Total cycles elapsed, see [`InstructionSet::cycles`]
+    pub cycle: usize,
+    /// Total instructions executed
+    pub steps: usize,
+    visited: Option<Vec<bool>>,
+    /// How the machine stopped on a step that also met the predicate of [`Self::run_until`]
+    pending_exit: Option<Exit>,
+}
+
+impl<'a, I: InstructionSet> Vm<'a, I> {
+    pub const fn new(program: &'a [I], state: I::State) -> Self {
+        Self {
+            program,
+            state,
+            pc: 0,
+            cycle: 0,
+            steps: 0,
+            visited: None,
+            pending_exit: None,
+        }
+    }
+
+    /// Stop with [`Exit::InfiniteLoop`] as soon as any instruction is about to run twice.
+    ///
+    /// This is only a real infinite loop if the control flow doesn't depend on the state
+    /// (such as a program made of only unconditional jumps).
+    #[must_use]
+    pub fn with_loop_detection(mut self) -> Self {
+        self.visited = Some(vec![false; self.program.len()]);
+        self
+    }
+
+    #[must_use]
+    pub const fn program(&self) -> &'a [I] {
+        self.program
+    }
+
+    /// Executes a single instruction, calling `hook` once for every cycle it takes
+    /// (with the cycle number and the state *during* that cycle, before the instruction
+    /// completes). Returns `Some` if the machine has stopped.
+    pub fn step_with<F>(&mut self, hook: &mut F) -> Option<Exit>
+    where
+        F: FnMut(usize, &I::State),
+    {
+        let Some(instruction) = self.program.get(self.pc) else {
+            return Some(Exit::OutOfBounds);
+        };
+        if let Some(visited) = &mut self.visited {
+            if visited[self.pc] {
+                return Some(Exit::InfiniteLoop { pc: self.pc });
+            }
+            visited[self.pc] = true;
+        }
+        for _ in 0..instruction.cycles() {
+            self.cycle += 1;
+            hook(self.cycle, &self.state);
+        }
+        let flow = instruction.execute(&mut self.state);
+        self.steps += 1;
+        tracing::trace!(
+            pc = self.pc,
+            cycle = self.cycle,
+            ?instruction,
+            ?flow,
+            state = ?self.state
+        );
+        let next = match flow {
+            Flow::Next => self.pc.checked_add(1),
+            Flow::Jump(offset) => self.pc.checked_add_signed(offset),
+            Flow::Goto(index) => Some(index),
+            Flow::Halt => return Some(Exit::Halted),
+        };
+        match next {
+            Some(next) if next < self.program.len() => {
+                self.pc = next;
+                None
+            }
+            Some(next) => {
+                self.pc = next;
+                Some(Exit::OutOfBounds)
+            }
+            None => Some(Exit::OutOfBounds),
+        }
+    }
+
+    /// Executes a single instruction. Returns `Some` if the machine has stopped.
+    pub fn step(&mut self) -> Option<Exit> {
+        self.step_with(&mut |_, _| {})
+    }
+
+    /// Runs the program until it stops, calling `hook` on every cycle (see [`Self::step_with`])
+    pub fn run_with_hook<F>(&mut self, mut hook: F) -> Exit
+    where
+        F: FnMut(usize, &I::State),
+    {
+        loop {
+            if let Some(exit) = self.step_with(&mut hook) {
+                return exit;
+            }
+        }
+    }
+
+    /// Runs the program until it stops
+    pub fn run(&mut self) -> Exit {
+        self.run_with_hook(|_, _| {})
+    }
+
+    /// Runs the program until `predicate` holds for the state after an instruction, or until it
+    /// stops. Returns `None` if the predicate was met, even by the last instruction before the
+    /// machine stopped, in which case the next call returns that [`Exit`] right away.
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Option<Exit>
+    where
+        F: FnMut(&I::State) -> bool,
+    {
+        if let Some(exit) = self.pending_exit.take() {
+            return Some(exit);
+        }
+        loop {
+            let steps = self.steps;
+            let exit = self.step();
+            // A machine that had already stopped didn't execute anything new
+            if self.steps > steps && predicate(&self.state) {
+                self.pending_exit = exit;
+                return None;
+            }
+            if exit.is_some() {
+                return exit;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        Noop,
+        Add(i64),
+        Slow(i64),
+        JumpIfNonZero(isize),
+        Halt,
+    }
+
+    impl InstructionSet for Op {
+        type State = i64;
+
+        fn execute(&self, x: &mut i64) -> Flow {
+            match self {
+                Self::Add(n) | Self::Slow(n) => *x += n,
+                Self::JumpIfNonZero(offset) if *x != 0 => return Flow::Jump(*offset),
+                Self::Noop | Self::JumpIfNonZero(_) => {}
+                Self::Halt => return Flow::Halt,
+            }
+            Flow::Next
+        }
+
+        fn cycles(&self) -> usize {
+            match self {
+                Self::Slow(_) => 2,
+                _ => 1,
+            }
+        }
+    }
+
+    #[test]
+    fn runs_off_the_end() {
+        let program = [Op::Add(3), Op::Add(-1), Op::JumpIfNonZero(-1), Op::Noop];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run(), Exit::OutOfBounds);
+        assert_eq!(vm.state, 0);
+        assert_eq!(vm.pc, 4);
+        assert_eq!(vm.steps, 1 + 3 + 3 + 1);
+    }
+
+    #[test]
+    fn negative_jump() {
+        let program = [Op::Add(1), Op::JumpIfNonZero(-5)];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run(), Exit::OutOfBounds);
+        assert_eq!(vm.pc, 1);
+    }
+
+    #[test]
+    fn halts() {
+        let program = [Op::Add(1), Op::Halt, Op::Add(1)];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run(), Exit::Halted);
+        assert_eq!(vm.state, 1);
+        assert_eq!(vm.pc, 1);
+    }
+
+    #[test]
+    fn detects_loops() {
+        let program = [Op::Add(1), Op::JumpIfNonZero(-1)];
+        let mut vm = Vm::new(&program, 0).with_loop_detection();
+        assert_eq!(vm.run(), Exit::InfiniteLoop { pc: 0 });
+        assert_eq!(vm.state, 1);
+    }
+
+    #[test]
+    fn cycle_hooks() {
+        let program = [Op::Noop, Op::Slow(3), Op::Slow(-5)];
+        let mut during = Vec::new();
+        let mut vm = Vm::new(&program, 1);
+        vm.run_with_hook(|cycle, &x| during.push((cycle, x)));
+        assert_eq!(during, [(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(vm.cycle, 5);
+        assert_eq!(vm.state, -1);
+    }
+
+    #[test]
+    fn resumes_after_predicate() {
+        let program = [Op::Add(1), Op::Add(1), Op::Add(1)];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run_until(|&x| x == 2), None);
+        assert_eq!(vm.pc, 2);
+        assert_eq!(vm.run_until(|&x| x == 2), Some(Exit::OutOfBounds));
+        assert_eq!(vm.state, 3);
+    }
+
+    #[test]
+    fn predicate_on_the_last_step() {
+        let program = [Op::Add(1), Op::Add(1), Op::Halt];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run_until(|&x| x == 2), None);
+        assert_eq!(vm.run_until(|_| false), Some(Exit::Halted));
+        let program = [Op::Add(1), Op::Halt];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run_until(|&x| x == 1), None);
+        assert_eq!(vm.run_until(|&x| x == 1), None);
+        assert_eq!(vm.run_until(|&x| x == 1), Some(Exit::Halted));
+        let program = [Op::Add(1), Op::Add(1)];
+        let mut vm = Vm::new(&program, 0);
+        assert_eq!(vm.run_until(|&x| x == 2), None);
+        assert_eq!(vm.pc, 2);
+        assert_eq!(vm.run_until(|&x| x == 2), Some(Exit::OutOfBounds));
+    }
+}
@@ -1,6 +1,5 @@
-use std::collections::HashSet;
-
 use crate::default_input_path;
+use advent_of_code_common::algorithms::disjoint_set::DisjointSet;
 use advent_of_code_common::Solver;
 use itertools::Itertools;
 
@@ -69,54 +68,29 @@ impl Solver<'_> for Part1And2 {
     }
 }
 
-fn merge_families(mut families: Vec<HashSet<usize>>) -> Vec<HashSet<usize>> {
-    let mut distinct_families = Vec::new();
-    while let Some(mut family) = families.pop() {
-        if family.is_empty() {
-            families.retain(|f| !f.is_empty());
-            continue;
-        }
-        let mut merged = true;
-        while merged {
-            merged = false;
-            for other_family in &mut families {
-                if !family.is_disjoint(other_family) {
-                    family.extend(other_family.iter());
-                    other_family.clear();
-                    merged = true;
-                }
-            }
-        }
-        if !merged {
-            distinct_families.push(family);
-        }
-    }
-    distinct_families
-}
-
 struct Part3;
 impl Solver<'_> for Part3 {
     type Output = usize;
 
     fn solve(&self, input: &str) -> Self::Output {
         let sequences = DNA::from_input(input);
-        let families: Vec<_> = sequences
+        let mut families: DisjointSet<usize> = sequences.iter().map(|dna| dna.index).collect();
+        for seq in sequences
             .iter()
             .permutations(3)
             .filter(|seq| seq[1].index < seq[2].index) // Ensure unique parent pairs
-            .filter_map(|seq| {
-                if seq[0].similarity(seq[1], seq[2]).is_some() {
-                    Some(HashSet::from([seq[0].index, seq[1].index, seq[2].index]))
-                } else {
-                    None
-                }
-            })
-            .collect();
-        let groups = merge_families(families);
-        groups
+            .filter(|seq| seq[0].similarity(seq[1], seq[2]).is_some())
+        {
+            families.union(&seq[0].index, &seq[1].index);
+            families.union(&seq[0].index, &seq[2].index);
+        }
+        // A lone sequence is not a family, even if it is the only one
+        families
+            .components()
             .iter()
-            .max_by_key(|g| g.len())
-            .map(|g| g.iter().sum())
+            .filter(|family| family.len() > 1)
+            .max_by_key(|family| family.len())
+            .map(|family| family.iter().copied().sum())
             .unwrap_or_default()
     }
 
@@ -148,4 +122,10 @@ mod tests {
         expect_solution!(Part3, 5, 36);
         expect_solution!(Part3, 6, 40528);
     }
+
+    #[test]
+    fn no_families() {
+        assert_eq!(Part3.solve("1:AAAA\n2:CCCC\n3:GGGG"), 0);
+        assert_eq!(Part3.solve("1:ACGT\n2:AAAA\n3:CCGT\n4:TTTT"), 1 + 2 + 3);
+    }
 }
@@ -1,8 +1,8 @@
 use crate::default_input_path;
 use advent_of_code_common::grid::{Coords, Grid, GridCell, Point};
+use advent_of_code_common::render::{Color, Style};
 use advent_of_code_common::Solver;
 use advent_of_code_macros::char_enum;
-use colored::Colorize;
 use itertools::Itertools;
 use std::collections::{BTreeMap, HashSet};
 
@@ -154,11 +154,11 @@ impl std::fmt::Display for Move {
         let x = usize::try_from(self.to.x).unwrap_or(usize::MAX);
         let x = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().nth(x).unwrap_or('?');
         let y = self.to.y + 1;
-        let ty = match self.ty {
-            Entity::Sheep => self.ty.to_string().green(),
-            Entity::Dragon => self.ty.to_string().red(),
-            Entity::Shield => self.ty.to_string().yellow(),
-        };
+        let ty = Style::new(self.ty.into()).color(match self.ty {
+            Entity::Sheep => Color::GREEN,
+            Entity::Dragon => Color::RED,
+            Entity::Shield => Color::YELLOW,
+        });
         write!(f, "{ty}>{x}{y}")
     }
 }
@@ -1,5 +1,5 @@
 use advent_of_code_common::grid::{Direction, Grid};
-use colored::Colorize;
+use advent_of_code_common::render::{Color, Style};
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -101,12 +101,12 @@ impl From<char> for GridCell {
 
 impl std::fmt::Display for GridCell {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let c = if self.is_rune.get() {
-            self.c.to_string().red()
+        let style = Style::new(self.c);
+        if self.is_rune.get() {
+            write!(f, "{}", style.color(Color::RED))
         } else {
-            self.c.to_string().normal()
-        };
-        write!(f, "{c}")
+            write!(f, "{style}")
+        }
     }
 }
 
@@ -0,0 +1,178 @@
+use quote::quote;
+
+/// A piece of an `#[aoc_format("...")]` template
+enum Piece {
+    Literal(String),
+    Optional(String),
+    Field(String),
+    List(String, String),
+}
+
+/// Splits a template like `"p={x},{y} unit[s] {values:,}"` into its pieces. `{{`, `}}`, `[[` and
+/// `]]` stand for the literal brackets.
+fn parse_template(template: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' | '}' | ']' if chars.peek() == Some(&c) => {
+                chars.next();
+                literal.push(c);
+            }
+            '{' | '[' => {
+                let close = if c == '{' { '}' } else { ']' };
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some(n) if n == close => break,
+                        Some(n) => inner.push(n),
+                        None => {
+                            return Err(format!(
+                                "unterminated `{c}`, close it with `{close}` or use `{c}{c}` for a \
+                                 literal one"
+                            ));
+                        }
+                    }
+                }
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                }
+                pieces.push(if c == '[' {
+                    Piece::Optional(inner)
+                } else if let Some((name, separator)) = inner.split_once(':') {
+                    Piece::List(name.trim().to_string(), separator.to_string())
+                } else {
+                    Piece::Field(inner.trim().to_string())
+                });
+            }
+            '}' | ']' => return Err(format!("unmatched `{c}`, use `{c}{c}` for a literal one")),
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+    // Fields are delimited by the literal text after them, so two of them can't touch
+    let mut previous_field = false;
+    for piece in &pieces {
+        match piece {
+            Piece::Field(name) | Piece::List(name, _) if previous_field => {
+                return Err(format!(
+                    "`{name}` must be separated from the field before it"
+                ));
+            }
+            Piece::Field(_) | Piece::List(..) => previous_field = true,
+            Piece::Literal(_) => previous_field = false,
+            Piece::Optional(_) => {}
+        }
+    }
+    Ok(pieces)
+}
+
+/// The `#[aoc_format(default = ...)]` value of a field, for fields that are not in the template
+fn field_default(field: &syn::Field) -> syn::Result<proc_macro2::TokenStream> {
+    for attr in &field.attrs {
+        if attr.path().is_ident("aoc_format") {
+            let meta: syn::MetaNameValue = attr.parse_args()?;
+            if !meta.path.is_ident("default") {
+                return Err(syn::Error::new_spanned(
+                    meta.path,
+                    "expected `default = ...`",
+                ));
+            }
+            let value = meta.value;
+            return Ok(quote! { #value });
+        }
+    }
+    Ok(quote! { ::std::default::Default::default() })
+}
+
+pub fn from_input_impl(input: &syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "FromInput can only be derived for structs with named fields",
+        ));
+    };
+    let format = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("aoc_format"))
+        .ok_or_else(|| {
+            syn::Error::new_spanned(ident, "FromInput needs an #[aoc_format(\"...\")] template")
+        })?
+        .parse_args::<syn::LitStr>()?;
+    let pieces =
+        parse_template(&format.value()).map_err(|e| syn::Error::new_spanned(&format, e))?;
+
+    let mut segments = Vec::new();
+    let mut template_fields = Vec::new();
+    for piece in &pieces {
+        segments.push(match piece {
+            Piece::Literal(text) => quote! { Segment::Literal(#text) },
+            Piece::Optional(text) => quote! { Segment::Optional(#text) },
+            Piece::Field(_) => quote! { Segment::Field },
+            Piece::List(_, separator) => quote! { Segment::List(#separator) },
+        });
+        match piece {
+            Piece::Field(name) => template_fields.push((name, None)),
+            Piece::List(name, separator) => template_fields.push((name, Some(separator))),
+            Piece::Literal(_) | Piece::Optional(_) => {}
+        }
+    }
+    for (i, (name, _)) in template_fields.iter().enumerate() {
+        let known = fields
+            .named
+            .iter()
+            .any(|f| f.ident.as_ref().is_some_and(|f| f == name));
+        if !known {
+            let message = format!("`{ident}` has no field named `{name}`");
+            return Err(syn::Error::new_spanned(&format, message));
+        }
+        if template_fields[..i].iter().any(|(other, _)| other == name) {
+            let message = format!("`{name}` appears more than once in the template");
+            return Err(syn::Error::new_spanned(&format, message));
+        }
+    }
+
+    let mut inits = Vec::new();
+    for field in &fields.named {
+        let field_ident = field.ident.as_ref().expect("named field");
+        let name = field_ident.to_string();
+        let position = template_fields.iter().position(|(n, _)| **n == name);
+        inits.push(match position.map(|i| (i, template_fields[i].1)) {
+            Some((i, None)) => quote! { #field_ident: parse_field(s, fields[#i], #name)? },
+            Some((i, Some(separator))) => {
+                quote! { #field_ident: parse_list(s, fields[#i], #separator, #name)? }
+            }
+            None => {
+                let default = field_default(field)?;
+                quote! { #field_ident: #default }
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = advent_of_code_common::file::parse::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                use advent_of_code_common::file::parse::{
+                    match_template, parse_field, parse_list, Segment,
+                };
+                const TEMPLATE: &[Segment] = &[#(#segments),*];
+                let fields = match_template(s, TEMPLATE)?;
+                Ok(Self {
+                    #(#inits,)*
+                })
+            }
+        }
+    })
+}
@@ -4,6 +4,7 @@ pub fn enum_char_impls(input: &syn::DeriveInput, display: bool) -> proc_macro::T
     let ident = &input.ident;
     let mut to_value = vec![];
     let mut from_value = vec![];
+    let mut values = vec![];
     if let syn::Data::Enum(data) = &input.data {
         for variant in &data.variants {
             for attr in &variant.attrs {
@@ -18,6 +19,7 @@ pub fn enum_char_impls(input: &syn::DeriveInput, display: bool) -> proc_macro::T
                             from_value.push(quote! {
                                 #value => Ok(Self::#v_ident)
                             });
+                            values.push(quote! { #value }.to_string());
                         }
                         Err(e) => {
                             return e.to_compile_error().into();
@@ -27,6 +29,7 @@ pub fn enum_char_impls(input: &syn::DeriveInput, display: bool) -> proc_macro::T
             }
         }
     }
+    let expected = format!("one of {}", values.join(", "));
     let mut expanded = quote! {
         impl From<#ident> for char {
             fn from(v: #ident) -> Self {
@@ -39,13 +42,13 @@ pub fn enum_char_impls(input: &syn::DeriveInput, display: bool) -> proc_macro::T
         }
 
         impl TryFrom<char> for #ident {
-            type Error = &'static str;
+            type Error = advent_of_code_common::Error;
             fn try_from(c: char) -> Result<Self, Self::Error> {
                 match c {
                     #(
                         #from_value,
                     )*
-                    _ => Err("Invalid char"),
+                    _ => Err(advent_of_code_common::Error::invalid_char(c, #expected)),
                 }
             }
         }
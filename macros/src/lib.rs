@@ -1,6 +1,7 @@
 use quote::{ToTokens, quote};
 use syn::parse_macro_input;
 
+mod from_input;
 mod impls;
 mod solver_fn;
 
@@ -109,6 +110,25 @@ pub fn all_the_days(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     .into()
 }
 
+/// Derives `FromStr` for a struct from a sentence template, like
+/// `#[aoc_format("p={x},{y} v={vx},{vy}")]`.
+///
+/// - `{field}` parses the text up to the next literal part with the field's own `FromStr`
+/// - `{field:sep}` parses a `Vec` of values separated by `sep`
+/// - `[text]` is optional text, like a plural `second[s]`
+/// - `{{`, `}}`, `[[` and `]]` are literal brackets
+///
+/// Fields that are not in the template get their `Default`, or the value given with
+/// `#[aoc_format(default = ...)]` on the field. Errors are
+/// `advent_of_code_common::file::parse::ParseError`s, with the line and column of the problem.
+#[proc_macro_derive(FromInput, attributes(aoc_format))]
+pub fn derive_from_input(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    from_input::from_input_impl(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 #[proc_macro_derive(CharEnum, attributes(c))]
 pub fn derive_char_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
@@ -1,3 +1,4 @@
+use advent_of_code_common::boxes::{BoxN, BoxSet};
 use itertools::{iproduct, Product};
 use std::{num::ParseIntError, ops::RangeInclusive};
 
@@ -68,8 +69,18 @@ impl TryFrom<&str> for Rect {
     }
 }
 
+impl From<&Rect> for BoxN<2> {
+    fn from(rect: &Rect) -> Self {
+        let corners = [&rect.top_left, &rect.bottom_right]
+            .map(|p| [p.x, p.y].map(|v| i64::try_from(v).expect("coordinate fits in i64")));
+        let [[x1, y1], [x2, y2]] = corners;
+        Self::new([x1.min(x2), y1.min(y2)], [x1.max(x2), y1.max(y2)]).expect("non-empty rect")
+    }
+}
+
+/// Only tracks which rectangles are lit, instead of every single light
 struct GridOnOff {
-    lights: Vec<Vec<bool>>,
+    lights: BoxSet<2>,
 }
 
 trait LightGrid {
@@ -94,30 +105,24 @@ trait LightGrid {
 impl LightGrid for GridOnOff {
     fn new() -> Self {
         Self {
-            lights: vec![vec![false; 1000]; 1000], // TODO: Check if this allocates more than necessary
+            lights: BoxSet::new(),
         }
     }
 
     fn toggle(&mut self, rect: &Rect) {
-        self.get_product(rect).for_each(|(x, y)| {
-            self.lights[x][y] = !self.lights[x][y];
-        });
+        self.lights.toggle(rect.into());
     }
 
     fn turn_up(&mut self, rect: &Rect) {
-        self.get_product(rect).for_each(|(x, y)| {
-            self.lights[x][y] = true;
-        });
+        self.lights.insert(rect.into());
     }
 
     fn turn_down(&mut self, rect: &Rect) {
-        self.get_product(rect).for_each(|(x, y)| {
-            self.lights[x][y] = false;
-        });
+        self.lights.remove(&rect.into());
     }
 
     fn count_visible(&self) -> usize {
-        self.lights.iter().flatten().filter(|&&x| x).count()
+        usize::try_from(self.lights.volume()).expect("count fits in usize")
     }
 }
 
@@ -1,9 +1,12 @@
-use anyhow::anyhow;
+use advent_of_code_macros::FromInput;
 
 const EXAMPLE: &str = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
 Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, FromInput)]
+#[aoc_format(
+    "{name} can fly {speed} km/s for {endurance} second[s], but then must rest for {rest} second[s]."
+)]
 struct Reindeer {
     name: String,
     speed: usize,
@@ -12,31 +15,12 @@ struct Reindeer {
     points: usize,
 }
 
-impl std::str::FromStr for Reindeer {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split_whitespace();
-        let name = split.next().ok_or_else(|| anyhow!("No name"))?.to_string();
-        let speed = split.nth(2).ok_or_else(|| anyhow!("No speed"))?.parse()?;
-        let endurance = split.nth(2).ok_or_else(|| anyhow!("No endurance"))?.parse()?;
-        let rest = split.nth(6).ok_or_else(|| anyhow!("No rest"))?.parse()?;
-        Ok(Self {
-            name,
-            speed,
-            endurance,
-            rest,
-            points: 0,
-        })
-    }
-}
-
 impl Reindeer {
     fn position_at_t(&self, t: usize) -> usize {
         let run_length = self.endurance + self.rest;
         let full_runs = t / run_length;
         let modulo = t - (full_runs * run_length);
-        
+
         (full_runs * self.speed * self.endurance) + (modulo.min(self.endurance) * self.speed)
     }
 }
@@ -83,6 +67,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let single: Reindeer =
+            "Vixen can fly 8 km/s for 1 second, but then must rest for 1 second."
+                .parse()
+                .unwrap();
+        assert_eq!((single.endurance, single.rest), (1, 1));
+        let error = "Comet can fly fast km/s for 10 seconds, but then must rest for 127 seconds."
+            .parse::<Reindeer>()
+            .unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (15, "fast"));
+        let error = "Comet can run".parse::<Reindeer>().unwrap_err();
+        assert_eq!(error.expected, "\" can fly \"");
+    }
+
     #[test]
     fn test_example_1() {
         let mut lines = EXAMPLE.lines();
@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 
-use anyhow::{anyhow, Context};
+use advent_of_code_macros::FromInput;
 use itertools::Itertools;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, FromInput)]
+#[aoc_format(
+    "{name}: capacity {capacity}, durability {durability}, flavor {flavor}, texture {texture}, calories {calories}"
+)]
 struct Ingredient {
     name: String,
     capacity: f64,
@@ -11,61 +14,10 @@ struct Ingredient {
     flavor: f64,
     texture: f64,
     calories: f64,
+    #[aoc_format(default = 1)]
     quantity: u32,
 }
 
-impl std::str::FromStr for Ingredient {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split_whitespace();
-        let name = split
-            .next()
-            .ok_or_else(|| anyhow!("No name"))?
-            .trim_end_matches(':')
-            .to_string();
-        let capacity = split
-            .nth(1)
-            .ok_or_else(|| anyhow!("No capacity"))?
-            .trim_end_matches(',')
-            .parse()
-            .context("capacity")?;
-        let durability = split
-            .nth(1)
-            .ok_or_else(|| anyhow!("No durability"))?
-            .trim_end_matches(',')
-            .parse()
-            .context("durability")?;
-        let flavor = split
-            .nth(1)
-            .ok_or_else(|| anyhow!("No flavor"))?
-            .trim_end_matches(',')
-            .parse()
-            .context("flavor")?;
-        let texture = split
-            .nth(1)
-            .ok_or_else(|| anyhow!("No texture"))?
-            .trim_end_matches(',')
-            .parse()
-            .context("texture")?;
-        let calories = split
-            .nth(1)
-            .ok_or_else(|| anyhow!("No calories"))?
-            .trim_end_matches(',')
-            .parse()
-            .context("calories")?;
-        Ok(Self {
-            name,
-            capacity,
-            durability,
-            flavor,
-            texture,
-            calories,
-            quantity: 1,
-        })
-    }
-}
-
 impl Ingredient {
     fn set_quantity(&mut self, quantity: u32) {
         let new_quantity = f64::from(quantity);
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use advent_of_code_common::vm::{Flow, InstructionSet, Vm};
 use anyhow::anyhow;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,9 +15,9 @@ enum Instruction {
     Half(Register),
     Triple(Register),
     Increment(Register),
-    Jump(i32),
-    JumpIfEven(Register, i32),
-    JumpIfOne(Register, i32),
+    Jump(isize),
+    JumpIfEven(Register, isize),
+    JumpIfOne(Register, isize),
     #[default]
     UnknownInstruction,
 }
@@ -34,7 +35,7 @@ impl FromStr for Instruction {
             .ok_or_else(|| anyhow!("No second part"))?
             .trim_start_matches('+')
             .trim_end_matches(',');
-        let third: i32 = split
+        let third: isize = split
             .next()
             .unwrap_or("")
             .trim_start_matches('+')
@@ -59,44 +60,30 @@ impl FromStr for Instruction {
     }
 }
 
-#[allow(
-    clippy::cast_possible_truncation,
-    clippy::cast_sign_loss,
-    clippy::cast_possible_wrap
-)]
-fn process(instructions: &[Instruction], mut a: u32, mut b: u32) -> (u32, u32) {
-    let mut index = 0;
-    while index < instructions.len() {
-        let current = instructions
-            .get(index)
-            .expect("Inside the instruction bounds");
-        let mut new_index = index as i32 + 1;
-        (a, b, new_index) = match current {
-            Instruction::Half(Register::A) => (a / 2, b, new_index),
-            Instruction::Half(Register::B) => (a, b / 2, new_index),
-            Instruction::Triple(Register::A) => (a * 3, b, new_index),
-            Instruction::Triple(Register::B) => (a, b * 3, new_index),
-            Instruction::Increment(Register::A) => (a + 1, b, new_index),
-            Instruction::Increment(Register::B) => (a, b + 1, new_index),
-            Instruction::JumpIfEven(Register::A, d) if a.is_multiple_of(2) => {
-                (a, b, index as i32 + d)
-            }
-            Instruction::JumpIfEven(Register::B, d) if b.is_multiple_of(2) => {
-                (a, b, index as i32 + d)
+impl InstructionSet for Instruction {
+    type State = [u32; 2];
+
+    fn execute(&self, registers: &mut [u32; 2]) -> Flow {
+        match *self {
+            Self::Half(r) => registers[r as usize] /= 2,
+            Self::Triple(r) => registers[r as usize] *= 3,
+            Self::Increment(r) => registers[r as usize] += 1,
+            Self::Jump(d) => return Flow::Jump(d),
+            Self::JumpIfEven(r, d) if registers[r as usize].is_multiple_of(2) => {
+                return Flow::Jump(d)
             }
-            Instruction::JumpIfOne(Register::A, d) if a == 1 => (a, b, index as i32 + d),
-            Instruction::JumpIfOne(Register::B, d) if b == 1 => (a, b, index as i32 + d),
-            Instruction::JumpIfOne(_, _) | Instruction::JumpIfEven(_, _) => (a, b, new_index),
-            Instruction::Jump(d) => (a, b, index as i32 + d),
-            Instruction::UnknownInstruction => break,
-        };
-        if new_index >= 0 && new_index < instructions.len() as i32 {
-            index = new_index as usize;
-        } else {
-            break;
+            Self::JumpIfOne(r, d) if registers[r as usize] == 1 => return Flow::Jump(d),
+            Self::JumpIfOne(_, _) | Self::JumpIfEven(_, _) => {}
+            Self::UnknownInstruction => return Flow::Halt,
         }
+        Flow::Next
     }
-    (a, b)
+}
+
+fn process(instructions: &[Instruction], a: u32, b: u32) -> (u32, u32) {
+    let mut vm = Vm::new(instructions, [a, b]);
+    vm.run();
+    vm.state.into()
 }
 
 fn parse_input(s: &str) -> Vec<Instruction> {
@@ -108,6 +95,12 @@ mod tests {
     use super::*;
     use std::fs::read_to_string;
 
+    #[test]
+    fn test_example() {
+        let input = "inc a\njio a, +2\ntpl a\ninc a";
+        assert_eq!(process(&parse_input(input), 0, 0), (2, 0));
+    }
+
     #[test]
     fn test_part_1() {
         let input = read_to_string("../inputs/2015/day23.txt").unwrap();
@@ -0,0 +1,71 @@
+use advent_of_code_common::math::mod_pow;
+use advent_of_code_macros::aoc_solver;
+
+const FIRST_CODE: u128 = 20_151_125;
+const MULTIPLIER: u128 = 252_533;
+const MODULUS: u128 = 33_554_393;
+
+/// The row and column from "Enter the code at row R, column C."
+fn parse_position(input: &str) -> Option<(u128, u128)> {
+    let mut numbers = input
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|s| s.parse().ok());
+    Some((numbers.next()?, numbers.next()?))
+}
+
+/// The code at 1-based `row` and `col`. Codes are filled in diagonal by diagonal, each one being
+/// the previous code times a constant, so the n-th code is a single modular exponentiation.
+const fn code_at(row: u128, col: u128) -> u128 {
+    let diagonal = row + col - 1;
+    let index = diagonal * (diagonal - 1) / 2 + col - 1;
+    FIRST_CODE * mod_pow(MULTIPLIER, index, MODULUS) % MODULUS
+}
+
+#[aoc_solver(file = "inputs/2015/day25.txt", expected = Some(9_132_360))]
+fn part_1(input: &str) -> Option<u128> {
+    parse_position(input).map(|(row, col)| code_at(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The top left corner of the code grid, from the puzzle
+    const TABLE: [[u128; 6]; 6] = [
+        [
+            20_151_125, 18_749_137, 17_289_845, 30_943_339, 10_071_777, 33_511_524,
+        ],
+        [
+            31_916_031, 21_629_792, 16_929_656, 7_726_640, 15_514_188, 4_041_754,
+        ],
+        [
+            16_080_970, 8_057_251, 1_601_130, 7_981_243, 11_661_866, 16_474_243,
+        ],
+        [
+            24_592_653, 32_451_966, 21_345_942, 9_380_097, 10_600_672, 31_527_494,
+        ],
+        [
+            77_061, 17_552_253, 28_094_349, 6_899_651, 9_250_759, 31_663_883,
+        ],
+        [
+            33_071_741, 6_796_745, 25_397_450, 24_659_492, 1_534_922, 27_995_004,
+        ],
+    ];
+
+    #[test]
+    fn test_example() {
+        for (row, codes) in (1..).zip(TABLE) {
+            for (col, code) in (1..).zip(codes) {
+                assert_eq!(code_at(row, col), code, "row {row}, column {col}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "To continue, please consult the code grid in the manual.
+                     Enter the code at row 2981, column 3075.";
+        assert_eq!(parse_position(input), Some((2981, 3075)));
+        assert_eq!(parse_position("Enter the code at row 2981."), None);
+    }
+}
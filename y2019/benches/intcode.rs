@@ -0,0 +1,159 @@
+//! Timing of the Intcode core on the heaviest 2019 workloads.
+//!
+//! Run with `cargo bench -p advent_of_code_2019`. The BOOST program (day 9) and the repair droid
+//! program (day 15) are read from `../inputs/2019/` and skipped when missing; a synthetic loop
+//! is always measured. Each workload also runs on the previous `HashMap` core, in
+//! [`baseline`], to show the speed-up.
+use advent_of_code_2019::intcode::{IntcodeComputer, Memory};
+
+#[path = "intcode/baseline.rs"]
+mod baseline;
+
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+const ROUNDS: u32 = 10;
+
+fn bench(name: &str, mut f: impl FnMut() -> i64) {
+    let mut best = Duration::MAX;
+    let mut total = Duration::ZERO;
+    let mut result = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        result = std::hint::black_box(f());
+        let elapsed = start.elapsed();
+        best = best.min(elapsed);
+        total += elapsed;
+    }
+    println!(
+        "{name:<40} best {best:>12?}  mean {:>12?}  (result {result})",
+        total / ROUNDS
+    );
+}
+
+/// The parts of the current and the baseline cores that the workloads use
+trait Core: Clone {
+    fn program(values: Vec<i64>) -> Self;
+
+    /// Queue a single input, run until the program stops, and take the last output
+    fn send(&mut self, input: i64) -> Option<i64>;
+}
+
+impl Core for IntcodeComputer {
+    fn program(values: Vec<i64>) -> Self {
+        Self::new(values)
+    }
+
+    fn send(&mut self, input: i64) -> Option<i64> {
+        self.queue_input(input);
+        self.run();
+        let last = self.output.pop_back();
+        self.output.clear();
+        last
+    }
+}
+
+impl Core for baseline::IntcodeComputer {
+    fn program(values: Vec<i64>) -> Self {
+        Self::new(
+            values
+                .into_iter()
+                .map(|v| isize::try_from(v).expect("fits in isize"))
+                .collect(),
+        )
+    }
+
+    fn send(&mut self, input: i64) -> Option<i64> {
+        self.queue_input(isize::try_from(input).expect("fits in isize"));
+        self.run();
+        let last = self.output.pop_back();
+        self.output.clear();
+        last.map(|v| v as i64)
+    }
+}
+
+fn read_program(day: &str) -> Option<Vec<i64>> {
+    let path = format!("../inputs/2019/{day}.txt");
+    match std::fs::read_to_string(&path) {
+        Ok(s) => Some(
+            s.trim()
+                .split(',')
+                .map(|v| v.parse().expect("valid program"))
+                .collect(),
+        ),
+        Err(e) => {
+            println!("Skipping {day}: {path}: {e}");
+            None
+        }
+    }
+}
+
+/// Counts down from `n` to 0 in a tight jump loop, then outputs the counter.
+fn countdown(n: i64) -> Vec<i64> {
+    vec![1101, 0, n, 20, 1001, 20, -1, 20, 1005, 20, 4, 4, 20, 99]
+}
+
+fn run_to_output<C: Core>(program: &[i64], input: i64) -> i64 {
+    C::program(program.to_vec())
+        .send(input)
+        .expect("an output value")
+}
+
+/// The day 15 exploration: every move clones the droid's computer and sends a single command.
+/// `copy` is applied to each clone, to rebuild its memory instead of sharing it with its parent.
+fn explore<C: Core>(computer: &C, copy: impl Fn(&mut C)) -> i64 {
+    let mut seen = HashSet::from([(0, 0)]);
+    let mut queue = VecDeque::from([((0, 0), computer.clone())]);
+    while let Some(((x, y), droid)) = queue.pop_front() {
+        for (command, next) in [
+            (1, (x, y - 1)),
+            (2, (x, y + 1)),
+            (3, (x - 1, y)),
+            (4, (x + 1, y)),
+        ] {
+            if !seen.insert(next) {
+                continue;
+            }
+            let mut droid = droid.clone();
+            copy(&mut droid);
+            if droid.send(command) != Some(0) {
+                queue.push_back((next, droid));
+            }
+        }
+    }
+    i64::try_from(seen.len()).expect("fits in i64")
+}
+
+fn main() {
+    type Baseline = baseline::IntcodeComputer;
+    let program = countdown(1_000_000);
+    bench("synthetic countdown (1M iterations)", || {
+        run_to_output::<IntcodeComputer>(&program, 0)
+    });
+    bench("synthetic countdown, baseline", || {
+        run_to_output::<Baseline>(&program, 0)
+    });
+    if let Some(boost) = read_program("day09") {
+        bench("day09 BOOST (part 2)", || {
+            run_to_output::<IntcodeComputer>(&boost, 2)
+        });
+        bench("day09 BOOST, baseline", || {
+            run_to_output::<Baseline>(&boost, 2)
+        });
+    }
+    if let Some(program) = read_program("day15") {
+        let droid = IntcodeComputer::program(program.clone());
+        bench("day15 exploration (snapshot clones)", || {
+            explore(&droid, |_| {})
+        });
+        bench("day15 exploration (deep copies)", || {
+            explore(&droid, |droid| {
+                droid.memory = Memory::from(droid.memory.to_vec());
+            })
+        });
+        let droid = Baseline::program(program);
+        bench("day15 exploration, baseline", || explore(&droid, |_| {}));
+    }
+}
@@ -2,24 +2,24 @@ use crate::intcode::IntcodeComputer;
 use advent_of_code_macros::aoc_solver;
 
 #[aoc_solver(file = "inputs/2019/day02.txt", expected = 6627023)]
-fn part_1(input: &str) -> isize {
+fn part_1(input: &str) -> i64 {
     let mut computer: IntcodeComputer = input.parse().expect("Invalid input");
-    computer.memory.insert(1, 12);
-    computer.memory.insert(2, 2);
+    computer.memory.set(1, 12);
+    computer.memory.set(2, 2);
     computer.run();
-    *computer.memory.get(&0).unwrap_or(&0)
+    computer.memory.get(0)
 }
 
 #[aoc_solver(file = "inputs/2019/day02.txt", expected = 4019)]
-fn part_2(input: &str) -> isize {
+fn part_2(input: &str) -> i64 {
     let original: IntcodeComputer = input.parse().expect("Invalid input");
     for noun in 0..100 {
         for verb in 0..100 {
             let mut computer = original.clone();
-            computer.memory.insert(1, noun);
-            computer.memory.insert(2, verb);
+            computer.memory.set(1, noun);
+            computer.memory.set(2, verb);
             computer.run();
-            if *computer.memory.get(&0).unwrap_or(&0) == 19_690_720 {
+            if computer.memory.get(0) == 19_690_720 {
                 return 100 * noun + verb;
             }
         }
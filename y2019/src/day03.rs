@@ -1,26 +1,9 @@
 use advent_of_code_common::Solver;
-use advent_of_code_common::grid::{Coords, Direction, Point};
+use advent_of_code_common::coords::{Coords, Direction, Point};
+use advent_of_code_common::geometry::{Intersection, Segment};
 use std::collections::HashSet;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct Line {
-    start: Point,
-    end: Point,
-    dir: Direction,
-    length: isize,
-}
-
-impl Line {
-    fn x_range(&self) -> std::ops::RangeInclusive<isize> {
-        self.start.x.min(self.end.x)..=self.start.x.max(self.end.x)
-    }
-
-    fn y_range(&self) -> std::ops::RangeInclusive<isize> {
-        self.start.y.min(self.end.y)..=self.start.y.max(self.end.y)
-    }
-}
-
-fn parse_line(input: &str) -> Vec<Line> {
+fn parse_wire(input: &str) -> Vec<Segment> {
     let mut start = Point::default();
     input
         .split(',')
@@ -34,42 +17,28 @@ fn parse_line(input: &str) -> Vec<Line> {
                 _ => panic!("Invalid direction"),
             };
             let length: isize = length_str.parse().unwrap_or(0);
-            let line = Line {
-                start,
-                end: start + (dir * length),
-                dir,
-                length,
-            };
+            let line = Segment::new(start, start + (dir * length));
             start = line.end;
             line
         })
         .collect()
 }
 
-fn parse_input(s: &str) -> (Vec<Line>, Vec<Line>) {
+fn parse_input(s: &str) -> (Vec<Segment>, Vec<Segment>) {
     let mut lines = s.lines();
-    let a = parse_line(lines.next().unwrap_or_default());
-    let b = parse_line(lines.next().unwrap_or_default());
+    let a = parse_wire(lines.next().unwrap_or_default());
+    let b = parse_wire(lines.next().unwrap_or_default());
     (a, b)
 }
 
-fn crossings(a: &[Line], b: &[Line]) -> HashSet<(Point, usize, usize)> {
+fn crossings(a: &[Segment], b: &[Segment]) -> HashSet<(Point, usize, usize)> {
     let mut results = HashSet::new();
     for (ai, a) in a.iter().enumerate() {
         for (bi, b) in b.iter().enumerate() {
-            let ax = a.x_range();
-            let ay = a.y_range();
-            let bx = b.x_range();
-            let by = b.y_range();
-            let new_point = if ax.contains(bx.start()) && by.contains(ay.start()) {
-                (*bx.start(), *ay.start()).as_point()
-            } else if bx.contains(ax.start()) && ay.contains(by.start()) {
-                (*ax.start(), *by.start()).as_point()
-            } else {
-                Point::default()
-            };
-            if new_point != Point::default() {
-                results.insert((new_point, ai, bi));
+            if let Some(Intersection::Point(p)) = a.intersection(b)
+                && p != Point::default()
+            {
+                results.insert((p, ai, bi));
             }
         }
     }
@@ -103,9 +72,9 @@ impl Solver<'_> for Part2 {
         crossings(&a, &b)
             .iter()
             .map(|(p, ai, bi)| {
-                let mut distance_a: isize = a[..*ai].iter().map(|line| line.length).sum();
+                let mut distance_a: isize = a[..*ai].iter().map(Segment::len).sum();
                 distance_a += p.manhattan_distance(&a[*ai].start);
-                let mut distance_b: isize = b[..*bi].iter().map(|line| line.length).sum();
+                let mut distance_b: isize = b[..*bi].iter().map(Segment::len).sum();
                 distance_b += p.manhattan_distance(&b[*bi].start);
                 distance_a + distance_b
             })
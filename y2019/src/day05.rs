@@ -3,9 +3,9 @@ use advent_of_code_macros::aoc_solver;
 use std::iter::once;
 
 #[aoc_solver(file = "inputs/2019/day05.txt", expected = 5182797)]
-fn part_1(input: &str) -> isize {
+fn part_1(input: &str) -> i64 {
     let mut computer: IntcodeComputer = input.parse().expect("Invalid input");
-    computer.run_with_input(once(1_isize));
+    computer.run_with_input(once(1_i64));
     computer
         .output
         .pop_back()
@@ -13,9 +13,9 @@ fn part_1(input: &str) -> isize {
 }
 
 #[aoc_solver(file = "inputs/2019/day05.txt", expected = 12077198)]
-fn part_2(input: &str) -> isize {
+fn part_2(input: &str) -> i64 {
     let mut computer: IntcodeComputer = input.parse().expect("Invalid input");
-    computer.run_with_input(once(5_isize));
+    computer.run_with_input(once(5_i64));
     computer
         .output
         .pop_back()
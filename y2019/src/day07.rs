@@ -2,7 +2,7 @@ use crate::intcode::{IntcodeComputer, State};
 use advent_of_code_macros::aoc_solver;
 use itertools::Itertools;
 
-fn run_amplifiers(program: &[isize], phases: &[isize]) -> isize {
+fn run_amplifiers(program: &[i64], phases: &[i64]) -> i64 {
     let mut input = 0;
     let mut computers = vec![IntcodeComputer::new(program.to_vec()); 5];
     for (i, &phase) in phases.iter().enumerate() {
@@ -18,7 +18,7 @@ fn run_amplifiers(program: &[isize], phases: &[isize]) -> isize {
     input
 }
 
-fn run_amplifiers_with_feedback(program: &[isize], phases: &[isize]) -> isize {
+fn run_amplifiers_with_feedback(program: &[i64], phases: &[i64]) -> i64 {
     let mut computers = vec![IntcodeComputer::new(program.to_vec()); 5];
     for (i, &phase) in phases.iter().enumerate() {
         computers[i].queue_input(phase);
@@ -63,8 +63,8 @@ const EXAMPLE3: &str = "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,
     expected = 65210
 )]
 #[aoc_solver(file = "inputs/2019/day07.txt", expected = 20413)]
-fn part_1(program: &str) -> isize {
-    let program: Vec<isize> = program
+fn part_1(program: &str) -> i64 {
+    let program: Vec<i64> = program
         .trim()
         .split(',')
         .map(|s| s.parse().expect("to parse an integer"))
@@ -90,8 +90,8 @@ const EXAMPLE5: &str = "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,
     expected = 18216
 )]
 #[aoc_solver(file = "inputs/2019/day07.txt", expected = 3321777)]
-fn part_2(program: &str) -> isize {
-    let program: Vec<isize> = program
+fn part_2(program: &str) -> i64 {
+    let program: Vec<i64> = program
         .trim()
         .split(',')
         .map(|s| s.parse().expect("to parse an integer"))
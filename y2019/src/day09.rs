@@ -18,17 +18,17 @@ use std::iter::once;
     expected = [1125899906842624]
 )]
 #[aoc_solver(file = "inputs/2019/day09.txt", expected = [3742852857])]
-fn part_1(input: &str) -> Vec<isize> {
+fn part_1(input: &str) -> Vec<i64> {
     let mut computer: IntcodeComputer = input.parse().expect("Invalid input");
-    computer.run_with_input(once(1_isize));
+    computer.run_with_input(once(1_i64));
     println!("Output: {:?}", computer.output);
     computer.output.drain(..).collect()
 }
 
 #[aoc_solver(file = "inputs/2019/day09.txt", expected = [73439])]
-fn part_2(input: &str) -> Vec<isize> {
+fn part_2(input: &str) -> Vec<i64> {
     let mut computer: IntcodeComputer = input.parse().expect("Invalid input");
-    computer.run_with_input(once(2_isize));
+    computer.run_with_input(once(2_i64));
     println!("Output: {:?}", computer.output);
     computer.output.drain(..).collect()
 }
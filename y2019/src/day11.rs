@@ -1,40 +1,38 @@
 use crate::intcode::{IntcodeComputer, State};
-use advent_of_code_common::coords::{Direction, Point};
+use advent_of_code_common::coords::{Direction, Point, Pose, Turn};
 use advent_of_code_macros::aoc_solver;
 use std::collections::HashMap;
 
 struct PaintingRobot {
-    direction: Direction,
-    position: Point,
-    colors: HashMap<Point, isize>,
+    pose: Pose,
+    colors: HashMap<Point, i64>,
 }
 
 impl Default for PaintingRobot {
     fn default() -> Self {
         Self {
-            direction: Direction::North,
-            position: Point::default(),
+            pose: Pose::new(Point::default(), Direction::North),
             colors: HashMap::new(),
         }
     }
 }
 
 impl PaintingRobot {
-    fn run(&mut self, computer: &mut IntcodeComputer, starting_color: isize) {
-        self.colors.insert(self.position, starting_color);
+    fn run(&mut self, computer: &mut IntcodeComputer, starting_color: i64) {
+        self.colors.insert(self.pose.position, starting_color);
         while computer.state != State::Halted {
-            let input_color = *self.colors.get(&self.position).unwrap_or(&0);
+            let input_color = *self.colors.get(&self.pose.position).unwrap_or(&0);
             computer.queue_input(input_color);
             computer.run();
             let color_output = computer.output.pop_front().expect("color output");
             let turn_output = computer.output.pop_front().expect("turn output");
-            self.colors.insert(self.position, color_output);
-            self.direction = match turn_output {
-                0 => self.direction.turn_cw_270(),
-                1 => self.direction.turn_cw_90(),
+            self.colors.insert(self.pose.position, color_output);
+            self.pose.apply(match turn_output {
+                0 => Turn::Left,
+                1 => Turn::Right,
                 _ => unreachable!("invalid turn output"),
-            };
-            self.position += self.direction;
+            });
+            self.pose.advance(1);
         }
     }
 }
@@ -1,38 +1,22 @@
+use advent_of_code_common::coords::Point3;
 use advent_of_code_macros::aoc_solver;
 use itertools::Itertools;
 use std::str::FromStr;
 
-#[derive(Default, Debug)]
-struct V3 {
-    x: isize,
-    y: isize,
-    z: isize,
-}
-
 trait Energy {
     fn energy(&self) -> isize;
 }
 
-impl Energy for V3 {
+impl Energy for Point3 {
     fn energy(&self) -> isize {
-        self.x.abs() + self.y.abs() + self.z.abs()
+        self.manhattan_distance(&Self::default())
     }
 }
 
 #[derive(Debug)]
 struct Moon {
-    position: V3,
-    velocity: V3,
-}
-
-macro_rules! apply_axis_gravity {
-    ($self:expr, $other:expr, $axis:ident) => {
-        if $self.position.$axis < $other.position.$axis {
-            $self.velocity.$axis += 1;
-        } else if $self.position.$axis > $other.position.$axis {
-            $self.velocity.$axis -= 1;
-        }
-    };
+    position: Point3,
+    velocity: Point3,
 }
 
 impl FromStr for Moon {
@@ -47,27 +31,21 @@ impl FromStr for Moon {
             .collect();
         let parts: [isize; 3] = parts.try_into().map_err(|_| "expected 3 parts")?;
         Ok(Self {
-            position: V3 {
-                x: parts[0],
-                y: parts[1],
-                z: parts[2],
-            },
-            velocity: V3::default(),
+            position: Point3 { coords: parts },
+            velocity: Point3::default(),
         })
     }
 }
 
 impl Moon {
-    const fn apply_gravity(&mut self, other: &Self) {
-        apply_axis_gravity!(self, other, x);
-        apply_axis_gravity!(self, other, y);
-        apply_axis_gravity!(self, other, z);
+    fn apply_gravity(&mut self, other: &Self) {
+        for axis in 0..3 {
+            self.velocity[axis] += (other.position[axis] - self.position[axis]).signum();
+        }
     }
 
-    const fn apply_velocity(&mut self) {
-        self.position.x += self.velocity.x;
-        self.position.y += self.velocity.y;
-        self.position.z += self.velocity.z;
+    fn apply_velocity(&mut self) {
+        self.position += self.velocity;
     }
 }
 
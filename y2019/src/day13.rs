@@ -1,7 +1,7 @@
 use crate::intcode::{IntcodeComputer, State};
 use advent_of_code_common::coords::Point;
+use advent_of_code_common::render::{Animation, Canvas, Color, Style};
 use advent_of_code_macros::aoc_solver;
-use colored::Colorize;
 use itertools::Itertools;
 use std::collections::BTreeMap;
 
@@ -14,9 +14,9 @@ enum TileKind {
     Ball = 4,
 }
 
-impl TryFrom<isize> for TileKind {
+impl TryFrom<i64> for TileKind {
     type Error = ();
-    fn try_from(value: isize) -> Result<Self, Self::Error> {
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(Self::Empty),
             1 => Ok(Self::Wall),
@@ -38,7 +38,7 @@ struct Tile {
 struct Game {
     computer: IntcodeComputer,
     tiles: BTreeMap<Point, TileKind>,
-    score: isize,
+    score: i64,
     ball: Option<Point>,
     paddle: Option<Point>,
     dimensions: Option<(isize, isize, isize, isize)>, // (min_x, max_x, min_y, max_y)
@@ -46,39 +46,7 @@ struct Game {
 
 impl std::fmt::Display for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (min_x, max_x, min_y, max_y) = self.dimensions.unwrap_or_default();
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                if x == 0 && y == 0 {
-                    write!(f, "┏")?;
-                } else if x == 0 && y == max_y {
-                    write!(f, "┗")?;
-                } else if x == max_x && y == 0 {
-                    write!(f, "┓")?;
-                } else if x == max_x && y == max_y {
-                    write!(f, "┛")?;
-                } else if x == 0 || x == max_x {
-                    write!(f, "┃")?;
-                } else if y == 0 || y == max_y {
-                    write!(f, "━")?;
-                } else {
-                    let tile = self
-                        .tiles
-                        .get(&Point { x, y })
-                        .copied()
-                        .unwrap_or(TileKind::Empty);
-                    let symbol = match tile {
-                        TileKind::Empty => " ".normal(),
-                        TileKind::Block => "▇".green(),
-                        TileKind::Paddle => "▂".blue().bold(),
-                        TileKind::Ball => "o".red().bold(),
-                        TileKind::Wall => unreachable!("Handled by the border drawing code above"),
-                    };
-                    write!(f, "{symbol}")?;
-                }
-            }
-            writeln!(f)?;
-        }
+        writeln!(f, "{}", self.canvas())?;
         writeln!(
             f,
             "Score: {} | DIFF: {:?}",
@@ -92,7 +60,7 @@ impl Game {
     fn new(program: &str, with_quarters: bool) -> Result<Self, Box<dyn std::error::Error>> {
         let mut computer: IntcodeComputer = program.parse()?;
         if with_quarters {
-            computer.memory.insert(0, 2); // Set quarters
+            computer.memory.set(0, 2); // Set quarters
         }
         Ok(Self {
             computer,
@@ -104,6 +72,44 @@ impl Game {
         })
     }
 
+    /// The screen, with the walls drawn as a frame around it
+    fn canvas(&self) -> Canvas {
+        let (min_x, max_x, min_y, max_y) = self.dimensions.unwrap_or_default();
+        let mut canvas = Canvas::new(min_x..max_x + 1, min_y..max_y + 1, ' ');
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let symbol = if x == 0 && y == 0 {
+                    Style::new('┏')
+                } else if x == 0 && y == max_y {
+                    Style::new('┗')
+                } else if x == max_x && y == 0 {
+                    Style::new('┓')
+                } else if x == max_x && y == max_y {
+                    Style::new('┛')
+                } else if x == 0 || x == max_x {
+                    Style::new('┃')
+                } else if y == 0 || y == max_y {
+                    Style::new('━')
+                } else {
+                    let tile = self
+                        .tiles
+                        .get(&Point { x, y })
+                        .copied()
+                        .unwrap_or(TileKind::Empty);
+                    match tile {
+                        TileKind::Empty => Style::new(' '),
+                        TileKind::Block => Style::new('▇').color(Color::GREEN),
+                        TileKind::Paddle => Style::new('▂').color(Color::BLUE).bold(),
+                        TileKind::Ball => Style::new('o').color(Color::RED).bold(),
+                        TileKind::Wall => unreachable!("Handled by the border drawing code above"),
+                    }
+                };
+                canvas.set(&Point { x, y }, symbol);
+            }
+        }
+        canvas
+    }
+
     fn blocks_remaining(&self) -> usize {
         self.tiles
             .values()
@@ -124,7 +130,10 @@ impl Game {
                 continue;
             }
             let kind = TileKind::try_from(tile_value).expect("invalid tile value");
-            let point = Point { x, y };
+            let point = Point {
+                x: x.try_into().expect("x fits in isize"),
+                y: y.try_into().expect("y fits in isize"),
+            };
             match kind {
                 TileKind::Ball => self.ball = Some(point),
                 TileKind::Paddle => self.paddle = Some(point),
@@ -165,13 +174,16 @@ fn part_1(input: &str) -> usize {
 }
 
 #[aoc_solver(file = "inputs/2019/day13.txt", expected = 13989)]
-fn part_2(input: &str) -> isize {
+fn part_2(input: &str) -> i64 {
     let mut game = Game::new(input, true).expect("valid game");
+    let mut animation = Animation::from_env();
     game.run();
     while game.blocks_remaining() > 0 {
+        animation.frame(|| game.canvas());
         let diff = game.get_diff_input().signum();
         assert!(diff.abs() <= 1, "DIFF should be -1, 0, or 1");
-        game.computer.queue_input(diff);
+        game.computer
+            .queue_input(i64::try_from(diff).expect("diff fits in i64"));
         game.run();
     }
     assert_eq!(game.computer.state, State::Halted);
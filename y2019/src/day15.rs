@@ -54,7 +54,7 @@ fn explore_grid(robot: Robot) -> Option<(Grid<Tile>, usize)> {
                 continue;
             }
             let input_dir = match dir {
-                Direction::North => 1_isize,
+                Direction::North => 1_i64,
                 Direction::South => 2,
                 Direction::West => 3,
                 Direction::East => 4,
@@ -1,4 +1,5 @@
 use crate::intcode::{IntcodeComputer, State};
+use advent_of_code_common::coords::{Pose, Turn};
 use advent_of_code_common::grid::{Direction, Grid, GridCell};
 use advent_of_code_macros::aoc_solver;
 use std::str::FromStr;
@@ -12,7 +13,7 @@ enum Tile {
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ();
+    type Error = advent_of_code_common::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -22,7 +23,10 @@ impl TryFrom<char> for Tile {
             'v' => Ok(Self::Robot(Direction::South)),
             '<' => Ok(Self::Robot(Direction::West)),
             '>' => Ok(Self::Robot(Direction::East)),
-            _ => Err(()),
+            _ => Err(Self::Error::invalid_char(
+                value,
+                "a scaffold tile ('.', '#', '^', 'v', '<' or '>')",
+            )),
         }
     }
 }
@@ -82,16 +86,16 @@ fn ascii_from_output(computer: &mut IntcodeComputer) -> String {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Action {
-    TurnLeft,
-    TurnRight,
+    Turn(Turn),
     Forward(usize),
 }
 
 impl std::fmt::Display for Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::TurnLeft => write!(f, "L"),
-            Self::TurnRight => write!(f, "R"),
+            Self::Turn(Turn::Left) => write!(f, "L"),
+            Self::Turn(Turn::Right) => write!(f, "R"),
+            Self::Turn(Turn::Around) => write!(f, "R,R"),
             Self::Forward(n) => write!(f, "{n}"),
         }
     }
@@ -109,10 +113,13 @@ impl Iterator for Robot {
         let Tile::Robot(previous_direction) = robot.data else {
             unreachable!("robot should have a direction");
         };
-        let left = previous_direction.turn_cw_270();
-        let right = previous_direction.turn_cw_90();
-        for direction in [previous_direction, left, right] {
-            let next_cell = robot_position + direction;
+        let pose = Pose::new(robot_position, previous_direction);
+        for turn in [None, Some(Turn::Left), Some(Turn::Right)] {
+            let mut next = pose;
+            if let Some(turn) = turn {
+                next.apply(turn);
+            }
+            let next_cell = next.ahead(1);
             if matches!(
                 self.grid.get(&next_cell),
                 Some(&GridCell {
@@ -120,22 +127,14 @@ impl Iterator for Robot {
                     ..
                 })
             ) {
-                match direction {
-                    d if d == left => {
-                        self.grid.get_mut(&robot_position).expect("checked").data = Tile::Robot(d);
-                        return Some(Action::TurnLeft);
-                    }
-                    d if d == right => {
-                        self.grid.get_mut(&robot_position).expect("checked").data = Tile::Robot(d);
-                        return Some(Action::TurnRight);
-                    }
-                    d if d == previous_direction => {
-                        self.grid.get_mut(&robot_position).expect("checked").data = Tile::Scaffold;
-                        self.grid.get_mut(&next_cell).expect("checked").data = Tile::Robot(d);
-                        return Some(Action::Forward(1));
-                    }
-                    _ => unreachable!("direction should be one of left, right, or previous"),
+                let robot = self.grid.get_mut(&robot_position).expect("checked");
+                if let Some(turn) = turn {
+                    robot.data = Tile::Robot(next.direction);
+                    return Some(Action::Turn(turn));
                 }
+                robot.data = Tile::Scaffold;
+                self.grid.get_mut(&next_cell).expect("checked").data = Tile::Robot(next.direction);
+                return Some(Action::Forward(1));
             }
         }
         None
@@ -147,7 +146,7 @@ impl FromStr for Robot {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut computer: IntcodeComputer = s.parse().expect("valid intcode");
-        computer.memory.insert(0, 2); // Set to "wake up" the robot
+        computer.memory.set(0, 2); // Set to "wake up" the robot
         computer.run();
         let grid_output = ascii_from_output(&mut computer);
         let (grid_output, extra_output) = grid_output.split_once("\n\n").expect("valid output");
@@ -1,11 +1,167 @@
+use crate::intcode::IntcodeComputer;
 use advent_of_code_macros::aoc_solver;
+use std::ops::RangeInclusive;
 
-#[aoc_solver(file = "inputs/2019/day19.txt", expected = 0)]
+/// How far right to look for the beam on a given row, per row of depth. The search only runs
+/// that far on empty rows, which the narrow beam leaves close to the emitter.
+const MAX_SLOPE: usize = 8;
+
+/// Asks a fresh copy of the drone program whether the point `(x, y)` is pulled by the beam.
+fn drone_probe(drone: &IntcodeComputer) -> impl FnMut(usize, usize) -> bool + '_ {
+    move |x, y| {
+        let mut drone = drone.clone();
+        drone.queue_input(i64::try_from(x).expect("x fits in i64"));
+        drone.queue_input(i64::try_from(y).expect("y fits in i64"));
+        drone.run();
+        drone.output.pop_front() == Some(1)
+    }
+}
+
+/// Follows the upper and lower edges of the tractor beam row by row.
+///
+/// Both edges of the beam only move right as `y` grows, so each row starts probing where the
+/// previous row ended and the total number of probes grows with the number of rows and the
+/// beam's final position, not with its area.
+struct BeamTracer<F> {
+    probe: F,
+    y: usize,
+    edges: Option<RangeInclusive<usize>>,
+}
+
+impl<F: FnMut(usize, usize) -> bool> BeamTracer<F> {
+    const fn new(probe: F) -> Self {
+        Self {
+            probe,
+            y: 0,
+            edges: None,
+        }
+    }
+}
+
+impl<F: FnMut(usize, usize) -> bool> Iterator for BeamTracer<F> {
+    /// The row index and the beam's span on that row (`None` if the row is empty)
+    type Item = (usize, Option<RangeInclusive<usize>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let y = self.y;
+        self.y += 1;
+        let (mut low, previous_high) = self
+            .edges
+            .as_ref()
+            .map_or((0, 0), |edges| (*edges.start(), *edges.end()));
+        let limit = MAX_SLOPE * (y + 1);
+        while low <= limit && !(self.probe)(low, y) {
+            low += 1;
+        }
+        if low > limit {
+            // Empty row, keep the last known edges to resume from
+            return Some((y, None));
+        }
+        let mut high = previous_high.max(low);
+        while (self.probe)(high + 1, y) {
+            high += 1;
+        }
+        self.edges = Some(low..=high);
+        Some((y, self.edges.clone()))
+    }
+}
+
+fn count_pulled<F: FnMut(usize, usize) -> bool>(probe: F, size: usize) -> usize {
+    BeamTracer::new(probe)
+        .take(size)
+        .filter_map(|(_, edges)| edges)
+        .map(|edges| edges.filter(|&x| x < size).count())
+        .sum()
+}
+
+/// Finds the top-left corner of the first `size` x `size` square that fits entirely in the beam.
+///
+/// Walks down the lower-left edge and checks if the row `size - 1` above still reaches far
+/// enough right to cover the top-right corner.
+fn fit_square<F: FnMut(usize, usize) -> bool>(probe: F, size: usize) -> (usize, usize) {
+    let mut high_edges: Vec<Option<usize>> = Vec::new();
+    for (y, edges) in BeamTracer::new(probe) {
+        high_edges.push(edges.as_ref().map(|e| *e.end()));
+        let Some(edges) = edges else { continue };
+        let Some(top) = (y + 1).checked_sub(size) else {
+            continue;
+        };
+        let x = *edges.start();
+        if high_edges[top].is_some_and(|high| high >= x + size - 1) {
+            return (x, top);
+        }
+    }
+    unreachable!("the beam is infinite")
+}
+
+#[aoc_solver(
+    file = "inputs/2019/day19.txt",
+    expected = 0,
+    ignore = "answer not recorded"
+)]
 fn part_1(input: &str) -> usize {
-    todo!("Implement part 1 of day 19 {input}");
+    let drone: IntcodeComputer = input.parse().expect("valid program");
+    count_pulled(drone_probe(&drone), 50)
+}
+
+#[aoc_solver(
+    file = "inputs/2019/day19.txt",
+    expected = 0,
+    ignore = "answer not recorded"
+)]
+fn part_2(input: &str) -> usize {
+    let drone: IntcodeComputer = input.parse().expect("valid program");
+    let (x, y) = fit_square(drone_probe(&drone), 100);
+    x * 10_000 + y
 }
 
-#[aoc_solver(file = "inputs/2019/day19.txt", expected = 0)]
-fn part_2(input: &str) -> isize {
-    todo!("Implement part 2 of day 19 {input}");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A narrow beam between the slopes 1.3 and 1.4 that leaves some rows near the emitter empty
+    const fn example_beam(x: usize, y: usize) -> bool {
+        10 * x >= 13 * y && 10 * x <= 14 * y
+    }
+
+    #[test]
+    fn traced_rows_match_full_scan() {
+        for (y, edges) in BeamTracer::new(example_beam).take(200) {
+            let scanned: Vec<usize> = (0..400).filter(|&x| example_beam(x, y)).collect();
+            match edges {
+                Some(edges) => assert_eq!(edges.collect::<Vec<_>>(), scanned, "row {y}"),
+                None => assert!(scanned.is_empty(), "row {y}"),
+            }
+        }
+    }
+
+    #[test]
+    fn count_in_area() {
+        let expected = (0..50)
+            .flat_map(|y| (0..50).map(move |x| (x, y)))
+            .filter(|&(x, y)| example_beam(x, y))
+            .count();
+        assert_eq!(count_pulled(example_beam, 50), expected);
+    }
+
+    #[test]
+    fn square_fits_with_few_probes() {
+        let size = 10;
+        let fits =
+            |x: usize, y: usize| (y..y + size).all(|y| (x..x + size).all(|x| example_beam(x, y)));
+        let expected = (0..)
+            .find_map(|y| (0..=2 * y).find(|&x| fits(x, y)).map(|x| (x, y)))
+            .expect("a fitting square");
+        let mut tracer_probes = 0;
+        let probe = |x, y| {
+            tracer_probes += 1;
+            example_beam(x, y)
+        };
+        assert_eq!(fit_square(probe, size), expected);
+        let rows = expected.1 + size;
+        assert!(
+            tracer_probes < 10 * rows,
+            "{tracer_probes} probes for {rows} rows"
+        );
+    }
 }
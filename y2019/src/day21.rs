@@ -1,11 +1,430 @@
+use crate::intcode::IntcodeComputer;
 use advent_of_code_macros::aoc_solver;
+use std::{fmt::Write, str::FromStr};
 
-#[aoc_solver(file = "inputs/2019/day21.txt", expected = 0)]
-fn part_1(input: &str) -> usize {
-    todo!("Implement part 1 of day 21 {input}");
+/// The springdroid refuses programs longer than this
+const MAX_INSTRUCTIONS: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Register {
+    /// One of the read-only hull sensors, `A` (1 tile ahead) to `I` (9 tiles ahead)
+    Sensor(u8),
+    Temporary,
+    Jump,
+}
+
+impl std::fmt::Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sensor(n) => write!(f, "{}", char::from(b'A' + n)),
+            Self::Temporary => write!(f, "T"),
+            Self::Jump => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    And,
+    Or,
+    Not,
+}
+
+/// A single springscript instruction, `Op X Y` stores the result in the writable register `Y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Instruction {
+    op: Op,
+    src: Register,
+    dst: Register,
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Not => "NOT",
+        };
+        write!(f, "{op} {} {}", self.src, self.dst)
+    }
+}
+
+/// A boolean expression over the hull sensors, deciding whether the droid should jump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Sensor(u8),
+    Not(Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+}
+
+impl Expr {
+    /// Evaluates the expression with the sensors given as bits, bit 0 being sensor `A`.
+    fn eval(&self, sensors: u16) -> bool {
+        match self {
+            Self::Sensor(n) => sensors & (1 << n) != 0,
+            Self::Not(e) => !e.eval(sensors),
+            Self::And(a, b) => a.eval(sensors) && b.eval(sensors),
+            Self::Or(a, b) => a.eval(sensors) || b.eval(sensors),
+        }
+    }
+
+    /// The sensor (and whether it is negated) if this is a sensor or a negated sensor.
+    fn literal(&self) -> Option<(u8, bool)> {
+        match self {
+            Self::Sensor(n) => Some((*n, false)),
+            Self::Not(e) => match **e {
+                Self::Sensor(n) => Some((n, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Parses expressions like `(!A | !B | !C) & D`, with `!` binding tighter than `&`,
+/// and `&` binding tighter than `|`.
+impl FromStr for Expr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut parser = ExprParser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected '{c}' at position {}", parser.pos)),
+        }
+    }
+}
+
+struct ExprParser {
+    tokens: Vec<char>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.tokens.get(self.pos) == Some(&c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat('|') {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat('&') {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('!') {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat('(') {
+            let expr = self.or()?;
+            return if self.eat(')') {
+                Ok(expr)
+            } else {
+                Err(format!("Expected ')' at position {}", self.pos))
+            };
+        }
+        match self.tokens.get(self.pos) {
+            Some(&c @ 'A'..='I') => {
+                self.pos += 1;
+                Ok(Expr::Sensor(c as u8 - b'A'))
+            }
+            Some(c) => Err(format!("Unexpected '{c}' at position {}", self.pos)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
 }
 
-#[aoc_solver(file = "inputs/2019/day21.txt", expected = 0)]
-fn part_2(input: &str) -> isize {
-    todo!("Implement part 2 of day 21 {input}");
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CompileError {
+    /// Both operands need a scratch register, but the two writable registers are already in use
+    NotEnoughRegisters,
+    TooManyInstructions(usize),
+}
+
+/// Compiles expressions into springscript that leaves the result in `J`, using `T` as the only
+/// other register.
+///
+/// Binary operations with a (possibly negated) sensor on one side are folded into the register
+/// holding the other side. When no scratch register is free, negated sensors are folded in with
+/// De Morgan's laws instead (`x & !s == !(!x | s)`). Both `T` and `J` start out false, which
+/// saves an instruction when loading a plain sensor into a fresh register.
+#[derive(Debug, Default)]
+struct Compiler {
+    program: Vec<Instruction>,
+    /// Registers that were not written to yet, so they are known to be false
+    fresh: Vec<Register>,
+}
+
+impl Compiler {
+    fn compile(expr: &Expr) -> Result<Vec<Instruction>, CompileError> {
+        let mut compiler = Self {
+            program: Vec::new(),
+            fresh: vec![Register::Temporary, Register::Jump],
+        };
+        compiler.emit(expr, Register::Jump, Some(Register::Temporary))?;
+        if compiler.program.len() > MAX_INSTRUCTIONS {
+            return Err(CompileError::TooManyInstructions(compiler.program.len()));
+        }
+        Ok(compiler.program)
+    }
+
+    fn push(&mut self, op: Op, src: Register, dst: Register) {
+        self.fresh.retain(|&r| r != dst);
+        self.program.push(Instruction { op, src, dst });
+    }
+
+    /// Emits code for `expr` into `target`, optionally clobbering `scratch`.
+    fn emit(
+        &mut self,
+        expr: &Expr,
+        target: Register,
+        scratch: Option<Register>,
+    ) -> Result<(), CompileError> {
+        if let Some((n, negated)) = expr.literal() {
+            let sensor = Register::Sensor(n);
+            match (negated, self.fresh.contains(&target)) {
+                (true, _) => self.push(Op::Not, sensor, target),
+                (false, true) => self.push(Op::Or, sensor, target),
+                (false, false) => {
+                    self.push(Op::Not, sensor, target);
+                    self.push(Op::Not, target, target);
+                }
+            }
+            return Ok(());
+        }
+        let (a, b, op) = match expr {
+            Expr::Not(e) => {
+                self.emit(e, target, scratch)?;
+                self.push(Op::Not, target, target);
+                return Ok(());
+            }
+            Expr::And(a, b) => (a, b, Op::And),
+            Expr::Or(a, b) => (a, b, Op::Or),
+            Expr::Sensor(_) => unreachable!("sensors are literals"),
+        };
+        // Try both operand orders, keeping the shorter program that compiles
+        let start = self.program.len();
+        let fresh = self.fresh.clone();
+        let mut best: Option<(Vec<Instruction>, Vec<Register>)> = None;
+        for (first, second) in [(a, b), (b, a)] {
+            self.program.truncate(start);
+            self.fresh.clone_from(&fresh);
+            if self.emit_binary(op, first, second, target, scratch).is_ok()
+                && best
+                    .as_ref()
+                    .is_none_or(|(program, _)| program.len() > self.program.len())
+            {
+                best = Some((self.program[start..].to_vec(), self.fresh.clone()));
+            }
+        }
+        self.program.truncate(start);
+        let (program, fresh) = best.ok_or(CompileError::NotEnoughRegisters)?;
+        self.program.extend(program);
+        self.fresh = fresh;
+        Ok(())
+    }
+
+    fn emit_binary(
+        &mut self,
+        op: Op,
+        first: &Expr,
+        second: &Expr,
+        target: Register,
+        scratch: Option<Register>,
+    ) -> Result<(), CompileError> {
+        match (second.literal(), scratch) {
+            (Some((n, false)), _) => {
+                self.emit(first, target, scratch)?;
+                self.push(op, Register::Sensor(n), target);
+            }
+            (Some((n, true)), Some(scratch)) => {
+                self.emit(first, target, Some(scratch))?;
+                self.push(Op::Not, Register::Sensor(n), scratch);
+                self.push(op, scratch, target);
+            }
+            (Some((n, true)), None) => {
+                let dual = if op == Op::And { Op::Or } else { Op::And };
+                self.emit(first, target, None)?;
+                self.push(Op::Not, target, target);
+                self.push(dual, Register::Sensor(n), target);
+                self.push(Op::Not, target, target);
+            }
+            (None, Some(scratch)) => {
+                self.emit(first, target, Some(scratch))?;
+                self.emit(second, scratch, None)?;
+                self.push(op, scratch, target);
+            }
+            (None, None) => return Err(CompileError::NotEnoughRegisters),
+        }
+        Ok(())
+    }
+}
+
+/// Runs a springscript program for a single sensor reading, the same way the droid would.
+fn simulate(program: &[Instruction], sensors: u16) -> bool {
+    let (mut t, mut j) = (false, false);
+    for instruction in program {
+        let src = match instruction.src {
+            Register::Sensor(n) => sensors & (1 << n) != 0,
+            Register::Temporary => t,
+            Register::Jump => j,
+        };
+        let dst = match instruction.dst {
+            Register::Temporary => &mut t,
+            Register::Jump => &mut j,
+            Register::Sensor(_) => panic!("sensors are read-only"),
+        };
+        *dst = match instruction.op {
+            Op::And => src && *dst,
+            Op::Or => src || *dst,
+            Op::Not => !src,
+        };
+    }
+    j
+}
+
+/// Compiles the jump condition, runs it on the droid and returns the reported hull damage.
+///
+/// # Panics
+///
+/// Panics with the droid's last moments if it falls into space.
+fn survey_hull(input: &str, condition: &str, command: &str) -> i64 {
+    let condition: Expr = condition.parse().expect("valid expression");
+    let program = Compiler::compile(&condition).expect("program fits the droid");
+    let script = program.iter().fold(String::new(), |mut s, instruction| {
+        writeln!(s, "{instruction}").expect("writing to a string");
+        s
+    });
+    let mut droid: IntcodeComputer = input.parse().expect("valid program");
+    droid.queue_ascii(&script);
+    droid.queue_ascii(command);
+    droid.queue_ascii("\n");
+    droid.run();
+    match droid.output.back() {
+        Some(&damage) if damage > 127 => damage,
+        _ => {
+            let render: String = droid
+                .output
+                .iter()
+                .filter_map(|&c| u8::try_from(c).ok().map(char::from))
+                .collect();
+            panic!("The droid fell into space:\n{render}")
+        }
+    }
+}
+
+/// Jump if there is a hole in the next 3 tiles, and ground to land on
+const WALK_CONDITION: &str = "(!A | !B | !C) & D";
+/// Same as walking, but only if the droid can either keep going or jump again right after landing
+const RUN_CONDITION: &str = "(!A | !B | !C) & D & (E | H)";
+
+#[aoc_solver(
+    file = "inputs/2019/day21.txt",
+    expected = 0,
+    ignore = "answer not recorded"
+)]
+fn part_1(input: &str) -> i64 {
+    survey_hull(input, WALK_CONDITION, "WALK")
+}
+
+#[aoc_solver(
+    file = "inputs/2019/day21.txt",
+    expected = 0,
+    ignore = "answer not recorded"
+)]
+fn part_2(input: &str) -> i64 {
+    survey_hull(input, RUN_CONDITION, "RUN")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_equivalent(expression: &str) -> usize {
+        let expr: Expr = expression.parse().expect("valid expression");
+        let program = Compiler::compile(&expr).expect("compiles");
+        for sensors in 0..1 << 9 {
+            assert_eq!(
+                simulate(&program, sensors),
+                expr.eval(sensors),
+                "{expression} with sensors {sensors:09b}"
+            );
+        }
+        program.len()
+    }
+
+    #[test]
+    fn solutions_are_equivalent() {
+        assert_eq!(assert_equivalent(WALK_CONDITION), 6);
+        assert_eq!(assert_equivalent(RUN_CONDITION), 10);
+    }
+
+    #[test]
+    fn other_expressions_are_equivalent() {
+        for expression in [
+            "A",
+            "!A",
+            "!!A",
+            "A & B",
+            "!A & !B",
+            "!(A | B) | C & !D",
+            "(A | B) & (C | D)",
+            "!(A & B) & !(C | D) & I",
+            "(A | !B) & (!C | D) | (E & !F)",
+            "!((A | B) & (C | !D))",
+        ] {
+            assert!(assert_equivalent(expression) <= MAX_INSTRUCTIONS);
+        }
+    }
+
+    #[test]
+    fn compile_errors() {
+        let expr: Expr = "(A | B) & (C | D) & ((E | F) & (G | H))"
+            .parse()
+            .expect("valid");
+        assert_eq!(
+            Compiler::compile(&expr),
+            Err(CompileError::NotEnoughRegisters)
+        );
+        let expr: Expr = "!A & !B & !C & !D & !E & !F & !G & !H & !I"
+            .parse()
+            .expect("valid");
+        assert_eq!(
+            Compiler::compile(&expr),
+            Err(CompileError::TooManyInstructions(17))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("A &".parse::<Expr>().is_err());
+        assert!("(A | B".parse::<Expr>().is_err());
+        assert!("A | J".parse::<Expr>().is_err());
+        assert!("A B".parse::<Expr>().is_err());
+    }
+
+    #[test]
+    fn instruction_display() {
+        let expr: Expr = "!A & D".parse().expect("valid");
+        let program = Compiler::compile(&expr).expect("compiles");
+        let script: Vec<String> = program.iter().map(ToString::to_string).collect();
+        assert_eq!(script, ["NOT A J", "AND D J"]);
+    }
 }
@@ -1,20 +1,16 @@
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, VecDeque},
-    str::FromStr,
-};
+use std::{cmp::Ordering, collections::VecDeque, rc::Rc, str::FromStr};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum ValueMode {
     Position,
     Immediate,
     Relative,
 }
 
-impl TryFrom<isize> for ValueMode {
-    type Error = isize;
+impl TryFrom<i64> for ValueMode {
+    type Error = i64;
 
-    fn try_from(value: isize) -> Result<Self, Self::Error> {
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(Self::Position),
             1 => Ok(Self::Immediate),
@@ -24,7 +20,7 @@ impl TryFrom<isize> for ValueMode {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Instruction {
     Add = 1,
     Multiply = 2,
@@ -39,7 +35,7 @@ enum Instruction {
 }
 
 impl Instruction {
-    const fn parameter_count(&self) -> usize {
+    const fn parameter_count(self) -> usize {
         match self {
             Self::Add | Self::Multiply | Self::LessThan | Self::Equals => 3,
             Self::Output | Self::AdjustRelativeBase => 1,
@@ -49,10 +45,10 @@ impl Instruction {
     }
 }
 
-impl TryFrom<isize> for Instruction {
-    type Error = isize;
+impl TryFrom<i64> for Instruction {
+    type Error = i64;
 
-    fn try_from(value: isize) -> Result<Self, Self::Error> {
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
         match value % 100 {
             1 => Ok(Self::Add),
             2 => Ok(Self::Multiply),
@@ -69,21 +65,94 @@ impl TryFrom<isize> for Instruction {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 struct Opcode {
     instruction: Instruction,
     modes: [ValueMode; 3],
 }
 
-impl From<isize> for Opcode {
-    fn from(value: isize) -> Self {
-        let instruction = Instruction::try_from(value).expect("Invalid instruction");
+impl TryFrom<i64> for Opcode {
+    type Error = i64;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        if value < 0 {
+            return Err(value);
+        }
+        let instruction = Instruction::try_from(value)?;
         let modes = [
-            ValueMode::try_from((value / 100) % 10).expect("Invalid mode for parameter 1"),
-            ValueMode::try_from((value / 1000) % 10).expect("Invalid mode for parameter 2"),
-            ValueMode::try_from((value / 10000) % 10).expect("Invalid mode for parameter 3"),
+            ValueMode::try_from((value / 100) % 10)?,
+            ValueMode::try_from((value / 1000) % 10)?,
+            ValueMode::try_from((value / 10000) % 10)?,
         ];
-        Self { instruction, modes }
+        Ok(Self { instruction, modes })
+    }
+}
+
+/// Number of words in a single memory page
+const PAGE_SIZE: usize = 256;
+
+/// Flat, growable Intcode memory.
+///
+/// Words are stored in fixed size pages that are shared between clones, so cloning a
+/// [`Memory`] (or a whole [`IntcodeComputer`]) is a cheap snapshot. A page is only copied
+/// the first time a snapshot writes to it. Reading beyond the end of memory returns 0 and
+/// writing beyond it grows the memory as needed.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    pages: Vec<Rc<[i64; PAGE_SIZE]>>,
+}
+
+impl From<Vec<i64>> for Memory {
+    fn from(words: Vec<i64>) -> Self {
+        let pages = words
+            .chunks(PAGE_SIZE)
+            .map(|chunk| {
+                let mut page = [0; PAGE_SIZE];
+                page[..chunk.len()].copy_from_slice(chunk);
+                Rc::new(page)
+            })
+            .collect();
+        Self { pages }
+    }
+}
+
+impl Memory {
+    /// Returns the word at `index`, or 0 if that address was never written.
+    #[must_use]
+    pub fn get(&self, index: usize) -> i64 {
+        self.pages
+            .get(index / PAGE_SIZE)
+            .map_or(0, |page| page[index % PAGE_SIZE])
+    }
+
+    /// Writes `value` to `index`, growing the memory and un-sharing the page if needed.
+    pub fn set(&mut self, index: usize, value: i64) {
+        let page_index = index / PAGE_SIZE;
+        if page_index >= self.pages.len() {
+            self.pages
+                .resize_with(page_index + 1, || Rc::new([0; PAGE_SIZE]));
+        }
+        Rc::make_mut(&mut self.pages[page_index])[index % PAGE_SIZE] = value;
+    }
+
+    /// The number of addressable words currently allocated (always a multiple of the page size).
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.pages.len() * PAGE_SIZE
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Copies all allocated words into a new `Vec`.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.pages
+            .iter()
+            .flat_map(|page| page.iter().copied())
+            .collect()
     }
 }
 
@@ -96,28 +165,36 @@ pub enum State {
 
 #[derive(Clone)]
 pub struct IntcodeComputer {
-    pub memory: HashMap<usize, isize>,
+    pub memory: Memory,
+    /// Opcodes decoded once from the original program, keyed by address. Each entry keeps the raw
+    /// word it was decoded from, so an address that was overwritten since is decoded again.
+    decoded: Rc<[Option<(i64, Opcode)>]>,
     pc: usize, // program counter
-    queued_input: VecDeque<isize>,
+    queued_input: VecDeque<i64>,
     pub state: State,
-    pub output: VecDeque<isize>,
-    relative_base: isize,
+    pub output: VecDeque<i64>,
+    relative_base: i64,
 }
 
 impl FromStr for IntcodeComputer {
     type Err = std::num::ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let memory: Result<Vec<isize>, _> = s.trim().split(',').map(str::parse).collect();
+        let memory: Result<Vec<i64>, _> = s.trim().split(',').map(str::parse).collect();
         Ok(Self::new(memory?))
     }
 }
 
 impl IntcodeComputer {
-    pub fn new(memory: Vec<isize>) -> Self {
-        let memory: HashMap<usize, isize> = memory.into_iter().enumerate().collect();
+    #[must_use]
+    pub fn new(memory: Vec<i64>) -> Self {
+        let decoded = memory
+            .iter()
+            .map(|&word| Opcode::try_from(word).ok().map(|opcode| (word, opcode)))
+            .collect();
         Self {
-            memory,
+            memory: memory.into(),
+            decoded,
             pc: 0,
             queued_input: VecDeque::new(),
             state: State::Running,
@@ -126,16 +203,25 @@ impl IntcodeComputer {
         }
     }
 
-    pub fn queue_input(&mut self, input: impl Into<isize>) {
+    pub fn queue_input(&mut self, input: impl Into<i64>) {
         self.queued_input.push_back(input.into());
     }
 
-    fn read_memory(&self, index: usize) -> isize {
-        self.memory.get(&index).copied().unwrap_or(0)
+    /// Queues every byte of `input` as a separate input value, for the ASCII capable programs.
+    pub fn queue_ascii(&mut self, input: &str) {
+        self.queued_input.extend(input.bytes().map(i64::from));
+    }
+
+    fn fetch(&self) -> Opcode {
+        let word = self.memory.get(self.pc);
+        match self.decoded.get(self.pc) {
+            Some(Some((original, opcode))) if *original == word => *opcode,
+            _ => Opcode::try_from(word).unwrap_or_else(|v| panic!("Invalid opcode {v}")),
+        }
     }
 
-    fn write_index(&self, mode: &ValueMode, index: usize) -> usize {
-        let value = self.read_memory(index);
+    fn write_index(&self, mode: ValueMode, index: usize) -> usize {
+        let value = self.memory.get(index);
         match mode {
             ValueMode::Position => Self::real_index(value),
             ValueMode::Relative => Self::real_index(self.relative_base + value),
@@ -143,35 +229,33 @@ impl IntcodeComputer {
         }
     }
 
-    fn handle_input(&mut self, mode: &ValueMode) {
+    fn handle_input(&mut self, mode: ValueMode) {
         let dest = self.write_index(mode, self.pc + 1);
         match self.queued_input.pop_front() {
             Some(value) => {
-                self.set(value, dest);
+                self.memory.set(dest, value);
                 self.pc += 2; // Move past the input instruction and its parameter only when consumed
             }
             None => self.state = State::AwaitingInput,
         }
     }
 
-    /// Small helper function to convert an isize index to usize, panicking if the index is negative.
-    fn real_index(n: isize) -> usize {
+    /// Small helper function to convert an i64 index to usize, panicking if the index is negative.
+    fn real_index(n: i64) -> usize {
         usize::try_from(n).expect("Index must be non-negative")
     }
 
-    fn get(&self, mode: &ValueMode, index: usize) -> isize {
-        let value = self.read_memory(index);
+    fn get(&self, mode: ValueMode, index: usize) -> i64 {
+        let value = self.memory.get(index);
         match mode {
-            ValueMode::Position => self.read_memory(Self::real_index(value)),
+            ValueMode::Position => self.memory.get(Self::real_index(value)),
             ValueMode::Immediate => value,
-            ValueMode::Relative => self.read_memory(Self::real_index(self.relative_base + value)),
+            ValueMode::Relative => self
+                .memory
+                .get(Self::real_index(self.relative_base + value)),
         }
     }
 
-    fn set(&mut self, value: isize, index: usize) {
-        self.memory.insert(index, value);
-    }
-
     pub fn run(&mut self) -> State {
         if matches!(self.state, State::AwaitingInput) {
             if self.queued_input.is_empty() {
@@ -180,36 +264,36 @@ impl IntcodeComputer {
             self.state = State::Running;
         }
         while self.state == State::Running {
-            let opcode = Opcode::from(*self.memory.get(&self.pc).unwrap_or(&0));
-            match opcode.instruction {
+            let Opcode { instruction, modes } = self.fetch();
+            match instruction {
                 Instruction::Add => {
-                    let a = self.get(&opcode.modes[0], self.pc + 1);
-                    let b = self.get(&opcode.modes[1], self.pc + 2);
-                    let dest = self.write_index(&opcode.modes[2], self.pc + 3);
-                    self.set(a + b, dest);
+                    let a = self.get(modes[0], self.pc + 1);
+                    let b = self.get(modes[1], self.pc + 2);
+                    let dest = self.write_index(modes[2], self.pc + 3);
+                    self.memory.set(dest, a + b);
                 }
                 Instruction::Multiply => {
-                    let a = self.get(&opcode.modes[0], self.pc + 1);
-                    let b = self.get(&opcode.modes[1], self.pc + 2);
-                    let dest = self.write_index(&opcode.modes[2], self.pc + 3);
-                    self.set(a * b, dest);
+                    let a = self.get(modes[0], self.pc + 1);
+                    let b = self.get(modes[1], self.pc + 2);
+                    let dest = self.write_index(modes[2], self.pc + 3);
+                    self.memory.set(dest, a * b);
                 }
                 Instruction::Input => {
-                    self.handle_input(&opcode.modes[0]);
+                    self.handle_input(modes[0]);
                     if self.state == State::AwaitingInput {
                         break;
                     }
                     continue; // Skip the normal pc increment at the end of the loop
                 }
                 Instruction::Output => {
-                    let src = self.get(&opcode.modes[0], self.pc + 1);
+                    let src = self.get(modes[0], self.pc + 1);
                     self.output.push_back(src);
                 }
                 Instruction::JumpIfTrue | Instruction::JumpIfFalse => {
-                    let a = self.get(&opcode.modes[0], self.pc + 1);
-                    match (a != 0, &opcode.instruction) {
+                    let a = self.get(modes[0], self.pc + 1);
+                    match (a != 0, instruction) {
                         (true, Instruction::JumpIfTrue) | (false, Instruction::JumpIfFalse) => {
-                            let b = self.get(&opcode.modes[1], self.pc + 2);
+                            let b = self.get(modes[1], self.pc + 2);
                             self.pc = Self::real_index(b);
                             continue; // Skip the normal pc increment at the end of the loop
                         }
@@ -217,20 +301,20 @@ impl IntcodeComputer {
                     }
                 }
                 Instruction::LessThan | Instruction::Equals => {
-                    let a = self.get(&opcode.modes[0], self.pc + 1);
-                    let b = self.get(&opcode.modes[1], self.pc + 2);
-                    let dest = self.write_index(&opcode.modes[2], self.pc + 3);
-                    self.set(
-                        match (a.cmp(&b), &opcode.instruction) {
+                    let a = self.get(modes[0], self.pc + 1);
+                    let b = self.get(modes[1], self.pc + 2);
+                    let dest = self.write_index(modes[2], self.pc + 3);
+                    self.memory.set(
+                        dest,
+                        match (a.cmp(&b), instruction) {
                             (Ordering::Less, Instruction::LessThan)
                             | (Ordering::Equal, Instruction::Equals) => 1,
                             _ => 0,
                         },
-                        dest,
                     );
                 }
                 Instruction::AdjustRelativeBase => {
-                    let a = self.get(&opcode.modes[0], self.pc + 1);
+                    let a = self.get(modes[0], self.pc + 1);
                     self.relative_base += a;
                 }
                 Instruction::Halt => {
@@ -238,7 +322,7 @@ impl IntcodeComputer {
                     break;
                 }
             }
-            self.pc += 1 + opcode.instruction.parameter_count();
+            self.pc += 1 + instruction.parameter_count();
         }
         self.state
     }
@@ -246,9 +330,61 @@ impl IntcodeComputer {
     pub fn run_with_input<I, T>(&mut self, input: I) -> State
     where
         I: Iterator<Item = T>,
-        T: Into<isize>,
+        T: Into<i64>,
     {
         self.queued_input.extend(input.map(Into::into));
         self.run()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter::once;
+
+    #[test]
+    fn memory_grows_and_defaults_to_zero() {
+        let mut memory = Memory::from(vec![1, 2, 3]);
+        assert_eq!(memory.len(), PAGE_SIZE);
+        assert_eq!(memory.get(2), 3);
+        assert_eq!(memory.get(10 * PAGE_SIZE), 0);
+        memory.set(3 * PAGE_SIZE + 1, 42);
+        assert_eq!(memory.len(), 4 * PAGE_SIZE);
+        assert_eq!(memory.get(3 * PAGE_SIZE + 1), 42);
+        assert_eq!(memory.get(2 * PAGE_SIZE), 0);
+    }
+
+    #[test]
+    fn snapshots_are_copy_on_write() {
+        let original = Memory::from((0..1000).collect::<Vec<_>>());
+        let mut snapshot = original.clone();
+        assert!(Rc::ptr_eq(&original.pages[0], &snapshot.pages[0]));
+        snapshot.set(0, -1);
+        assert!(!Rc::ptr_eq(&original.pages[0], &snapshot.pages[0]));
+        assert!(Rc::ptr_eq(&original.pages[1], &snapshot.pages[1]));
+        assert_eq!(original.get(0), 0);
+        assert_eq!(snapshot.get(0), -1);
+    }
+
+    #[test]
+    fn self_modifying_code_is_decoded_again() {
+        // Overwrites the instruction at address 4 (add) with 1102 (multiply, immediate modes)
+        let program = "1101,0,1102,4,1101,5,6,11,4,11,99,0";
+        let mut computer: IntcodeComputer = program.parse().expect("valid program");
+        assert_eq!(computer.run(), State::Halted);
+        assert_eq!(computer.output, [30]);
+    }
+
+    #[test]
+    fn clones_run_independently() {
+        // Reads an input, outputs it doubled, then loops back to the start
+        let program = "3,11,1002,11,2,11,4,11,1105,1,0";
+        let mut computer: IntcodeComputer = program.parse().expect("valid program");
+        assert_eq!(computer.run(), State::AwaitingInput);
+        let mut other = computer.clone();
+        computer.run_with_input(once(5));
+        other.run_with_input(once(7));
+        assert_eq!(computer.output, [10]);
+        assert_eq!(other.output, [14]);
+    }
+}
@@ -1,5 +1,7 @@
-mod intcode;
+pub mod intcode;
 advent_of_code_macros::all_the_days!(17);
+pub mod day19;
+pub mod day21;
 
 #[macro_export]
 macro_rules! default_input_path {
@@ -1,3 +1,4 @@
+use advent_of_code_common::vm::{Exit, Flow, InstructionSet, Vm};
 use anyhow::Result;
 use std::str::FromStr;
 
@@ -34,23 +35,30 @@ fn parse_input(s: &str) -> Vec<Instruction> {
         .collect()
 }
 
-#[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-fn execute(instructions: &[Instruction]) -> (i32, bool) {
-    let mut acc = 0;
-    let mut pc = 0;
-    let mut visited = vec![false; instructions.len()];
-    while pc < instructions.len() && !visited[pc] {
-        visited[pc] = true;
-        match instructions[pc] {
-            Instruction::Nop(_) => pc += 1,
-            Instruction::Acc(arg) => {
-                acc += arg;
-                pc += 1;
+impl InstructionSet for Instruction {
+    type State = i32;
+
+    fn execute(&self, acc: &mut i32) -> Flow {
+        match *self {
+            Self::Nop(_) => Flow::Next,
+            Self::Acc(arg) => {
+                *acc += arg;
+                Flow::Next
             }
-            Instruction::Jmp(arg) => pc = (pc as i32 + arg) as usize,
+            Self::Jmp(arg) => Flow::Jump(isize::try_from(arg).expect("offset fits in isize")),
         }
     }
-    (acc, pc == instructions.len())
+}
+
+/// Runs until the program terminates or repeats an instruction, returning the accumulator and
+/// whether it terminated by running right past the last instruction
+fn execute(instructions: &[Instruction]) -> (i32, bool) {
+    let mut vm = Vm::new(instructions, 0).with_loop_detection();
+    let exit = vm.run();
+    (
+        vm.state,
+        exit == Exit::OutOfBounds && vm.pc == instructions.len(),
+    )
 }
 
 fn fix_bug(instructions: &[Instruction]) -> i32 {
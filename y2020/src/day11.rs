@@ -8,14 +8,14 @@ enum SeatState {
 }
 
 impl TryFrom<char> for SeatState {
-    type Error = &'static str;
+    type Error = advent_of_code_common::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Floor),
             'L' => Ok(Self::Empty),
             '#' => Ok(Self::Occupied),
-            _ => Err("Invalid character in input"),
+            _ => Err(Self::Error::invalid_char(value, "one of '.', 'L', '#'")),
         }
     }
 }
@@ -1,126 +1,74 @@
-use std::{ops::Neg, str::FromStr};
-
-#[derive(Debug, Default, Clone, Copy)]
-enum Direction {
-    North,
-    #[default]
-    East,
-    South,
-    West,
-}
+use advent_of_code_common::coords::{Coords, Direction, Move, Point, Pose, Turn};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy)]
-enum Action {
-    Right90(u16),
-    Forward(u16),
-    Direction(Direction, u16),
+struct Action {
+    kind: Move,
+    value: isize,
 }
 
 impl FromStr for Action {
-    type Err = ();
+    type Err = advent_of_code_common::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut chars = s.chars();
-        let action = chars.next().ok_or(())?;
-        let number: u16 = chars.as_str().parse().map_err(|_| ())?;
-        let action = match action {
-            'N' => Self::Direction(Direction::North, number),
-            'S' => Self::Direction(Direction::South, number),
-            'E' => Self::Direction(Direction::East, number),
-            'W' => Self::Direction(Direction::West, number),
-            'L' => Self::Right90(4 - (number / 90)),
-            'R' => Self::Right90(number / 90),
-            'F' => Self::Forward(number),
-            _ => return Err(()),
-        };
-        Ok(action)
+        let letter = chars
+            .next()
+            .ok_or_else(|| Self::Err::unexpected_end("an action"))?;
+        let value = chars
+            .as_str()
+            .parse()
+            .map_err(|_| Self::Err::invalid_token(chars.as_str(), "a number").at(1, 2))?;
+        Ok(Self {
+            kind: letter.try_into()?,
+            value,
+        })
     }
 }
 
-#[derive(Debug, Default)]
-struct Location {
-    x: i32,
-    y: i32,
-    facing: Direction,
+fn turn(action: Action) -> Result<Option<Turn>, advent_of_code_common::Error> {
+    let turn = Turn::from_degrees(action.value).map_err(|e| e.at(1, 2))?;
+    // L90 is the opposite of R90
+    match action.kind {
+        Move::Relative(Turn::Left) => Ok(turn.map(Turn::reverse)),
+        _ => Ok(turn),
+    }
 }
 
-impl Location {
-    fn apply_action(&mut self, action: Action) {
-        match action {
-            Action::Right90(0) => {}
-            Action::Right90(n) => {
-                self.facing = match self.facing {
-                    Direction::North => Direction::East,
-                    Direction::East => Direction::South,
-                    Direction::South => Direction::West,
-                    Direction::West => Direction::North,
-                };
-                self.apply_action(Action::Right90(n - 1));
-            }
-            Action::Forward(n) => {
-                let n = i32::from(n);
-                match self.facing {
-                    Direction::North => self.y -= n,
-                    Direction::East => self.x += n,
-                    Direction::South => self.y += n,
-                    Direction::West => self.x -= n,
+fn move_ship(input: &str) -> Result<isize, advent_of_code_common::Error> {
+    let mut ship = Pose::new(Point::default(), Direction::East);
+    for line in input.lines() {
+        let action: Action = line.trim().parse()?;
+        match action.kind {
+            Move::Absolute(direction) => ship.position += direction * action.value,
+            Move::Relative(_) => {
+                if let Some(turn) = turn(action)? {
+                    ship.apply(turn);
                 }
             }
-            Action::Direction(new_facing, n) => {
-                let saved_facing = self.facing;
-                self.facing = new_facing;
-                self.apply_action(Action::Forward(n));
-                self.facing = saved_facing;
-            }
+            Move::Forward => ship.advance(action.value),
         }
     }
+    Ok(ship.position.manhattan_distance(&Point::default()))
+}
 
-    const fn manhattan_from_center(&self) -> i32 {
-        self.x.abs() + self.y.abs()
-    }
-
-    fn rotate_90_right(&mut self) {
-        let tmp = self.x;
-        self.x = self.y.neg();
-        self.y = tmp;
-    }
-
-    fn apply_waypoint_action(&mut self, waypoint: &mut Self, action: Action) {
-        match action {
-            Action::Right90(n) => {
-                for _ in 0..n {
-                    waypoint.rotate_90_right();
+fn move_ship_by_waypoint(input: &str) -> Result<isize, advent_of_code_common::Error> {
+    let mut ship = Point::default();
+    // The waypoint is relative to the ship
+    let mut waypoint = Point { x: 10, y: -1 };
+    for line in input.lines() {
+        let action: Action = line.trim().parse()?;
+        match action.kind {
+            Move::Absolute(direction) => waypoint += direction * action.value,
+            Move::Relative(_) => {
+                if let Some(turn) = turn(action)? {
+                    waypoint = waypoint.rotate(turn);
                 }
             }
-            Action::Forward(n) => {
-                let n = i32::from(n);
-                self.x += waypoint.x * n;
-                self.y += waypoint.y * n;
-            }
-            Action::Direction(direction, n) => {
-                waypoint.apply_action(Action::Direction(direction, n));
-            }
+            Move::Forward => ship += waypoint * action.value,
         }
     }
-}
-
-fn move_ship(input: &str) -> Result<i32, ()> {
-    let mut ship: Location = Location::default();
-    for line in input.lines() {
-        let action = line.trim().parse()?;
-        ship.apply_action(action);
-    }
-    Ok(ship.manhattan_from_center())
-}
-
-fn move_ship_by_waypoint(input: &str) -> Result<i32, ()> {
-    let mut ship: Location = Location::default();
-    let mut waypoint = Location { x: 10, y: -1, facing: Direction::default() };
-    for line in input.lines() {
-        let action = line.trim().parse()?;
-        ship.apply_waypoint_action(&mut waypoint, action);
-    }
-    Ok(ship.manhattan_from_center())
+    Ok(ship.manhattan_distance(&Point::default()))
 }
 
 #[advent_of_code_macros::aoc_tests]
@@ -146,6 +94,14 @@ mod tests {
         assert_eq!(move_ship_by_waypoint(EXAMPLE).unwrap(), 286);
     }
 
+    #[test]
+    fn odd_angles() {
+        assert_eq!(move_ship("L270\nF3").unwrap(), 3);
+        let error = move_ship("F10\nL45").unwrap_err();
+        assert_eq!(error.position(), (1, 2));
+        assert!(move_ship_by_waypoint("R100").is_err());
+    }
+
     #[test]
     fn part_2() {
         assert_eq!(move_ship_by_waypoint(&read_input()).unwrap(), 41212);
@@ -1,3 +1,5 @@
+use advent_of_code_common::math::crt;
+
 #[derive(Debug, Default)]
 struct NextBus {
     id: usize,
@@ -21,8 +23,24 @@ fn first_bus_value(input: &str) -> usize {
     first_bus.id * (first_bus.depart_time - min_time)
 }
 
+/// The earliest time at which every bus departs as many minutes after it as its offset in the list,
+/// or `None` if the bus IDs share factors that make the offsets impossible to line up
+fn earliest_timestamp(input: &str) -> Option<i64> {
+    let congruences: Vec<(i64, i64)> = input
+        .lines()
+        .last()
+        .unwrap_or_default()
+        .split(',')
+        .zip(0..)
+        .filter_map(|(id, offset)| id.parse().ok().map(|id: i64| (-offset, id)))
+        .collect();
+    crt(&congruences).map(|(timestamp, _)| timestamp)
+}
+
 #[advent_of_code_macros::aoc_tests]
 mod tests {
+    use advent_of_code_common::math::lcm_all;
+
     const EXAMPLE: &str = "939
 7,13,x,x,59,x,31,19";
 
@@ -35,4 +53,40 @@ mod tests {
     fn part_1() {
         assert_eq!(first_bus_value(&read_input()), 259);
     }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(earliest_timestamp(EXAMPLE), Some(1_068_781));
+        let examples = [
+            ("17,x,13,19", 3417),
+            ("67,7,59,61", 754_018),
+            ("67,x,7,59,61", 779_210),
+            ("67,7,x,59,61", 1_261_476),
+            ("1789,37,47,1889", 1_202_161_486),
+        ];
+        for (buses, expected) in examples {
+            assert_eq!(earliest_timestamp(buses), Some(expected), "{buses}");
+        }
+        assert_eq!(earliest_timestamp("4,6"), None);
+    }
+
+    /// Waits for the buses one at a time, stepping by the period of the ones already lined up
+    fn sieve(input: &str) -> i64 {
+        let (mut timestamp, mut period) = (0, 1);
+        let buses = input.lines().last().unwrap_or_default().split(',').zip(0..);
+        let buses = buses.filter_map(|(id, offset)| Some((id.parse().ok()?, offset)));
+        for (id, offset) in buses {
+            while (timestamp + offset) % id != 0 {
+                timestamp += period;
+            }
+            period = lcm_all([period, id]);
+        }
+        timestamp
+    }
+
+    #[test]
+    fn part_2() {
+        let input = read_input();
+        assert_eq!(earliest_timestamp(&input), Some(sieve(&input)));
+    }
 }
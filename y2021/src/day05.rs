@@ -1,5 +1,7 @@
+use advent_of_code_common::coords::Point;
+use advent_of_code_common::geometry::Segment;
+use advent_of_code_common::render::Canvas;
 use itertools::Itertools;
-use std::cmp::Ordering;
 use std::collections::HashMap;
 
 const PATH: &str = "../inputs/2021/day05.txt";
@@ -37,53 +39,11 @@ const EXAMPLE_OUTPUT_2: &str = "
 1.......1.
 222111....";
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug)]
-struct Line {
-    start: Point,
-    end: Point,
-}
-
-impl Line {
-    fn get_points(&self) -> Vec<Point> {
-        let mut result = vec![];
-        let mut p = self.start;
-        let x_inc = match self.start.x.cmp(&self.end.x) {
-            Ordering::Greater => -1,
-            Ordering::Less => 1,
-            Ordering::Equal => 0,
-        };
-        let y_inc = match self.start.y.cmp(&self.end.y) {
-            Ordering::Greater => -1,
-            Ordering::Less => 1,
-            Ordering::Equal => 0,
-        };
-        while (p.x, p.y) != (self.end.x, self.end.y) {
-            result.push(p);
-            p = Point {
-                x: (p.x + x_inc),
-                y: (p.y + y_inc),
-            }
-        }
-        result.push(self.end);
-        result
-    }
-
-    const fn is_diagonal(&self) -> bool {
-        self.start.x != self.end.x && self.start.y != self.end.y
-    }
-}
-
-fn point_count(data: &[Line], diagonals: bool) -> HashMap<Point, i32> {
+fn point_count(data: &[Segment], diagonals: bool) -> HashMap<Point, i32> {
     let points: Vec<Point> = data
         .iter()
-        .filter(|line| diagonals || !line.is_diagonal())
-        .flat_map(Line::get_points)
+        .filter(|line| line.is_axis_aligned() || diagonals && line.is_diagonal())
+        .flat_map(Segment::points)
         .collect();
     let mut counter = HashMap::new();
     for p in points {
@@ -92,32 +52,29 @@ fn point_count(data: &[Line], diagonals: bool) -> HashMap<Point, i32> {
     counter
 }
 
-fn setup_data(data: &[String]) -> Vec<Line> {
+fn setup_data(data: &[String]) -> Vec<Segment> {
     data.iter()
         .map(|line| {
-            let (a, b, c, d): (i32, i32, i32, i32) = line
-                .replace(" -> ", ",")
-                .split(',')
+            let (start, end) = line
+                .split(" -> ")
                 .map(|s| s.parse().unwrap())
                 .collect_tuple()
                 .unwrap();
-            Line {
-                start: Point { x: a, y: b },
-                end: Point { x: c, y: d },
-            }
+            Segment::new(start, end)
         })
         .collect()
 }
 
-#[allow(clippy::cast_sign_loss)] // This should be validated to be positive elsewhere
-fn setup_output(points: &HashMap<Point, i32>, w: i32, h: i32) -> String {
-    let mut board: Vec<Vec<String>> = (0..h)
-        .map(|_| (0..w).map(|_| ".".to_string()).collect())
-        .collect();
+fn setup_output(points: &HashMap<Point, i32>, w: isize, h: isize) -> String {
+    let mut canvas = Canvas::new(0..w, 0..h, '.');
     for (p, num) in points {
-        board[p.y as usize][p.x as usize] = num.to_string();
+        let symbol = u32::try_from(*num)
+            .ok()
+            .and_then(|n| char::from_digit(n, 10))
+            .unwrap_or('+');
+        canvas.set(p, symbol);
     }
-    board.iter().map(|line| line.join("")).join("\n")
+    canvas.plain()
 }
 
 #[cfg(test)]
@@ -132,6 +89,7 @@ mod tests {
         let counter = point_count(&data, false);
         let result = counter.values().filter(|v| **v >= 2).count();
         assert_eq!(result, 5);
+        assert_eq!(setup_output(&counter, 10, 10), EXAMPLE_OUTPUT_1.trim());
     }
 
     #[test]
@@ -141,6 +99,7 @@ mod tests {
         let counter = point_count(&data, true);
         let result = counter.values().filter(|v| **v >= 2).count();
         assert_eq!(result, 12);
+        assert_eq!(setup_output(&counter, 10, 10), EXAMPLE_OUTPUT_2.trim());
     }
 
     #[test]
@@ -190,11 +190,11 @@ impl Packet {
 }
 
 impl std::str::FromStr for Packet {
-    type Err = &'static str;
+    type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut bits: BitsIterator = s.parse()?;
-        let (packet, _consumed) = Self::from_bits(&mut bits)?;
+        let (packet, _consumed) = Self::from_bits(&mut bits).map_err(anyhow::Error::msg)?;
         if bits.remaining() > 0 {
             let padding = bits.consume_until_end();
             debug_assert_eq!(padding, 0, "Padding bits");
@@ -213,6 +213,16 @@ mod tests {
         assert_eq!(packet.value(), 2021);
     }
 
+    #[test]
+    fn parse_errors() {
+        let error = "D2FG28".parse::<Packet>().unwrap_err();
+        let error = error.downcast::<advent_of_code_common::Error>().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 4: expected a hexadecimal digit, found 'G'"
+        );
+    }
+
     #[test]
     fn op_bit_length() {
         let packet: Packet = "38006F45291200".parse().unwrap();
@@ -1 +1,151 @@
+use advent_of_code_common::coords::Point3;
+use advent_of_code_common::file::iter_blocks;
+use advent_of_code_common::symmetry::Rotation3;
+use advent_of_code_macros::aoc_tests;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 
+/// Two scanners see the same region when at least this many of their beacons line up
+const OVERLAP: usize = 12;
+
+/// The beacons of each scanner, relative to it, without the `--- scanner N ---` headers
+fn parse_scanners(input: &str) -> Result<Vec<Vec<Point3>>, advent_of_code_common::Error> {
+    iter_blocks(input)
+        .map(|block| block.iter().skip(1).map(|line| line.parse()).collect())
+        .collect()
+}
+
+/// Turn and move the beacons of `scanner` to line up with the `reference` ones, which are already
+/// in the frame of the first scanner. Returns the moved beacons and the position of the scanner.
+fn align(reference: &[Point3], scanner: &[Point3]) -> Option<(Vec<Point3>, Point3)> {
+    Rotation3::all().iter().find_map(|rotation| {
+        let rotated = rotation.apply_all(scanner);
+        let mut offsets: HashMap<Point3, usize> = HashMap::new();
+        for (a, b) in reference.iter().cartesian_product(&rotated) {
+            *offsets.entry(*a - *b).or_default() += 1;
+        }
+        let (offset, _) = offsets.into_iter().find(|&(_, n)| n >= OVERLAP)?;
+        Some((rotated.iter().map(|b| *b + offset).collect(), offset))
+    })
+}
+
+/// All the beacons and the positions of all the scanners, in the frame of the first scanner
+fn locate(scanners: &[Vec<Point3>]) -> (HashSet<Point3>, Vec<Point3>) {
+    let mut beacons = HashSet::new();
+    let mut positions = vec![Point3::new(0, 0, 0)];
+    let mut unaligned: Vec<&[Point3]> = scanners.iter().skip(1).map(Vec::as_slice).collect();
+    let mut aligned: Vec<Vec<Point3>> = scanners.first().cloned().into_iter().collect();
+    while let Some(reference) = aligned.pop() {
+        let mut still_unaligned = Vec::new();
+        for scanner in unaligned {
+            if let Some((moved, position)) = align(&reference, scanner) {
+                aligned.push(moved);
+                positions.push(position);
+            } else {
+                still_unaligned.push(scanner);
+            }
+        }
+        unaligned = still_unaligned;
+        beacons.extend(reference);
+    }
+    (beacons, positions)
+}
+
+/// How many beacons there are in total
+fn count_beacons(input: &str) -> usize {
+    let scanners = parse_scanners(input).expect("valid input");
+    locate(&scanners).0.len()
+}
+
+/// The largest Manhattan distance between any two scanners
+fn largest_distance(input: &str) -> isize {
+    let scanners = parse_scanners(input).expect("valid input");
+    locate(&scanners)
+        .1
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| a.manhattan_distance(b))
+        .max()
+        .unwrap_or(0)
+}
+
+#[aoc_tests]
+mod tests {
+    /// Three scanners at known positions, each turned another way, that see 40 pseudo-random
+    /// beacons: the first sees beacons 0 to 19, the second 8 to 29 and the third 18 to 39, so
+    /// each pair of neighbors shares 12 of them.
+    fn example() -> String {
+        let mut seed: isize = 19;
+        let beacons: Vec<Point3> = (0..40)
+            .map(|_| {
+                let mut next = || {
+                    seed = (seed * 1_103_515_245 + 12_345).rem_euclid(1 << 31);
+                    seed % 2001 - 1000
+                };
+                Point3::new(next(), next(), next())
+            })
+            .collect();
+        let rotations = Rotation3::all();
+        let scanners = [
+            (Point3::new(0, 0, 0), 0..20, rotations[0]),
+            (Point3::new(1105, -1205, -1229), 8..30, rotations[7]),
+            (Point3::new(-92, -2380, -20), 18..40, rotations[13]),
+        ];
+        scanners
+            .into_iter()
+            .enumerate()
+            .map(|(i, (position, seen, rotation))| {
+                let lines = beacons[seen]
+                    .iter()
+                    .map(|b| rotation.apply(&(*b - position)).coords.iter().join(","))
+                    .join("\n");
+                format!("--- scanner {i} ---\n{lines}")
+            })
+            .join("\n\n")
+    }
+
+    #[test]
+    fn example_1() {
+        assert_eq!(count_beacons(&example()), 40);
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(largest_distance(&example()), 3581);
+    }
+
+    // The answers aren't recorded, so these check what any correct solution has to satisfy
+
+    #[test]
+    fn part_1() {
+        let input = read_input();
+        let scanners = parse_scanners(&input).unwrap();
+        let (beacons, positions) = locate(&scanners);
+        assert_eq!(positions.len(), scanners.len(), "every scanner lines up");
+        // Each scanner after the first shares at least OVERLAP beacons with another one
+        let seen: usize = scanners.iter().map(Vec::len).sum();
+        let count = count_beacons(&input);
+        assert_eq!(count, beacons.len());
+        assert!(count <= seen - OVERLAP * (scanners.len() - 1), "{count}");
+    }
+
+    #[test]
+    fn part_2() {
+        let input = read_input();
+        let (_, positions) = locate(&parse_scanners(&input).unwrap());
+        // Scanners that share beacons can't be more than twice their range apart on any axis
+        for (i, a) in positions.iter().enumerate().skip(1) {
+            let neighbor = positions
+                .iter()
+                .take(i)
+                .map(|b| a.chebyshev_distance(b))
+                .min();
+            assert!(neighbor.is_some_and(|d| d <= 2000), "{a:?}");
+        }
+        let distance = largest_distance(&input);
+        assert!(distance > 0);
+        for position in &positions {
+            assert!(position.manhattan_distance(&positions[0]) <= distance);
+        }
+    }
+}
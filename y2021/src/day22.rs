@@ -1 +1,93 @@
+use advent_of_code_common::boxes::{BoxN, BoxSet};
+use advent_of_code_macros::aoc_tests;
 
+type Cuboid = BoxN<3>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+fn parse_range(s: &str) -> Option<[i64; 2]> {
+    let (_, range) = s.split_once('=')?;
+    let (low, high) = range.split_once("..")?;
+    Some([low.parse().ok()?, high.parse().ok()?])
+}
+
+impl std::str::FromStr for Step {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "on x=10..12,y=10..12,z=10..12"
+        let (action, ranges) = s.trim().split_once(' ').ok_or("Missing ranges")?;
+        let on = match action {
+            "on" => true,
+            "off" => false,
+            _ => return Err("Unknown action"),
+        };
+        let ranges: Vec<[i64; 2]> = ranges
+            .split(',')
+            .map(parse_range)
+            .collect::<Option<_>>()
+            .ok_or("Invalid range")?;
+        let [[x1, x2], [y1, y2], [z1, z2]] = ranges[..] else {
+            return Err("Expected 3 ranges");
+        };
+        let cuboid = BoxN::new([x1, y1, z1], [x2, y2, z2]).ok_or("Empty cuboid")?;
+        Ok(Self { on, cuboid })
+    }
+}
+
+fn reboot(input: &str) -> BoxSet<3> {
+    let mut reactor = BoxSet::new();
+    for step in input.lines().filter_map(|line| line.parse::<Step>().ok()) {
+        if step.on {
+            reactor.insert(step.cuboid);
+        } else {
+            reactor.remove(&step.cuboid);
+        }
+    }
+    reactor
+}
+
+fn part_1(input: &str) -> u64 {
+    let mut reactor = reboot(input);
+    reactor.retain_within(&BoxN::new([-50; 3], [50; 3]).expect("valid region"));
+    reactor.volume()
+}
+
+fn part_2(input: &str) -> u64 {
+    reboot(input).volume()
+}
+
+#[aoc_tests]
+mod tests {
+    const EXAMPLE: &str = "on x=10..12,y=10..12,z=10..12
+                           on x=11..13,y=11..13,z=11..13
+                           off x=9..11,y=9..11,z=9..11
+                           on x=10..10,y=10..10,z=10..10";
+
+    #[test]
+    fn parse_step() {
+        let step: Step = "off x=-54112..-39298,y=-85059..-49293,z=-27449..7877"
+            .parse()
+            .unwrap();
+        assert!(!step.on);
+        assert_eq!(step.cuboid.min, [-54112, -85059, -27449]);
+        assert_eq!(step.cuboid.max, [-39298, -49293, 7877]);
+        assert!("on x=1..2,y=3..4".parse::<Step>().is_err());
+    }
+
+    #[test]
+    fn example_1() {
+        assert_eq!(part_1(EXAMPLE), 39);
+        assert_eq!(part_1("on x=-100..100,y=0..0,z=0..0"), 101);
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(part_2(EXAMPLE), 39);
+        assert_eq!(part_2("on x=-100..100,y=0..0,z=0..0"), 201);
+    }
+}
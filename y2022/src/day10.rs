@@ -1,5 +1,7 @@
 use advent_of_code_common::file::split_lines_trim;
+use advent_of_code_common::vm::{Flow, InstructionSet, Vm};
 use anyhow::{Context, Result};
+use std::str::FromStr;
 
 const PATH: &str = "../inputs/2022/day10.txt";
 const EXAMPLE: &str = "../inputs/2022/day10_example.txt";
@@ -22,73 +24,105 @@ fn input(example: bool) -> Result<Vec<String>> {
     Ok(split_lines_trim(&s))
 }
 
-#[allow(clippy::cast_sign_loss)] // TODO: Find a better alternative
-fn calc_sprite_position(data: &[String]) -> [usize; 240] {
-    let mut cycles: [i16; 240] = [0; 240]; // 240 refers to part_2 (40 * 6)
-    let mut count = 0;
-    cycles[0] = 1;
-    for (i, row) in data.iter().enumerate() {
-        if row.starts_with("add") {
-            count += 1;
-            cycles[i + count] = row
-                .split_ascii_whitespace()
-                .last()
-                .unwrap_or_default()
-                .parse()
-                .unwrap_or_default();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(' ') {
+            None if s == "noop" => Ok(Self::Noop),
+            Some(("addx", value)) => Ok(Self::Addx(value.parse()?)),
+            _ => Err(anyhow::anyhow!("Invalid instruction {s}")),
+        }
+    }
+}
+
+impl InstructionSet for Instruction {
+    type State = i64;
+
+    fn execute(&self, x: &mut i64) -> Flow {
+        if let Self::Addx(value) = self {
+            *x += value;
         }
+        Flow::Next
     }
-    let mut result: [usize; 240] = [0; 240];
-    for i in 0..240 {
-        result[i] = cycles[..i].iter().sum::<i16>() as usize;
+
+    fn cycles(&self) -> usize {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
+        }
     }
-    result
 }
 
-fn part_1(data: &[String]) -> usize {
-    let sprite_positions = calc_sprite_position(data);
-    sprite_positions[19..220]
+/// The value of the X register *during* each of the first 240 cycles (40 * 6 for part 2)
+fn calc_sprite_position(data: &[String]) -> Result<[i64; 240]> {
+    let program: Vec<Instruction> = data.iter().map(|row| row.parse()).collect::<Result<_>>()?;
+    let mut result = [0; 240];
+    Vm::new(&program, 1).run_with_hook(|cycle, &x| {
+        if let Some(position) = result.get_mut(cycle - 1) {
+            *position = x;
+        }
+    });
+    Ok(result)
+}
+
+fn part_1(data: &[String]) -> Result<i64> {
+    let sprite_positions = calc_sprite_position(data)?;
+    Ok(sprite_positions[19..220]
         .iter()
         .step_by(40)
-        .enumerate()
-        .map(|(index, n)| (index * 40 + 20) * n)
-        .sum()
+        .zip((20..).step_by(40))
+        .map(|(x, cycle)| cycle * x)
+        .sum())
 }
 
-fn part_2(data: &[String]) -> String {
+fn part_2(data: &[String]) -> Result<String> {
     let mut result = String::new();
-    let sprite_positions = calc_sprite_position(data);
-    for (i, &p) in sprite_positions.iter().enumerate() {
-        let idx = i % 40;
-        if idx + 1 == p || idx == p || (idx > 0 && idx - 1 == p) {
+    let sprite_positions = calc_sprite_position(data)?;
+    for (i, (&p, idx)) in sprite_positions.iter().zip((0..40).cycle()).enumerate() {
+        if (idx - p).abs() <= 1 {
             result.push('#');
         } else {
             result.push('.');
         }
-        if i % 40 == 39 && i < 239 {
+        if idx == 39 && i < 239 {
             result.push('\n');
         }
     }
-    result
+    Ok(result)
 }
 
 #[test]
 fn example_1() {
-    assert_eq!(part_1(&input(true).unwrap()), 13140);
+    assert_eq!(part_1(&input(true).unwrap()).unwrap(), 13140);
 }
 
 #[test]
 fn task_1() {
-    assert_eq!(part_1(&input(false).unwrap()), 14060);
+    assert_eq!(part_1(&input(false).unwrap()).unwrap(), 14060);
 }
 
 #[test]
 fn example_2() {
-    assert_eq!(part_2(&input(true).unwrap()), EXPECTED_EXAMPLE);
+    assert_eq!(part_2(&input(true).unwrap()).unwrap(), EXPECTED_EXAMPLE);
 }
 
 #[test]
 fn task_2() {
-    let output = part_2(&input(false).unwrap());
+    let output = part_2(&input(false).unwrap()).unwrap();
     assert_eq!(output, EXPECTED_PART_2, "\n{output}\n");
 }
+
+#[test]
+fn invalid_instruction() {
+    let data = ["noop", "addx 3", "addx x", "noop"].map(String::from);
+    assert!(part_1(&data).is_err());
+    let data = ["noop", "jmp 3"].map(String::from);
+    assert!(part_2(&data).is_err());
+}
@@ -1,92 +1,41 @@
+use advent_of_code_common::coords::Point;
 use advent_of_code_common::file::split_lines_trim;
+use advent_of_code_common::geometry::Segment;
+use advent_of_code_common::render::{Animation, Canvas, Color, Style};
 use anyhow::{Context, Result};
-use itertools::{iproduct, Itertools};
-use std::{collections::HashSet, hash::Hash};
+use std::collections::HashSet;
 
 const PATH: &str = "../inputs/2022/day14.txt";
+/// Where the sand comes from
+const SOURCE: Point = Point { x: 500, y: 0 };
 const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: usize,
-    y: usize,
-}
-
-impl TryFrom<&str> for Point {
-    type Error = &'static str;
-
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let (x, y) = s
-            .trim()
-            .split(',')
-            .filter_map(|v| v.trim().parse().ok())
-            .collect_tuple()
-            .ok_or("Invalid point")?;
-        Ok(Self { x, y })
-    }
-}
-
-#[derive(Debug)]
-struct Line {
-    start: Point,
-    end: Point,
-}
-
-impl Line {
-    fn points(&self) -> HashSet<Point> {
-        let (min_x, max_x, min_y, max_y) = if self.start.x == self.end.x {
-            (
-                self.start.x,
-                self.start.x,
-                self.start.y.min(self.end.y),
-                self.start.y.max(self.end.y),
-            )
-        } else {
-            (
-                self.start.x.min(self.end.x),
-                self.start.x.max(self.end.x),
-                self.start.y,
-                self.start.y,
-            )
-        };
-        iproduct!(min_x..=max_x, min_y..=max_y)
-            .map(|(x, y)| Point { x, y })
-            .collect()
-    }
-}
-
 #[derive(Debug)]
 struct Rock {
-    lines: Vec<Line>,
+    lines: Vec<Segment>,
 }
 
 impl Rock {
     fn from_str(s: &str) -> Self {
-        let corners: Vec<Point> = s
-            .split("->")
-            .filter_map(|p| Point::try_from(p).ok())
-            .collect();
+        let corners: Vec<Point> = s.split("->").filter_map(|p| p.parse().ok()).collect();
         Self {
             lines: corners
                 .windows(2)
-                .map(|corner| Line {
-                    start: corner[0],
-                    end: corner[1],
-                })
+                .map(|corner| Segment::new(corner[0], corner[1]))
                 .collect(),
         }
     }
 
     fn points(&self) -> HashSet<Point> {
-        self.lines.iter().flat_map(Line::points).collect()
+        self.lines.iter().flat_map(Segment::points).collect()
     }
 }
 
 struct Cave {
     rocks: HashSet<Point>,
     sand: HashSet<Point>,
-    max_y: usize,
+    max_y: isize,
 }
 
 impl Cave {
@@ -96,6 +45,16 @@ impl Cave {
         sand
     }
 
+    /// Rocks in gray and sand in yellow, below the source
+    fn canvas(&self) -> Canvas {
+        let rock = Style::new('#').color(Color::GRAY);
+        let sand = Style::new('o').color(Color::YELLOW);
+        let everything = self.rocks.iter().chain(&self.sand).chain([&SOURCE]);
+        Canvas::from_points(everything, rock, '.')
+            .overlay(&self.sand, sand)
+            .overlay([&SOURCE], '+')
+    }
+
     fn drop_sand(&mut self, origin: &Point) -> Point {
         let mut sand = *origin;
         let points = self.points();
@@ -146,22 +105,24 @@ fn input(example: bool) -> Result<Cave> {
 }
 
 fn part_1(cave: &mut Cave) -> usize {
-    let origin = Point { x: 500, y: 0 };
-    let mut last_drop = origin;
+    let mut animation = Animation::from_env();
+    let mut last_drop = SOURCE;
     while last_drop.y < cave.max_y {
-        last_drop = cave.drop_sand(&origin);
+        last_drop = cave.drop_sand(&SOURCE);
+        animation.frame(|| cave.canvas());
     }
     cave.sand.len() - 1
 }
 
 fn part_2(cave: &mut Cave) -> usize {
     cave.max_y += 1;
-    let origin = Point { x: 500, y: 0 };
+    let mut animation = Animation::from_env();
     let mut last_drop = Point { x: 0, y: 0 };
     let mut counter = 0;
-    while last_drop != origin {
+    while last_drop != SOURCE {
         counter += 1;
-        last_drop = cave.drop_sand(&origin);
+        last_drop = cave.drop_sand(&SOURCE);
+        animation.frame(|| cave.canvas());
     }
     counter
 }
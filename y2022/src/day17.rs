@@ -1,3 +1,5 @@
+use advent_of_code_common::coords::Point;
+use advent_of_code_common::render::{Animation, Canvas, Color, Style};
 use advent_of_code_macros::aoc_tests;
 use itertools::Itertools;
 use std::{collections::VecDeque, fmt::Display};
@@ -99,6 +101,33 @@ impl Display for Game {
 }
 
 impl Game {
+    /// How many rows from the top of the tower are animated
+    const VIEW_HEIGHT: usize = 30;
+
+    /// The top of the tower, with the falling rock in red
+    fn canvas(&self) -> Canvas {
+        let to_isize = |n: usize| isize::try_from(n).expect("small board");
+        let rows = self.board.len().min(Self::VIEW_HEIGHT);
+        let mut canvas = Canvas::new(0..to_isize(self.width), 0..to_isize(rows), '.');
+        for (y, row) in self.board.iter().take(rows).enumerate() {
+            for (x, pixel) in row.iter().enumerate() {
+                let style = match pixel {
+                    Pixel::Empty => continue,
+                    Pixel::Moving => Style::new('@').color(Color::RED).bold(),
+                    Pixel::Full => Style::new('#').color(Color::GRAY),
+                };
+                canvas.set(
+                    &Point {
+                        x: to_isize(x),
+                        y: to_isize(y),
+                    },
+                    style,
+                );
+            }
+        }
+        canvas
+    }
+
     const fn new(width: usize) -> Self {
         Self {
             board: VecDeque::new(),
@@ -207,6 +236,7 @@ impl Game {
 
     fn game_loop(&mut self, directions: &str, shapes: usize) {
         let mut chars = directions.chars().cycle();
+        let mut animation = Animation::from_env();
         for _ in 0..shapes {
             self.add_shape();
             loop {
@@ -216,6 +246,7 @@ impl Game {
                     _ => false,
                 };
                 let moved = self.move_step(Direction::Down);
+                animation.frame(|| self.canvas());
                 if !moved {
                     break;
                 }
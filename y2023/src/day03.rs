@@ -1,88 +1,89 @@
-use itertools::iproduct;
-use std::collections::HashMap;
+use advent_of_code_common::file::iter_lines_trim;
+use advent_of_code_common::grid::{Grid, Point};
+use itertools::Itertools;
 
+/// A number of the schematic, from its leftmost digit
 #[derive(Debug)]
-enum EnginePart {
-    Number(u32),
-    Symbol(char),
+struct PartNumber {
+    start: Point,
+    len: isize,
+    value: u32,
 }
 
-type EngineMap = HashMap<(usize, usize), EnginePart>;
-
-fn create_engine_map(s: &str) -> EngineMap {
-    let mut map = HashMap::new();
-    let mut current_number = String::new();
-    for (y, line) in s.lines().enumerate() {
-        for (x, c) in line.trim().chars().enumerate() {
-            if "0123456789".contains(c) {
-                current_number.push(c);
-            } else {
-                if !current_number.is_empty() {
-                    let new_part = EnginePart::Number(current_number.parse().expect("number"));
-                    map.insert((x - current_number.len(), y), new_part);
-                    current_number.clear();
-                }
-                if c != '.' {
-                    map.insert((x, y), EnginePart::Symbol(c));
-                }
-            }
-        }
-        if !current_number.is_empty() {
-            let new_part = EnginePart::Number(current_number.parse().expect("number"));
-            map.insert((line.len() - current_number.len(), y), new_part);
-            current_number.clear();
-        }
+impl PartNumber {
+    /// Whether `p` touches one of the digits, diagonals included
+    fn is_adjacent(&self, p: Point) -> bool {
+        (self.start.x - 1..=self.start.x + self.len).contains(&p.x)
+            && (self.start.y - 1..=self.start.y + 1).contains(&p.y)
     }
-    map
 }
 
-fn numbers_with_neighbors(map: &EngineMap) -> Vec<u32> {
+#[derive(Debug)]
+struct Engine {
+    grid: Grid,
+    numbers: Vec<PartNumber>,
+}
+
+const fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+fn create_engine_map(s: &str) -> Engine {
+    let grid: Grid = iter_lines_trim(s)
+        .join("\n")
+        .parse()
+        .expect("valid schematic");
     let mut numbers = Vec::new();
-    for ((x, y), part) in map {
-        if let EnginePart::Number(number) = part {
-            let x_range = (x.saturating_sub(1))..=(x + number.to_string().len());
-            let y_range = (y.saturating_sub(1))..=(*y + 1);
-            for (x_s, y_s) in iproduct!(x_range, y_range) {
-                if let Some(EnginePart::Symbol { .. }) = map.get(&(x_s, y_s)) {
-                    numbers.push(*number);
-                    break;
-                }
+    for (y, row) in (0..).zip(grid.rows()) {
+        let mut x = 0;
+        for (is_digit, digits) in &row.into_iter().chunk_by(|c| c.is_ascii_digit()) {
+            let digits: String = digits.collect();
+            let len = isize::try_from(digits.len()).expect("short rows");
+            if is_digit {
+                numbers.push(PartNumber {
+                    start: Point { x, y },
+                    len,
+                    value: digits.parse().expect("number"),
+                });
             }
+            x += len;
         }
     }
-    numbers
+    Engine { grid, numbers }
 }
 
-fn find_number_neighbors(map: &EngineMap, x: usize, y: usize) -> Vec<u32> {
-    map.iter()
-        .filter(|((x_n, y_n), part)| {
-            if let EnginePart::Number(n) = part {
-                let x_range = (x_n.saturating_sub(1))..=(x_n + n.to_string().len());
-                let y_range = (y_n.saturating_sub(1))..=(y_n + 1);
-                x_range.contains(&x) && y_range.contains(&y)
-            } else {
-                false
-            }
-        })
-        .map(|((_, _), part)| match part {
-            EnginePart::Number(n) => *n,
-            EnginePart::Symbol(_) => unreachable!("Symbols are filtered out"),
+fn numbers_with_neighbors(engine: &Engine) -> Vec<u32> {
+    engine
+        .numbers
+        .iter()
+        .filter(|number| {
+            let (x, y) = (number.start.x, number.start.y);
+            engine
+                .grid
+                .subgrid(x - 1..x + number.len + 1, y - 1..y + 2)
+                .values()
+                .any(|cell| is_symbol(cell.data))
         })
+        .map(|number| number.value)
         .collect()
 }
 
-fn find_gears(map: &EngineMap) -> Vec<u32> {
-    let gears: Vec<u32> = map
-        .iter()
-        .filter(|(_, part)| match part {
-            EnginePart::Symbol(c) => *c == '*',
-            EnginePart::Number(_) => false,
+fn find_gears(engine: &Engine) -> Vec<u32> {
+    engine
+        .grid
+        .values()
+        .filter(|cell| cell.data == '*')
+        .map(|cell| {
+            engine
+                .numbers
+                .iter()
+                .filter(|number| number.is_adjacent(cell.point))
+                .map(|number| number.value)
+                .collect::<Vec<_>>()
         })
-        .map(|((x, y), _)| find_number_neighbors(map, *x, *y))
         .filter(|numbers| numbers.len() == 2)
         .map(|numbers| numbers[0] * numbers[1])
-        .collect();
-    gears
+        .collect()
 }
 
 #[cfg(test)]
@@ -101,8 +102,9 @@ mod tests {
 
     #[test]
     fn test_example_map() {
-        let map = create_engine_map(EXAMPLE1);
-        assert_eq!(map.len(), 16);
+        let engine = create_engine_map(EXAMPLE1);
+        let symbols = engine.grid.values().filter(|c| is_symbol(c.data)).count();
+        assert_eq!(engine.numbers.len() + symbols, 16);
     }
 
     #[test]
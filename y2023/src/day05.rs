@@ -1,4 +1,4 @@
-use advent_of_code_common::range::MultiRange;
+use advent_of_code_common::range::{IntervalMap, MultiRange};
 use anyhow::{Context, Result};
 
 #[derive(Debug, Clone)]
@@ -80,6 +80,13 @@ impl MultiMapping {
             .find_map(|mapping| mapping.apply_reverse(value))
             .unwrap_or(value)
     }
+
+    fn interval_map(&self) -> IntervalMap<i64> {
+        self.mappings
+            .iter()
+            .map(|mapping| (mapping.src_start..=mapping.src_end, mapping.dst_start))
+            .collect()
+    }
 }
 
 #[derive(Debug)]
@@ -97,6 +104,7 @@ pub struct SeedLocationMapping {
 impl SeedLocationMapping {
     /// Applies the mapping to the given seed value,
     /// all the way to the location value
+    #[must_use]
     pub fn apply(&self, value: i64) -> i64 {
         let mut value = self.to_soil.apply(value);
         value = self.to_fertilizer.apply(value);
@@ -109,6 +117,7 @@ impl SeedLocationMapping {
 
     /// Applies the mapping to the given location value,
     /// all the way to the seed value
+    #[must_use]
     pub fn apply_reverse(&self, value: i64) -> i64 {
         let mut value = self.to_location.apply_reverse(value);
         value = self.to_humidity.apply_reverse(value);
@@ -141,6 +150,7 @@ impl SeedLocationMapping {
     /// This only applies when the seed values are considered to be ranges.
     /// If the seed values are considered to be individual values,
     /// use `mapping.seeds.contains(value)`
+    #[must_use]
     pub fn contains(&self, value: &i64) -> bool {
         let mut index = 0;
         while index < self.seeds.len() {
@@ -155,96 +165,45 @@ impl SeedLocationMapping {
         false
     }
 
-    fn reduce_with_multi_range(&self) -> MultiRange<i64> {
-        let mut index = 0;
-        let mut ranges = vec![];
-        while index < self.seeds.len() {
-            let range_start = self.seeds[index];
-            let range_end = self.seeds[index] + self.seeds[index + 1] - 1;
-            let range = range_start..=range_end;
-            ranges.push(range);
-            index += 2;
-        }
-        let mut multi_range = MultiRange::from_iter(ranges);
-        dbg!(&multi_range);
-        for r in &self.to_soil.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_fertilizer.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_water.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_light.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_temperature.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_humidity.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(&multi_range);
-        for r in &self.to_location.mappings {
-            dbg!(r);
-            let mut cloned = multi_range.clone();
-            cloned -= &(r.src_start..=r.src_end);
-            if cloned != multi_range {
-                cloned += &(r.dst_start..=r.dst_end);
-            }
-            multi_range = cloned;
-        }
-        dbg!(multi_range)
+    /// The seed values as ranges
+    #[must_use]
+    pub fn seed_ranges(&self) -> MultiRange<i64> {
+        self.seeds
+            .chunks(2)
+            .map(|pair| pair[0]..=pair[0] + pair[1] - 1)
+            .collect()
+    }
+
+    /// The whole seed to location mapping as a single map
+    #[must_use]
+    pub fn interval_map(&self) -> IntervalMap<i64> {
+        [
+            &self.to_fertilizer,
+            &self.to_water,
+            &self.to_light,
+            &self.to_temperature,
+            &self.to_humidity,
+            &self.to_location,
+        ]
+        .into_iter()
+        .fold(self.to_soil.interval_map(), |map, next| {
+            map.then(&next.interval_map())
+        })
     }
 
+    /// The lowest location of any seed, when the seed values are considered to be ranges
+    #[must_use]
     pub fn minimal_location(&self) -> Option<i64> {
-        let multi_range = self.reduce_with_multi_range();
-        multi_range.into_iter().flatten().next()
+        let locations = self.interval_map().apply(&self.seed_ranges());
+        locations.ranges().first().map(|r| *r.start())
     }
 }
 
+/// Parses the seeds and all seven mappings
+///
+/// # Errors
+///
+/// Returns an error if the seeds line is missing
 pub fn input_parse(s: &str) -> Result<SeedLocationMapping> {
     fn extract_mappings<'a, I>(lines: &mut I) -> MultiMapping
     where
@@ -388,14 +347,14 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = include_str!("day05.txt");
+        let input = include_str!("../../inputs/2023/day05.txt");
         let seed_mapping = input_parse(input).unwrap();
         let results = seed_mapping
             .seeds
             .iter()
             .map(|seed| seed_mapping.apply(*seed))
             .min();
-        assert_eq!(results, Some(265018614));
+        assert_eq!(results, Some(265_018_614));
     }
 
     #[test]
@@ -413,10 +372,34 @@ mod tests {
     fn test_example_part2_reverse() {
         let example = include_str!("day05_example.txt");
         let seed_mapping = input_parse(example).unwrap();
-        let result = (0..).find(|loc| seed_mapping.contains(&seed_mapping.apply_reverse(*loc)));
+        let result =
+            (0..i64::MAX).find(|loc| seed_mapping.contains(&seed_mapping.apply_reverse(*loc)));
         assert_eq!(result, Some(46));
     }
 
+    #[test]
+    fn test_interval_map() {
+        let example = include_str!("day05_example.txt");
+        let seed_mapping = input_parse(example).unwrap();
+        let map = seed_mapping.interval_map();
+        let inverse = map.inverse();
+        for seed in 0..110 {
+            assert_eq!(map.get(seed), seed_mapping.apply(seed), "{seed}");
+            assert_eq!(
+                inverse.get(seed),
+                seed_mapping.apply_reverse(seed),
+                "{seed}"
+            );
+        }
+        let locations = map.apply(&seed_mapping.seed_ranges());
+        let expected: MultiRange<i64> = seed_mapping
+            .seed_iter()
+            .map(|seed| seed_mapping.apply(seed))
+            .map(|location| location..=location)
+            .collect();
+        assert_eq!(locations, expected);
+    }
+
     #[test]
     fn test_example_part2_reduce() {
         let example = include_str!("day05_example.txt");
@@ -424,4 +407,19 @@ mod tests {
         let result = seed_mapping.minimal_location();
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    #[ignore = "Answer not recorded, checked against the slow iteration over every seed"]
+    fn test_part2() {
+        let input = include_str!("../../inputs/2023/day05.txt");
+        let seed_mapping = input_parse(input).unwrap();
+        let result = seed_mapping.minimal_location();
+        let location = result.unwrap();
+        assert!(seed_mapping.contains(&seed_mapping.apply_reverse(location)));
+        let expected = seed_mapping
+            .seed_iter()
+            .map(|seed| seed_mapping.apply(seed))
+            .min();
+        assert_eq!(result, expected);
+    }
 }
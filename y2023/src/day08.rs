@@ -1,5 +1,6 @@
+use advent_of_code_common::math::lcm_all;
 use anyhow::{Context, Result};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 pub const EXAMPLE1: &str = "RL
 
@@ -28,26 +29,6 @@ pub const EXAMPLE3: &str = "LR
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
-fn prime_factors(n: &u128) -> Vec<u128> {
-    let mut n = *n;
-    let mut div = 2;
-    let mut result = Vec::new();
-    let max_div = n.isqrt();
-    while n > 1 {
-        if div > max_div {
-            result.push(n);
-            break;
-        } else if n.is_multiple_of(div) {
-            result.push(div);
-            n /= div;
-            div = 2;
-        } else {
-            div += 1;
-        }
-    }
-    result
-}
-
 fn parse_line(s: &str) -> Result<(&str, (&str, &str))> {
     let mut parts = s.split(" = ");
     let key = parts.next().context("no key")?;
@@ -112,11 +93,7 @@ fn solve2(commands: &str, map: &Map) -> u128 {
         keys = next_keys;
         count += 1;
     }
-    let prime_set: HashSet<u128> = z_map
-        .into_iter()
-        .flat_map(|v| prime_factors(&(v[1] - v[0])))
-        .collect();
-    prime_set.iter().product()
+    lcm_all(z_map.iter().map(|v| v[1] - v[0]))
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use tracing::debug;
+use advent_of_code_common::math::polynomial::DifferenceTable;
 
 fn parse_input(s: &str) -> Vec<Vec<isize>> {
     s.lines()
@@ -10,23 +10,18 @@ fn parse_input(s: &str) -> Vec<Vec<isize>> {
         .collect()
 }
 
-fn polynomial_degree(data: &[isize], level: u8) -> u8 {
-    let mut final_line = true;
-    debug!("{data:?}");
-    let mut next_line = vec![];
-    for w in data.windows(2) {
-        let diff = w[1] - w[0];
-        if diff != next_line.first().copied().unwrap_or(diff) {
-            final_line = false;
-        }
-        next_line.push(diff);
-    }
-    if final_line {
-        debug!("{next_line:?}");
-        level + 1
-    } else {
-        polynomial_degree(&next_line, level + 1)
-    }
+fn sum_next(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|history| DifferenceTable::new(history).next())
+        .sum()
+}
+
+fn sum_previous(histories: &[Vec<isize>]) -> isize {
+    histories
+        .iter()
+        .map(|history| DifferenceTable::new(history).previous())
+        .sum()
 }
 
 #[advent_of_code_macros::aoc_tests]
@@ -37,11 +32,28 @@ mod tests {
 
     #[test]
     fn degrees() {
-        // let input = read_input();
         let data = parse_input(EXAMPLE);
         for (row, i) in data.iter().zip([1, 2, 3]) {
-            let d = polynomial_degree(row, 0);
-            assert_eq!(d, i, "Row {row:?}");
+            let d = DifferenceTable::new(row).degree();
+            assert_eq!(d, Some(i), "Row {row:?}");
         }
     }
+
+    #[test]
+    fn example() {
+        let data = parse_input(EXAMPLE);
+        assert_eq!(sum_next(&data), 114);
+        assert_eq!(sum_previous(&data), 2);
+    }
+
+    #[test]
+    fn part2() {
+        let data = parse_input(include_str!("../../inputs/2023/day09.txt"));
+        // Extrapolating backwards is extrapolating the reversed history forwards
+        let reversed: Vec<Vec<isize>> = data
+            .iter()
+            .map(|history| history.iter().rev().copied().collect())
+            .collect();
+        assert_eq!(sum_previous(&data), sum_next(&reversed));
+    }
 }
@@ -1,5 +1,6 @@
+use advent_of_code_common::coords::Point;
+use advent_of_code_common::geometry::Polygon;
 use anyhow::{anyhow, Context, Result};
-use geo::{area::Area, Coord, CoordsIter, Polygon};
 use std::collections::HashMap;
 
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
@@ -16,17 +17,17 @@ enum PipeShape {
 }
 
 impl PipeShape {
-    fn connections(self) -> Vec<Coord<i32>> {
+    fn connections(self) -> Vec<Point> {
         use PipeShape::{
             BottomLeft, BottomRight, Horizontal, NoPipe, TopLeft, TopRight, Unknown, Vertical,
         };
         match self {
-            Vertical => vec![Coord { x: 0, y: 1 }, Coord { x: 0, y: -1 }],
-            Horizontal => vec![Coord { x: 1, y: 0 }, Coord { x: -1, y: 0 }],
-            BottomRight => vec![Coord { x: 0, y: -1 }, Coord { x: -1, y: 0 }],
-            BottomLeft => vec![Coord { x: 0, y: -1 }, Coord { x: 1, y: 0 }],
-            TopRight => vec![Coord { x: 0, y: 1 }, Coord { x: -1, y: 0 }],
-            TopLeft => vec![Coord { x: 0, y: 1 }, Coord { x: 1, y: 0 }],
+            Vertical => vec![Point { x: 0, y: 1 }, Point { x: 0, y: -1 }],
+            Horizontal => vec![Point { x: 1, y: 0 }, Point { x: -1, y: 0 }],
+            BottomRight => vec![Point { x: 0, y: -1 }, Point { x: -1, y: 0 }],
+            BottomLeft => vec![Point { x: 0, y: -1 }, Point { x: 1, y: 0 }],
+            TopRight => vec![Point { x: 0, y: 1 }, Point { x: -1, y: 0 }],
+            TopLeft => vec![Point { x: 0, y: 1 }, Point { x: 1, y: 0 }],
             Unknown | NoPipe => vec![],
         }
     }
@@ -82,12 +83,12 @@ impl PipeShape {
 
 #[derive(Debug, Clone, Copy)]
 struct Pipe {
-    coord: Coord<i32>,
+    coord: Point,
     shape: PipeShape,
 }
 
 impl Pipe {
-    fn connections(&self) -> Vec<Coord<i32>> {
+    fn connections(&self) -> Vec<Point> {
         self.shape
             .connections()
             .iter()
@@ -96,7 +97,7 @@ impl Pipe {
     }
 }
 
-fn fix_start_node(pipes: &mut HashMap<Coord<i32>, Pipe>) -> Result<Coord<i32>> {
+fn fix_start_node(pipes: &mut HashMap<Point, Pipe>) -> Result<Point> {
     let sp = pipes
         .values()
         .find(|pipe| pipe.shape == PipeShape::Unknown)
@@ -114,10 +115,10 @@ fn fix_start_node(pipes: &mut HashMap<Coord<i32>, Pipe>) -> Result<Coord<i32>> {
         ));
     }
 
-    let up = connected_pipes.contains(&(sp.coord + Coord { x: 0, y: -1 }));
-    let down = connected_pipes.contains(&(sp.coord + Coord { x: 0, y: 1 }));
-    let left = connected_pipes.contains(&(sp.coord + Coord { x: -1, y: 0 }));
-    let right = connected_pipes.contains(&(sp.coord + Coord { x: 1, y: 0 }));
+    let up = connected_pipes.contains(&(sp.coord + Point { x: 0, y: -1 }));
+    let down = connected_pipes.contains(&(sp.coord + Point { x: 0, y: 1 }));
+    let left = connected_pipes.contains(&(sp.coord + Point { x: -1, y: 0 }));
+    let right = connected_pipes.contains(&(sp.coord + Point { x: 1, y: 0 }));
     let new_shape = match (up, down, left, right) {
         (true, true, false, false) => PipeShape::Vertical,
         (false, false, true, true) => PipeShape::Horizontal,
@@ -134,10 +135,10 @@ fn fix_start_node(pipes: &mut HashMap<Coord<i32>, Pipe>) -> Result<Coord<i32>> {
     Ok(sp.coord)
 }
 
-type Pipes = HashMap<Coord<i32>, Pipe>;
+type Pipes = HashMap<Point, Pipe>;
 
-fn parse_input(s: &str) -> Result<Polygon<i32>> {
-    let mut pipes: HashMap<Coord<i32>, Pipe> = s
+fn parse_input(s: &str) -> Result<Polygon> {
+    let mut pipes: HashMap<Point, Pipe> = s
         .lines()
         .enumerate()
         .flat_map(|(y, line)| {
@@ -147,9 +148,9 @@ fn parse_input(s: &str) -> Result<Polygon<i32>> {
                 .map(move |(x, c)| (x, y, c))
                 .filter_map(|(x, y, c)| {
                     let shape = PipeShape::try_from(c).ok()?;
-                    let coord = Coord {
-                        x: i32::try_from(x).ok()?,
-                        y: i32::try_from(y).ok()?,
+                    let coord = Point {
+                        x: isize::try_from(x).ok()?,
+                        y: isize::try_from(y).ok()?,
                     };
                     Some((coord, Pipe { coord, shape }))
                 })
@@ -159,7 +160,7 @@ fn parse_input(s: &str) -> Result<Polygon<i32>> {
     pipes_polygon(&pipes, start)
 }
 
-fn pipes_polygon(pipes: &Pipes, start: Coord<i32>) -> Result<Polygon<i32>> {
+fn pipes_polygon(pipes: &Pipes, start: Point) -> Result<Polygon> {
     let mut points = vec![start];
     let mut next_coords = pipes[&start].connections()[0];
     while next_coords != start {
@@ -174,34 +175,17 @@ fn pipes_polygon(pipes: &Pipes, start: Coord<i32>) -> Result<Polygon<i32>> {
             next_coords = next_connections[0];
         }
     }
-    points.push(start);
-    Ok(Polygon::new(points.into(), vec![]))
+    Ok(Polygon::new(points))
 }
 
 fn furthest_node(input: &str) -> Result<usize> {
-    Ok(parse_input(input)?.exterior().points().len() / 2)
+    Ok(parse_input(input)?.vertices.len() / 2)
 }
 
-#[allow(clippy::float_cmp)] // We're not doing any floating point math here, other that the assert_eq! below
-#[allow(clippy::cast_sign_loss)] // We know the result is always positive
-#[allow(clippy::cast_precision_loss)] // Tested it, and it's fine
-#[allow(clippy::cast_possible_truncation)] // We're checking that the result is an integer
+/// The number of tiles enclosed by the loop, which are the lattice points strictly inside it
 fn inner_area(input: &str) -> Result<usize> {
-    let coords: Vec<_> = parse_input(input)?
-        .exterior_coords_iter()
-        .map(|c| Coord {
-            x: f64::from(c.x),
-            y: f64::from(c.y),
-        })
-        .collect();
-    let perimeter = coords.len() - 1; // The last point is the same as the first
-    let polygon = Polygon::new(coords.into(), vec![]);
-    let area = polygon.unsigned_area();
-    // Pick's theorem: A = i + b/2 - 1  (https://en.wikipedia.org/wiki/Pick%27s_theorem)
-    // So i = A - b/2 - 1
-    let inner_points = area - perimeter as f64 / 2.0 + 1.0;
-    assert_eq!(inner_points, inner_points.floor()); // This should always be an integer
-    Ok(inner_points as usize)
+    let interior = parse_input(input)?.interior_points();
+    usize::try_from(interior).context("Negative interior")
 }
 
 #[cfg(test)]
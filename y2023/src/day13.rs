@@ -1,4 +1,6 @@
-use anyhow::{anyhow, Result};
+use advent_of_code_common::grid::Grid;
+use advent_of_code_common::symmetry::Orient;
+use anyhow::Result;
 use tracing::instrument;
 
 pub const EXAMPLE1: &str = "#.##..##.
@@ -92,26 +94,14 @@ fn reflection_h_smudged(input: &str) -> Option<usize> {
     None
 }
 
-fn rotate_right(input: &str) -> Result<String> {
-    let lines: Vec<_> = input.lines().collect();
-    let mut rotated = String::new();
-    for index in 0..lines[0].len() {
-        for line in &lines {
-            let c = line
-                .chars()
-                .nth(index)
-                .ok_or_else(|| anyhow!("Index out of bounds"))?;
-            rotated.push(c);
-        }
-        if index != lines[0].len() - 1 {
-            rotated.push('\n');
-        }
-    }
-    Ok(rotated)
+/// Swap rows and columns, so that vertical mirrors become horizontal ones
+fn transpose(input: &str) -> Result<String> {
+    let grid: Grid<char> = input.parse()?;
+    Ok(grid.transpose().to_string())
 }
 
 fn reflection_v(input: &str, smudged: bool) -> Option<usize> {
-    let rotated = rotate_right(input).ok()?;
+    let rotated = transpose(input).ok()?;
     if smudged {
         reflection_h_smudged(rotated.as_str())
     } else {
@@ -157,7 +147,7 @@ mod tests {
 
     #[test]
     fn rotate() {
-        assert_eq!(rotate_right(EXAMPLE1).unwrap(), EXAMPLE1_ROTATED);
+        assert_eq!(transpose(EXAMPLE1).unwrap(), EXAMPLE1_ROTATED);
     }
 
     #[test]
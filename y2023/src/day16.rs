@@ -13,7 +13,7 @@ pub enum Mirror {
 }
 
 impl TryFrom<char> for CellData {
-    type Error = &'static str;
+    type Error = advent_of_code_common::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         let mirror = match value {
@@ -22,7 +22,10 @@ impl TryFrom<char> for CellData {
             '|' => Ok(Mirror::SplitterVertical),
             '-' => Ok(Mirror::SplitterHorizontal),
             '.' => Ok(Mirror::Empty),
-            _ => Err("Invalid mirror type"),
+            _ => Err(Self::Error::invalid_char(
+                value,
+                "a mirror, a splitter or '.'",
+            )),
         };
         Ok(Self {
             mirror: mirror?,
@@ -1,5 +1,6 @@
+use advent_of_code_common::coords::Point;
+use advent_of_code_common::math::polygon::lattice_points;
 use anyhow::Result;
-use geo::{area::Area, Coord, LineString, Polygon};
 
 pub const EXAMPLE: &str = "R 6 (#70c710)
 D 5 (#0dc571)
@@ -37,30 +38,30 @@ impl TryFrom<char> for Direction {
     }
 }
 
-impl From<Direction> for Coord {
+impl From<Direction> for Point {
     fn from(direction: Direction) -> Self {
         match direction {
-            Direction::Up => Self { x: 0.0, y: 1.0 },
-            Direction::Down => Self { x: 0.0, y: -1.0 },
-            Direction::Left => Self { x: -1.0, y: 0.0 },
-            Direction::Right => Self { x: 1.0, y: 0.0 },
+            Direction::Up => Self { x: 0, y: 1 },
+            Direction::Down => Self { x: 0, y: -1 },
+            Direction::Left => Self { x: -1, y: 0 },
+            Direction::Right => Self { x: 1, y: 0 },
         }
     }
 }
 
-fn line_parser_1(s: &str) -> Option<(Direction, f64)> {
+fn line_parser_1(s: &str) -> Option<(Direction, isize)> {
     let mut parts = s.split_whitespace();
     let direction = parts
         .next()
         .and_then(|s| s.chars().next())
         .and_then(|c| Direction::try_from(c).ok())?;
-    let distance: f64 = parts.next().and_then(|s| s.parse().ok())?;
+    let distance: isize = parts.next().and_then(|s| s.parse().ok())?;
     Some((direction, distance))
 }
 
-fn line_parser_2(s: &str) -> Option<(Direction, f64)> {
+fn line_parser_2(s: &str) -> Option<(Direction, isize)> {
     let (hex, dir) = s.split_once('#')?.1.split_at(5);
-    let distance = f64::from(u32::from_str_radix(hex, 16).ok()?);
+    let distance = isize::from_str_radix(hex, 16).ok()?;
     let direction = dir.chars().next().and_then(|c| match c {
         '0' => Some(Direction::Right),
         '1' => Some(Direction::Down),
@@ -71,37 +72,35 @@ fn line_parser_2(s: &str) -> Option<(Direction, f64)> {
     Some((direction, distance))
 }
 
-#[allow(clippy::float_cmp)] // We're not doing any floating point math here, other that the assert_eq! below
-#[allow(clippy::cast_sign_loss)] // We know the result is always positive
-#[allow(clippy::cast_possible_truncation)] // We're checking that the result is an integer
-fn polygon_area<F>(s: &str, line_parser: F) -> usize
+/// The corners of the trench, following the dig plan from `(0, 0)`
+fn vertices<F>(s: &str, line_parser: F) -> Vec<Point>
 where
-    F: Fn(&str) -> Option<(Direction, f64)>,
+    F: Fn(&str) -> Option<(Direction, isize)>,
 {
-    let mut points: Vec<Coord> = vec![Coord { x: 0.0, y: 0.0 }];
-    let mut perimeter = 0.0;
     s.lines()
         .filter_map(line_parser)
-        .for_each(|(direction, distance)| {
-            let prev_point = points.last().copied().expect("There are always points");
-            let travel = Coord::from(direction) * distance;
-            let next_point = prev_point + travel;
-            perimeter += distance;
-            points.push(next_point);
-        });
-    let polygon = Polygon::new(LineString(points), vec![]);
-    let area = polygon.unsigned_area();
-    // Pick's theorem: A = i + b/2 - 1  (https://en.wikipedia.org/wiki/Pick%27s_theorem)
-    // However in our case, i = A + P/2 + 1 where P is the perimeter of the polygon
-    // This is because the result we want is i + P, not just i
-    let inner_points = area + perimeter / 2.0 + 1.0;
-    assert_eq!(inner_points, inner_points.floor()); // This should always be an integer
-    inner_points as usize
+        .scan(Point::default(), |position, (direction, distance)| {
+            *position += Point::from(direction) * distance;
+            Some(*position)
+        })
+        .collect()
+}
+
+/// The number of cubic meters dug out: the trench along the edges and everything inside it
+fn polygon_area<F>(s: &str, line_parser: F) -> usize
+where
+    F: Fn(&str) -> Option<(Direction, isize)>,
+{
+    let vertices = vertices(s, line_parser);
+    // The trench is one meter wide around the vertices, so it counts the lattice points of the
+    // polygon, both inside and on the edges
+    lattice_points(&vertices).unsigned_abs()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code_common::grid::CompressedGrid;
 
     #[test]
     fn example1() {
@@ -122,6 +121,19 @@ mod tests {
         assert_eq!(result, 952408144115);
     }
 
+    #[test]
+    fn compressed_example2() {
+        // The same lagoon, dug out on a compressed grid instead of counted by Pick's theorem
+        let vertices = vertices(EXAMPLE, line_parser_2);
+        let mut grid = CompressedGrid::around(&vertices, false);
+        for (a, b) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            grid.fill_rectangle(a, b, &true);
+        }
+        let outside = grid.flood_fill(&Point::default(), |&dug| !dug);
+        let total = grid.area_where(|_| true);
+        assert_eq!(total - grid.area_of(&outside), 952_408_144_115);
+    }
+
     #[test]
     fn part2() {
         let input = include_str!("../../inputs/2023/day18.txt");
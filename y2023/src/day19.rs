@@ -1,6 +1,7 @@
+use advent_of_code_common::boxes::BoxN;
 use advent_of_code_macros::aoc_tests;
 use anyhow::{anyhow, Context, Result};
-use std::{cmp::Ordering, collections::HashMap, iter::once, str::FromStr};
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
 
 pub const EXAMPLE_RULE_LINE: &str = "ex{x>10:one,m<20:two,a>30:R,A}";
 pub const EXAMPLE_PART_LINE: &str = "{x=787,m=2655,a=1222,s=2876}";
@@ -23,7 +24,8 @@ hdj{m>838:A,pv}
 {x=2127,m=1623,a=2188,s=1013}";
 
 type AllRules<'a> = HashMap<&'a str, RuleSet>;
-type RangeMap = HashMap<(usize, usize), String>;
+/// All possible parts, as a 4D box of `x`, `m`, `a` and `s` ratings
+type PartBox = BoxN<4>;
 type ConditionRanges = (Option<(usize, usize)>, Option<(usize, usize)>);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,35 +64,6 @@ impl FromStr for Part {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct RangedPart {
-    x: RangeMap,
-    m: RangeMap,
-    a: RangeMap,
-    s: RangeMap,
-}
-
-impl Default for RangedPart {
-    fn default() -> Self {
-        let initial_map: RangeMap = once(((1, 4000), "in".to_string())).collect();
-        Self {
-            x: initial_map.clone(),
-            m: initial_map.clone(),
-            a: initial_map.clone(),
-            s: initial_map,
-        }
-    }
-}
-
-impl RangedPart {
-    fn is_finished(&self) -> bool {
-        self.x.values().all(|target| target == "R" || target == "A")
-            && self.m.values().all(|target| target == "R" || target == "A")
-            && self.a.values().all(|target| target == "R" || target == "A")
-            && self.s.values().all(|target| target == "R" || target == "A")
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Field {
     X,
@@ -99,6 +72,18 @@ enum Field {
     S,
 }
 
+impl Field {
+    /// The axis of this field in a [`PartBox`]
+    const fn axis(&self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::M => 1,
+            Self::A => 2,
+            Self::S => 3,
+        }
+    }
+}
+
 impl TryFrom<char> for Field {
     type Error = &'static str;
 
@@ -146,31 +131,18 @@ impl Rule {
         }
     }
 
-    fn eval_range_for_field(&self, part: &mut RangedPart) -> Option<(usize, usize)> {
-        let current_ranges = match self.field {
-            Field::X => part.x.iter(),
-            Field::M => part.m.iter(),
-            Field::A => part.a.iter(),
-            Field::S => part.s.iter(),
-        };
-        let mut new_ranges = HashMap::new();
-        let mut result = None;
-        for ((start, end), _) in current_ranges {
-            let (condition_true, condition_false) = self.eval_range(*start, *end);
-            if let Some((start_true, end_true)) = condition_true {
-                new_ranges.insert((start_true, end_true), self.target.clone());
+    /// Splits a box of parts into the parts that match this rule and the parts that don't
+    fn split_box(&self, parts: &PartBox) -> (Option<PartBox>, Option<PartBox>) {
+        let axis = self.field.axis();
+        let value = i64::try_from(self.value).expect("rule value fits in i64");
+        match self.op {
+            Ordering::Less => parts.split(axis, value),
+            Ordering::Greater => {
+                let (rest, matching) = parts.split(axis, value + 1);
+                (matching, rest)
             }
-            if condition_false.is_some() {
-                result = condition_false;
-            }
-        }
-        match self.field {
-            Field::X => part.x = new_ranges,
-            Field::M => part.m = new_ranges,
-            Field::A => part.a = new_ranges,
-            Field::S => part.s = new_ranges,
+            Ordering::Equal => unreachable!("Equal is not a valid operator"),
         }
-        result
     }
 }
 
@@ -218,23 +190,6 @@ impl RuleSet {
         }
         self.final_target.clone()
     }
-
-    fn eval_range(&self, part: &mut RangedPart) {
-        let mut last_ranges = None;
-        for rule in &self.rules {
-            last_ranges = rule.eval_range_for_field(part);
-        }
-        if let Some((start, end)) = last_ranges {
-            if let Some(last_rule) = self.rules.last() {
-                match last_rule.field {
-                    Field::X => part.x.insert((start, end), last_rule.target.clone()),
-                    Field::M => part.m.insert((start, end), last_rule.target.clone()),
-                    Field::A => part.a.insert((start, end), last_rule.target.clone()),
-                    Field::S => part.s.insert((start, end), last_rule.target.clone()),
-                };
-            }
-        }
-    }
 }
 
 fn parse_rule_set(s: &str) -> Result<(&str, RuleSet)> {
@@ -300,35 +255,46 @@ where
     Ok(total_accepted)
 }
 
-fn machine_process_ranges<H>(machine: &HashMap<&str, RuleSet, H>) -> usize
+/// Counts all combinations of ratings (1 to 4000 each) that end up accepted, by following
+/// whole boxes of parts through the workflows and splitting them at every rule
+fn accepted_combinations<H>(machine: &HashMap<&str, RuleSet, H>) -> Result<u64>
 where
     H: std::hash::BuildHasher,
 {
-    let mut part = RangedPart::default();
-    while !part.is_finished() {
-        for rule_set in machine.values() {
-            rule_set.eval_range(&mut part);
+    let all_parts = PartBox::new([1; 4], [4000; 4]).context("Empty ratings box")?;
+    let mut pending = vec![(all_parts, "in")];
+    let mut accepted = 0;
+    while let Some((parts, state)) = pending.pop() {
+        match state {
+            "A" => accepted += parts.volume(),
+            "R" => {}
+            _ => {
+                let rule_set = machine.get(state).ok_or_else(|| anyhow!("Unknown rule"))?;
+                let mut rest = Some(parts);
+                for rule in &rule_set.rules {
+                    let Some(current) = rest else { break };
+                    let (matching, other) = rule.split_box(&current);
+                    if let Some(matching) = matching {
+                        pending.push((matching, &rule.target));
+                    }
+                    rest = other;
+                }
+                if let Some(rest) = rest {
+                    pending.push((rest, &rule_set.final_target));
+                }
+            }
         }
     }
-    [part.x, part.m, part.a, part.s]
-        .iter()
-        .map(|field_map| {
-            field_map
-                .iter()
-                .map(|((start, end), _)| end - start + 1)
-                .sum::<usize>()
-        })
-        .product()
+    Ok(accepted)
 }
 
 #[aoc_tests]
 mod tests {
     #[test]
-    #[ignore = "Too slow, need to debug"]
     fn part2_example() {
         let (machine, _) = parse_input(EXAMPLE_FULL).unwrap();
-        let total_accepted = machine_process_ranges(&machine);
-        assert_eq!(total_accepted, 167409079868000);
+        let total_accepted = accepted_combinations(&machine).unwrap();
+        assert_eq!(total_accepted, 167_409_079_868_000);
     }
 
     #[test]
@@ -392,6 +358,17 @@ mod tests {
         assert_eq!(rule, expected);
     }
 
+    #[test]
+    fn rule_split_box() {
+        let parts = PartBox::new([1; 4], [4000; 4]).unwrap();
+        let rule = Rule::from_str("m>10:one").unwrap();
+        let (matching, rest) = rule.split_box(&parts);
+        assert_eq!(matching.unwrap().range(1), 11..=4000);
+        assert_eq!(rest.unwrap().range(1), 1..=10);
+        let rule = Rule::from_str("s<5000:two").unwrap();
+        assert_eq!(rule.split_box(&parts), (Some(parts), None));
+    }
+
     #[test]
     fn rule_eval_range() {
         let rule = Rule::from_str("x>10:one").unwrap();
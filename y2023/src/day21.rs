@@ -1,7 +1,8 @@
+use advent_of_code_common::math::polynomial::extrapolate;
 use advent_of_code_macros::aoc_tests;
 use anyhow::{anyhow, Context, Result};
 use petgraph::{algo::dijkstra, graphmap::UnGraphMap};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::instrument;
 
 pub const EXAMPLE: &str = "...........
@@ -119,6 +120,77 @@ pub fn find_even_steps(start: (i32, i32), graph: &Maze, max: i32) -> usize {
         .count()
 }
 
+/// The number of plots reachable in exactly `steps` steps, when the map repeats infinitely in
+/// every direction
+///
+/// # Errors
+///
+/// If the map has invalid tiles or no start
+#[instrument(skip_all, level = "info")]
+pub fn reachable_tiled(s: &str, steps: usize) -> Result<usize> {
+    let rows: Vec<Vec<Tile>> = s
+        .lines()
+        .map(|line| line.trim().chars().map(Tile::try_from).collect())
+        .collect::<Result<_, _>>()
+        .map_err(|e| anyhow!(e))?;
+    let size = i64::try_from(rows.len())?;
+    let start = rows
+        .iter()
+        .zip(0..)
+        .find_map(|(row, y)| {
+            let x = row.iter().position(|&tile| tile == Tile::Start)?;
+            Some((i64::try_from(x).ok()?, y))
+        })
+        .context("No start found")?;
+    let is_open = |(x, y): (i64, i64)| {
+        rows.get(usize::try_from(y.rem_euclid(size)).ok()?)
+            .and_then(|row| row.get(usize::try_from(x.rem_euclid(size)).ok()?))
+            .map(|&tile| tile != Tile::Rock)
+    };
+    // Plots reached with the same parity as `steps` can be revisited by stepping back and forth
+    let mut visited = HashSet::from([start]);
+    let mut frontier = vec![start];
+    let mut count = usize::from(steps.is_multiple_of(2));
+    for step in 1..=steps {
+        frontier = frontier
+            .into_iter()
+            .flat_map(|(x, y)| [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)])
+            .filter(|&p| is_open(p) == Some(true) && visited.insert(p))
+            .collect();
+        if step % 2 == steps % 2 {
+            count += frontier.len();
+        }
+    }
+    Ok(count)
+}
+
+/// [`reachable_tiled`] for step counts far too large to walk.
+///
+/// Once the reachable area has grown past `warmup` copies of the map, each copy further adds a
+/// quadratically growing number of plots, so three samples one map size apart give the rest.
+///
+/// # Errors
+///
+/// If the map is invalid, or the growth turns out not to be quadratic after the warmup
+#[instrument(skip_all, level = "info")]
+pub fn reachable_tiled_extrapolated(s: &str, steps: usize, warmup: usize) -> Result<usize> {
+    let size = s.lines().count();
+    let (periods, rest) = (steps / size, steps % size);
+    if periods < warmup + 3 {
+        return reachable_tiled(s, steps);
+    }
+    let samples = (warmup..warmup + 3)
+        .map(|k| {
+            reachable_tiled(s, rest + k * size)
+                .map(i128::try_from)?
+                .map_err(Into::into)
+        })
+        .collect::<Result<Vec<i128>>>()?;
+    let index = i128::try_from(periods - warmup)?;
+    let count = extrapolate(&samples, index).context("Growth is not quadratic")?;
+    Ok(usize::try_from(count)?)
+}
+
 #[aoc_tests]
 mod tests {
     #[test]
@@ -132,4 +204,34 @@ mod tests {
         let (start, graph) = parse_input(include_str!("../../inputs/2023/day21.txt")).unwrap();
         assert_eq!(find_even_steps(start, &graph, 64), 3729);
     }
+
+    #[test]
+    fn example_tiled() {
+        let expected = [(6, 16), (10, 50), (50, 1594), (100, 6536)];
+        for (steps, plots) in expected {
+            assert_eq!(reachable_tiled(EXAMPLE, steps).unwrap(), plots, "{steps}");
+        }
+        let expected = [
+            (100, 6536),
+            (500, 167_004),
+            (1000, 668_697),
+            (5000, 16_733_044),
+        ];
+        for (steps, plots) in expected {
+            let result = reachable_tiled_extrapolated(EXAMPLE, steps, 4).unwrap();
+            assert_eq!(result, plots, "{steps}");
+        }
+    }
+
+    #[test]
+    fn part2() {
+        let input = include_str!("../../inputs/2023/day21.txt");
+        let size = input.lines().count();
+        // The answer isn't recorded, so the extrapolation is checked against walking a few maps
+        for steps in [size / 2 + 3 * size, size / 2 + 4 * size] {
+            let result = reachable_tiled_extrapolated(input, steps, 0).unwrap();
+            assert_eq!(result, reachable_tiled(input, steps).unwrap(), "{steps}");
+        }
+        assert!(reachable_tiled_extrapolated(input, 26_501_365, 0).is_ok());
+    }
 }
@@ -0,0 +1,179 @@
+use advent_of_code_common::math::linear::{to_integers, Matrix, Rational};
+use advent_of_code_macros::aoc_tests;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use num::Zero;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Hailstone {
+    position: [i128; 3],
+    velocity: [i128; 3],
+}
+
+fn parse_vector(s: &str) -> Result<[i128; 3]> {
+    let values: Vec<i128> = s
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse()
+                .with_context(|| format!("Invalid number {v:?}"))
+        })
+        .collect::<Result<_>>()?;
+    values
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Expected 3 coordinates in {s:?}"))
+}
+
+impl FromStr for Hailstone {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s.split_once('@').context("Missing @")?;
+        Ok(Self {
+            position: parse_vector(position)?,
+            velocity: parse_vector(velocity)?,
+        })
+    }
+}
+
+pub(crate) fn parse_input(s: &str) -> Result<Vec<Hailstone>> {
+    s.lines().map(str::parse).collect()
+}
+
+const fn cross(a: [i128; 3], b: [i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [i128; 3], b: [i128; 3]) -> i128 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+impl Hailstone {
+    /// The position at time `t`
+    fn at(&self, t: Rational) -> [Rational; 3] {
+        std::array::from_fn(|i| t * self.velocity[i] + self.position[i])
+    }
+
+    /// The same hailstone, as seen from `other`
+    fn relative_to(&self, other: &Self) -> Self {
+        Self {
+            position: std::array::from_fn(|i| self.position[i] - other.position[i]),
+            velocity: std::array::from_fn(|i| self.velocity[i] - other.velocity[i]),
+        }
+    }
+
+    /// Where the paths of two hailstones cross, ignoring the Z axis, unless they only crossed in
+    /// the past of either of them
+    fn crossing_xy(&self, other: &Self) -> Option<[Rational; 2]> {
+        let (v, w) = (self.velocity, other.velocity);
+        let directions = Matrix::from_rows([[v[0], -w[0]], [v[1], -w[1]]]);
+        let offset = [0, 1].map(|i| Rational::from_integer(other.position[i] - self.position[i]));
+        let times = directions.solve(&offset)?;
+        if !times.is_unique() || times.particular.iter().any(|t| *t < Rational::zero()) {
+            return None;
+        }
+        let [x, y, _] = self.at(times.particular[0]);
+        Some([x, y])
+    }
+}
+
+/// The number of pairs of hailstones whose paths cross within `area` on both the X and Y axes
+pub(crate) fn count_crossings(hailstones: &[Hailstone], area: &RangeInclusive<i128>) -> usize {
+    let area = Rational::from_integer(*area.start())..=Rational::from_integer(*area.end());
+    hailstones
+        .iter()
+        .tuple_combinations()
+        .filter_map(|(a, b)| a.crossing_xy(b))
+        .filter(|crossing| crossing.iter().all(|c| area.contains(c)))
+        .count()
+}
+
+/// The rock that hits every hailstone, thrown in a straight line at a constant speed
+///
+/// Seen from the first hailstone, the rock has to pass through the origin, so it stays in the
+/// plane through the origin and the path of another hailstone. The times at which the remaining
+/// hailstones cross that plane are when the rock hits them, and two hits are enough to find the
+/// rock's path.
+pub(crate) fn throw_rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let (first, rest) = hailstones.split_first()?;
+    let relative: Vec<Hailstone> = rest.iter().map(|h| h.relative_to(first)).collect();
+    let normal = relative
+        .iter()
+        .map(|h| cross(h.position, h.velocity))
+        .find(|n| *n != [0; 3])?;
+    let mut hits = relative.iter().zip(rest).filter_map(|(r, h)| {
+        let speed = dot(normal, r.velocity);
+        (speed != 0).then(|| {
+            let t = Rational::new(-dot(normal, r.position), speed);
+            (t, h.at(t))
+        })
+    });
+    let (t1, x1) = hits.next()?;
+    let (t2, x2) = hits.find(|(t, _)| *t != t1)?;
+    let velocity: [Rational; 3] = std::array::from_fn(|i| (x2[i] - x1[i]) / (t2 - t1));
+    let position: [Rational; 3] = std::array::from_fn(|i| x1[i] - velocity[i] * t1);
+    Some(Hailstone {
+        position: to_integers(&position)?.try_into().ok()?,
+        velocity: to_integers(&velocity)?.try_into().ok()?,
+    })
+}
+
+#[aoc_tests]
+mod tests {
+    #[test]
+    fn example_1() {
+        let hailstones = parse_input(EXAMPLE).unwrap();
+        assert_eq!(count_crossings(&hailstones, &(7..=27)), 2);
+    }
+
+    #[test]
+    fn example_2() {
+        let hailstones = parse_input(EXAMPLE).unwrap();
+        let rock = throw_rock(&hailstones).unwrap();
+        assert_eq!(rock.position, [24, 13, 10]);
+        assert_eq!(rock.velocity, [-3, 1, 2]);
+    }
+
+    /// Hailstones as far apart as in a real input, to make sure nothing overflows
+    const FAR_APART: &str = "356539367795120, 113831836891126, 268758345704252 @ -146, -10, -287
+364572853236584, 311755746259384, -73065099371764 @ -182, -272, 229
+81585209589288, 96758669461096, -187791543098016 @ 154, 11, 211
+262710047171526, 233095026920392, -11126840334947 @ -40, -139, 34
+369107165142614, 55122932404954, 70366395417422 @ -224, 255, 75";
+
+    #[test]
+    fn far_apart() {
+        let hailstones = parse_input(FAR_APART).unwrap();
+        let area = 100_000_000_000_000..=400_000_000_000_000;
+        assert_eq!(count_crossings(&hailstones, &area), 9);
+        let rock = throw_rock(&hailstones).unwrap();
+        assert_eq!(
+            rock.position,
+            [
+                315_533_108_337_704,
+                222_871_208_630_164,
+                194_201_510_327_132
+            ]
+        );
+        assert_eq!(rock.velocity, [-102, -127, -207]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_input("1, 2, 3 @ 4, 5").is_err());
+        assert!(parse_input("1, 2, 3, 4, 5, 6").is_err());
+        assert!(parse_input("1, 2, x @ 4, 5, 6").is_err());
+    }
+}
@@ -2,7 +2,7 @@ pub mod day01;
 pub mod day02;
 pub mod day03;
 pub mod day04;
-// pub mod day05;
+pub mod day05;
 pub mod day06;
 // pub mod day07;
 pub mod day08;
@@ -19,3 +19,4 @@ pub mod day18;
 pub mod day19;
 // pub mod day20;
 pub mod day21;
+pub mod day24;
@@ -1,41 +1,21 @@
-use advent_of_code_common::grid::{Grid, GridCell};
+use advent_of_code_common::grid::Grid;
 use advent_of_code_macros::aoc_tests;
 
 fn find_sequences(grid: &Grid) -> usize {
-    let search = "MAS"; // Doesn't include the starting X
-    grid.cells
-        .values()
-        .filter(|c| c.data == 'X')
-        .map(|cell| {
-            grid.sight_lines_all(cell, &['S'])
-                .into_iter()
-                .filter(|seq| seq.iter().map(|c| c.data).collect::<String>() == search)
-                .count()
+    grid.rows()
+        .chain(grid.columns())
+        .chain(grid.diagonals())
+        .chain(grid.anti_diagonals())
+        .map(|line| {
+            let line: String = line.into_iter().collect();
+            line.matches("XMAS").count() + line.matches("SAMX").count()
         })
         .sum()
 }
 
-fn slice_to_string(slice: &[Option<char>]) -> String {
-    slice.iter().map(|c| c.unwrap_or(' ')).collect()
-}
-
 fn find_diagonal_sequences(grid: &Grid) -> usize {
-    // Cspell:disable-next-line
-    let correct = ["MMSS", "MSSM", "SSMM", "SMMS"]; // The results of diagonal slices that are correct
-    let diag_str = |cell: &GridCell| {
-        correct.contains(
-            &grid
-                .neighbors_diagonal(cell)
-                .iter()
-                .map(|c| c.map(|c| c.data).unwrap_or_default())
-                .collect::<String>()
-                .as_str(),
-        )
-    };
-    grid.cells
-        .values()
-        .filter(|c| c.data == 'A' && diag_str(c))
-        .count()
+    let pattern: Grid = "M.S\n.A.\nM.S".parse().expect("valid pattern");
+    grid.find_pattern_oriented(&pattern, Some(&'.')).len()
 }
 
 #[aoc_tests]
@@ -1,3 +1,4 @@
+use advent_of_code_common::math::linear::{Matrix, Rational};
 use tracing::instrument;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -69,40 +70,46 @@ fn parse_input(input: &str) -> Result<Vec<Machine>, &'static str> {
                 };
                 result.push(Machine { a, b, prize });
             }
-            _ => {},
+            _ => {}
         }
     }
     Ok(result)
 }
 
+impl Point {
+    /// How many times a button that moves the claw by `self` can be pressed before it overshoots
+    /// `prize` on some axis. A button that doesn't move the claw is never worth pressing.
+    fn press_limit(self, prize: Self) -> i128 {
+        [(self.x, prize.x), (self.y, prize.y)]
+            .into_iter()
+            .filter(|&(step, _)| step > 0)
+            .map(|(step, target)| i128::from(target / step))
+            .min()
+            .unwrap_or(0)
+    }
+}
+
 impl Machine {
-    #[allow(clippy::similar_names)]
+    /// The cheapest way to reach the prize, where pressing A costs 3 tokens and B costs 1
     fn minimize_cost(&self) -> Option<u64> {
-        let div_bx = self.prize.x / self.b.x;
-        let div_by = self.prize.y / self.b.y;
-        for b_multiplier in (0..=div_bx.min(div_by)).rev() {
-            let reach_b = Point {
-                x: b_multiplier * self.b.x,
-                y: b_multiplier * self.b.y,
-            };
-            let diff_b_prize = Point {
-                x: self.prize.x - reach_b.x,
-                y: self.prize.y - reach_b.y,
-            };
-            let (div_ax, div_ay) =
-                if !diff_b_prize.x.is_multiple_of(self.a.x) || !diff_b_prize.y.is_multiple_of(self.a.y) {
-                    continue;
-                } else {
-                    (diff_b_prize.x / self.a.x, diff_b_prize.y / self.a.y)
-                };
-            let a_multiplier = if div_ax == div_ay {
-                div_ax
-            } else {
-                continue;
-            };
-            return Some(a_multiplier * 3 + b_multiplier);
-        }
-        None
+        let buttons = Matrix::from_rows([[self.a.x, self.b.x], [self.a.y, self.b.y]]);
+        let prize = [self.prize.x, self.prize.y].map(i128::from);
+        let cost = if buttons.rank() == 2 {
+            // Independent buttons can only be pressed one way
+            let presses = buttons
+                .solve(&prize.map(Rational::from_integer))?
+                .integer()?;
+            if presses.iter().any(|&p| p < 0) {
+                return None;
+            }
+            3 * presses[0] + presses[1]
+        } else {
+            let bounds = [self.a, self.b].map(|p| p.press_limit(self.prize));
+            buttons
+                .min_cost_integer_solution(&prize, &bounds, &[3, 1])?
+                .0
+        };
+        u64::try_from(cost).ok()
     }
 }
 
@@ -166,12 +173,62 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "takes too long"]
+    fn example_2() {
+        let machines = parse_input(EXAMPLE).unwrap();
+        let winnable: Vec<bool> = machines
+            .iter()
+            .map(|m| {
+                let mut bigger = *m;
+                bigger.prize.x += 10_000_000_000_000;
+                bigger.prize.y += 10_000_000_000_000;
+                bigger.minimize_cost().is_some()
+            })
+            .collect();
+        assert_eq!(winnable, [false, true, false, true]);
+        assert_eq!(sum_cost_bigger(&machines), 875_318_608_908);
+    }
+
+    /// Cramer's rule, which only works if no machine has buttons that move in parallel
+    fn cramer_cost(machine: &Machine) -> Option<u64> {
+        let Machine { a, b, prize } = *machine;
+        let [ax, ay, bx, by, px, py] = [a.x, a.y, b.x, b.y, prize.x, prize.y].map(i128::from);
+        let det = ax * by - ay * bx;
+        assert_ne!(det, 0, "parallel buttons in {machine:?}");
+        let (a, b) = (px * by - py * bx, ax * py - ay * px);
+        if a % det != 0 || b % det != 0 || a / det < 0 || b / det < 0 {
+            return None;
+        }
+        u64::try_from(3 * a / det + b / det).ok()
+    }
+
+    #[test]
     fn part_2() {
         let machines = read_to_string("../inputs/2024/day13.txt")
             .map_err(|e| panic!("Error reading input: {e}"))
             .and_then(|input| parse_input(&input))
             .unwrap();
-        assert_eq!(sum_cost_bigger(&machines), 29598);
+        let expected = machines
+            .iter()
+            .filter_map(|m| {
+                let mut bigger = *m;
+                bigger.prize.x += 10_000_000_000_000;
+                bigger.prize.y += 10_000_000_000_000;
+                cramer_cost(&bigger)
+            })
+            .sum();
+        assert_eq!(sum_cost_bigger(&machines), expected);
+    }
+
+    #[test]
+    fn parallel_buttons() {
+        let machine = |a, b, prize| Machine { a, b, prize };
+        let p = |x, y| Point { x, y };
+        // B is cheaper per step, but 3 presses of B overshoot, so A has to make up the rest
+        assert_eq!(machine(p(2, 2), p(3, 3), p(8, 8)).minimize_cost(), Some(5));
+        assert_eq!(machine(p(2, 2), p(4, 4), p(7, 7)).minimize_cost(), None);
+        // Only the moving axis limits how often a button can be pressed
+        assert_eq!(machine(p(1, 0), p(2, 0), p(5, 0)).minimize_cost(), Some(5));
+        assert_eq!(machine(p(0, 0), p(0, 1), p(0, 4)).minimize_cost(), Some(4));
+        assert_eq!(machine(p(0, 0), p(0, 0), p(1, 0)).minimize_cost(), None);
     }
 }
@@ -1,44 +1,22 @@
-use advent_of_code_macros::aoc_tests;
+use advent_of_code_common::coords;
+use advent_of_code_common::render::{Animate, Canvas, Color, Style};
+use advent_of_code_macros::{aoc_tests, FromInput};
+use std::collections::HashSet;
+
+#[derive(FromInput)]
+#[aoc_format("{x},{y}")]
 struct Point {
     x: i64,
     y: i64,
 }
 
-impl std::str::FromStr for Point {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(',') {
-            Some((x, y)) => Ok(Self {
-                x: x.trim_start_matches(['p', 'v', '='])
-                    .parse()
-                    .map_err(|_| "invalid x")?,
-                y: y.parse().map_err(|_| "invalid y")?,
-            }),
-            _ => Err("invalid point"),
-        }
-    }
-}
-
+#[derive(FromInput)]
+#[aoc_format("p={position} v={velocity}")]
 struct Robot {
     position: Point,
     velocity: Point,
 }
 
-impl std::str::FromStr for Robot {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().split_once(' ') {
-            Some((position, velocity)) => Ok(Self {
-                position: position.parse()?,
-                velocity: velocity.parse()?,
-            }),
-            _ => Err("invalid robot"),
-        }
-    }
-}
-
 fn get_positions(robots: &[Robot], width: i64, height: i64, steps: i64) -> Vec<Point> {
     let positions: Vec<Point> = robots
         .iter()
@@ -79,25 +57,33 @@ fn get_quadrants(robots: &[Robot], width: i64, height: i64, steps: i64) -> i64 {
     top_left * top_right * bottom_left * bottom_right
 }
 
-fn grid_str(positions: &[Point], width: i64, height: i64) -> String {
-    let width = usize::try_from(width).expect("invalid width");
-    let height = usize::try_from(height).expect("invalid height");
-    let mut grid = vec![vec![' '; width]; height];
-    for position in positions {
-        let x = usize::try_from(position.x).expect("invalid x");
-        let y = usize::try_from(position.y).expect("invalid y");
-        grid[y][x] = '#';
-    }
-    grid.iter()
-        .map(|row| row.iter().collect::<String>())
-        .collect()
+fn robots_canvas(positions: &[Point], width: i64, height: i64) -> Canvas {
+    let to_isize = |v: i64| isize::try_from(v).expect("fits in isize");
+    let positions: Vec<coords::Point> = positions
+        .iter()
+        .map(|p| coords::Point {
+            x: to_isize(p.x),
+            y: to_isize(p.y),
+        })
+        .collect();
+    Canvas::new(0..to_isize(width), 0..to_isize(height), ' ')
+        .overlay(&positions, Style::new('#').color(Color::GREEN))
+}
+
+/// Whether 31 robots stand side by side in a row, like the top of the frame around the tree
+fn has_frame(positions: &[Point]) -> bool {
+    let occupied: HashSet<(i64, i64)> = positions.iter().map(|p| (p.x, p.y)).collect();
+    occupied
+        .iter()
+        .any(|&(x, y)| (1..31).all(|dx| occupied.contains(&(x + dx, y))))
 }
 
 #[allow(clippy::maybe_infinite_iter)]
 fn find_tree(robots: &[Robot], width: i64, height: i64) -> usize {
     (0..)
-        .map(|i| grid_str(&get_positions(robots, width, height, i), width, height))
-        .position(|grid| grid.contains("###############################"))
+        .map(|i| get_positions(robots, width, height, i))
+        .animate(|positions| robots_canvas(positions, width, height))
+        .position(|positions| has_frame(&positions))
         .unwrap_or_default()
 }
 
@@ -127,6 +113,34 @@ mod tests {
         assert_eq!(get_quadrants(&robots, 11, 7, 100), 12);
     }
 
+    #[test]
+    fn frame() {
+        let row = |y, xs: std::ops::Range<i64>| xs.map(move |x| Point { x, y });
+        assert!(has_frame(&row(3, 5..36).collect::<Vec<_>>()));
+        assert!(!has_frame(&row(3, 5..35).collect::<Vec<_>>()));
+        let gap: Vec<Point> = row(3, 0..20).chain(row(3, 21..40)).collect();
+        assert!(!has_frame(&gap));
+        let robots: Vec<Robot> = (0..31)
+            .map(|x| Robot {
+                position: Point { x: x - 3, y: 1 },
+                velocity: Point { x: 1, y: 0 },
+            })
+            .collect();
+        assert_eq!(find_tree(&robots, 101, 103), 3);
+    }
+
+    #[test]
+    fn parse_robot() {
+        let robot: Robot = "p=0,4 v=3,-3".parse().unwrap();
+        assert_eq!((robot.position.x, robot.position.y), (0, 4));
+        assert_eq!((robot.velocity.x, robot.velocity.y), (3, -3));
+        let Err(error) = "p=0,4 v=3".parse::<Robot>() else {
+            panic!("missing y of velocity");
+        };
+        assert_eq!((error.column, error.found.as_str()), (9, "3"));
+        assert!(error.expected.starts_with("a valid velocity"));
+    }
+
     #[test]
     fn part_1() {
         let robots = read_input()
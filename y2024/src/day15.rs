@@ -1,6 +1,7 @@
 use advent_of_code_common::grid::{Coords, Direction, Grid, GridCell};
+use advent_of_code_common::render::{Animation, Canvas, Color, Style};
 use advent_of_code_macros::{aoc_tests, char_enum};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use std::{
     collections::{BTreeMap, BTreeSet},
@@ -36,6 +37,33 @@ impl From<State> for [Expanded; 2] {
     }
 }
 
+/// Walls are gray, boxes yellow and the robot red
+const fn style(c: char) -> Style {
+    match c {
+        '#' => Style::new(c).color(Color::GRAY),
+        'O' | '[' | ']' => Style::new(c).color(Color::YELLOW),
+        '@' => Style::new(c).color(Color::RED).bold(),
+        _ => Style::new(c),
+    }
+}
+
+/// Move the robot through all the instructions, one `step` at a time, animating the warehouse
+fn run<T>(
+    grid: Grid<T>,
+    instructions: &[Direction],
+    step: fn(Grid<T>, Direction) -> Result<Grid<T>>,
+) -> Result<Grid<T>>
+where
+    T: Copy + Into<char>,
+{
+    let mut animation = Animation::from_env();
+    instructions.iter().try_fold(grid, |grid, &direction| {
+        let grid = step(grid, direction)?;
+        animation.frame(|| Canvas::from_grid(&grid, |cell| style(cell.data.into())));
+        Ok(grid)
+    })
+}
+
 trait Gps {
     fn gps(&self) -> isize;
 }
@@ -64,7 +92,7 @@ fn parse_input(input: &str) -> Result<(Grid<State>, Vec<Direction>)> {
         .take_while_ref(|line| !line.trim().is_empty())
         .join("\n")
         .parse()
-        .map_err(|e| anyhow!("Failed to parse grid: {e}"))?;
+        .context("Failed to parse grid")?;
     let instructions = lines
         .flat_map(|line| line.trim().chars())
         .filter_map(|c| Direction::try_from(c).ok())
@@ -212,20 +240,14 @@ mod tests {
     fn example_1() {
         let input = std::fs::read_to_string("../inputs/2024/day15_example.txt").unwrap();
         let (grid, instructions) = parse_input(&input).unwrap();
-        let result = instructions
-            .iter()
-            .try_fold(grid, |grid, &direction| push_step(grid, direction))
-            .unwrap();
+        let result = run(grid, &instructions, push_step).unwrap();
         assert_eq!(grid_gps_values(&result), 10092);
     }
 
     #[test]
     fn part_1() {
         let (grid, instructions) = parse_input(&read_input()).unwrap();
-        let result = instructions
-            .iter()
-            .try_fold(grid, |grid, &direction| push_step(grid, direction))
-            .unwrap();
+        let result = run(grid, &instructions, push_step).unwrap();
         assert_eq!(grid_gps_values(&result), 1526673);
     }
 
@@ -234,12 +256,7 @@ mod tests {
         let input = std::fs::read_to_string("../inputs/2024/day15_example.txt").unwrap();
         let (grid, instructions) = parse_input(&input).unwrap();
         let expanded = expand_grid(&grid).unwrap();
-        let result = instructions
-            .iter()
-            .try_fold(expanded, |expanded, &direction| {
-                push_step_expanded(expanded, direction)
-            })
-            .unwrap();
+        let result = run(expanded, &instructions, push_step_expanded).unwrap();
         assert_eq!(grid_gps_values(&result), 9021);
     }
 
@@ -247,12 +264,7 @@ mod tests {
     fn part_2() {
         let (grid, instructions) = parse_input(&read_input()).unwrap();
         let expanded = expand_grid(&grid).unwrap();
-        let result = instructions
-            .iter()
-            .try_fold(expanded, |expanded, &direction| {
-                push_step_expanded(expanded, direction)
-            })
-            .unwrap();
+        let result = run(expanded, &instructions, push_step_expanded).unwrap();
         assert_eq!(grid_gps_values(&result), 1535509);
     }
 }
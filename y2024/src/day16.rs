@@ -17,13 +17,18 @@ struct CellData {
 }
 
 impl TryFrom<char> for CellData {
-    type Error = ();
+    type Error = advent_of_code_common::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         let state = match value {
             '.' | 'S' | 'E' => State::Empty,
             '#' => State::Wall,
-            _ => return Err(()),
+            _ => {
+                return Err(Self::Error::invalid_char(
+                    value,
+                    "one of '.', '#', 'S', 'E'",
+                ));
+            }
         };
         Ok(Self {
             state,
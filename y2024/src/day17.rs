@@ -1,4 +1,9 @@
+use advent_of_code_common::file::parse::{
+    blank_lines, labelled, line, separated, unsigned, ParseError, Parser,
+};
+use advent_of_code_common::vm::{Flow, InstructionSet, Vm};
 use advent_of_code_macros::aoc_tests;
+use itertools::Itertools;
 use std::{ops::Range, str::FromStr};
 
 #[derive(Debug, Clone, Copy)]
@@ -31,218 +36,196 @@ impl TryFrom<u8> for Instruction {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Machine {
+/// A single instruction with its operand, the program counter always moves 2 words at a time
+#[derive(Debug, Clone, Copy)]
+struct Op {
+    instruction: Instruction,
+    operand: u8,
+}
+
+/// Disassembles into a readable pseudo-code statement, such as `b = a % 8` or `b ^= 5`
+impl std::fmt::Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let combo = match self.operand {
+            4 => "a".to_string(),
+            5 => "b".to_string(),
+            6 => "c".to_string(),
+            n => n.to_string(),
+        };
+        match self.instruction {
+            Instruction::Adv => write!(f, "a = a >> {combo}"),
+            Instruction::Bxl => write!(f, "b ^= {}", self.operand),
+            Instruction::Bst => write!(f, "b = {combo} % 8"),
+            Instruction::Jnz => write!(f, "if a != 0 goto {}", self.operand),
+            Instruction::Bxc => write!(f, "b ^= c"),
+            Instruction::Out => write!(f, "out {combo} % 8"),
+            Instruction::Bdv => write!(f, "b = a >> {combo}"),
+            Instruction::Cdv => write!(f, "c = a >> {combo}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Registers {
     a: u64,
     b: u64,
     c: u64,
-    ip: usize,
-    original_instructions: String,
-    instructions: Vec<Instruction>,
     output: Vec<u8>,
 }
 
-impl FromStr for Machine {
-    type Err = ();
+impl Registers {
+    fn combo(&self, operand: u8) -> u64 {
+        match operand {
+            0..=3 => u64::from(operand),
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            _ => unimplemented!("Invalid combo operator {operand}"),
+        }
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let a = lines
-            .next()
-            .unwrap_or_default()
-            .split_whitespace()
-            .last()
-            .unwrap_or_default()
-            .parse()
-            .map_err(|_| ())?;
-        let b = lines
-            .next()
-            .unwrap_or_default()
-            .split_whitespace()
-            .last()
-            .unwrap_or_default()
-            .parse()
-            .map_err(|_| ())?;
-        let c = lines
-            .next()
-            .unwrap_or_default()
-            .split_whitespace()
-            .last()
-            .unwrap_or_default()
-            .parse()
-            .map_err(|_| ())?;
-        let original_instructions = lines
-            .nth(1)
-            .unwrap_or_default()
-            .split_whitespace()
-            .last()
-            .unwrap_or_default()
-            .to_string();
-        let instructions = original_instructions
-            .split(',')
-            .filter_map(|x| x.parse::<u8>().ok())
-            .filter_map(|n| Instruction::try_from(n).ok())
-            .collect();
-        Ok(Self {
-            a,
-            b,
-            c,
-            ip: 0,
-            original_instructions,
-            instructions,
-            output: Vec::new(),
-        })
+    fn output(&self) -> String {
+        self.output
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(",")
     }
 }
 
-impl Machine {
-    fn combo_operator(&self) -> u64 {
-        match self.instructions.get(self.ip + 1).expect("Invalid instruction pointer") {
-            Instruction::Adv => 0,
-            Instruction::Bxl => 1,
-            Instruction::Bst => 2,
-            Instruction::Jnz => 3,
-            Instruction::Bxc => self.a, // 4
-            Instruction::Out => self.b, // 5
-            Instruction::Bdv => self.c, // 6
-            Instruction::Cdv => unimplemented!("Invalid combo operator 7"),
+impl InstructionSet for Op {
+    type State = Registers;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn execute(&self, registers: &mut Registers) -> Flow {
+        let combo = || registers.combo(self.operand);
+        match self.instruction {
+            Instruction::Adv => registers.a >>= combo(),
+            Instruction::Bxl => registers.b ^= u64::from(self.operand),
+            Instruction::Bst => registers.b = combo() & 7,
+            // Jump targets are word addresses, but every instruction takes 2 words
+            Instruction::Jnz if registers.a != 0 => return Flow::Goto(self.operand as usize / 2),
+            Instruction::Jnz => {}
+            Instruction::Bxc => registers.b ^= registers.c,
+            Instruction::Out => {
+                let value = (combo() & 7) as u8;
+                registers.output.push(value);
+            }
+            Instruction::Bdv => registers.b = registers.a >> combo(),
+            Instruction::Cdv => registers.c = registers.a >> combo(),
         }
+        Flow::Next
     }
+}
 
-    fn literal_operator(&self) -> u64 {
-        *self.instructions.get(self.ip + 1).expect("Invalid instruction pointer") as u64
-    }
+#[derive(Debug, Clone)]
+struct Machine {
+    registers: Registers,
+    original_instructions: String,
+    /// The program as raw 3-bit words
+    words: Vec<u8>,
+    program: Vec<Op>,
+}
 
-    fn division(&self) -> u64 {
-        // self.a / 2u64.pow(self.combo_operator() as u32)
-        self.a >> self.combo_operator()
-    }
+impl FromStr for Machine {
+    type Err = ParseError;
 
-    fn modulo(&self) -> u64 {
-        // self.combo_operator() % 8
-        self.combo_operator() & 7
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let register = |name| line(labelled(name, unsigned::<u64>()));
+        let op = |pair: &[u8]| {
+            Some(Op {
+                instruction: Instruction::try_from(pair[0]).ok()?,
+                operand: *pair.get(1)?,
+            })
+        };
+        let program =
+            separated(unsigned::<u8>(), ",").try_map("a program of opcode pairs", |words| {
+                let program = words.chunks(2).map(op).collect::<Option<Vec<_>>>()?;
+                Some((words.iter().join(","), words, program))
+            });
+        let ((((a, b), c), ()), (original_instructions, words, program)) = register("Register A:")
+            .then(register("Register B:"))
+            .then(register("Register C:"))
+            .then(blank_lines())
+            .then(line(labelled("Program:", program)))
+            .parse(s)?;
+        Ok(Self {
+            registers: Registers {
+                a,
+                b,
+                c,
+                output: Vec::new(),
+            },
+            original_instructions,
+            words,
+            program,
+        })
     }
+}
 
-    const fn increment_instruction(&mut self) {
-        self.ip += 2;
+impl Machine {
+    /// A fresh VM for this program, starting from the parsed registers
+    fn vm(&self) -> Vm<'_, Op> {
+        Vm::new(&self.program, self.registers.clone())
     }
 
-    #[allow(clippy::cast_possible_truncation)]
-    fn op(&mut self) -> bool {
-        let instruction = self.instructions[self.ip];
-        match instruction {
-            Instruction::Adv => {
-                self.a = self.division();
-                self.increment_instruction();
-                false
-            }
-            Instruction::Bxl => {
-                self.b ^= self.literal_operator();
-                self.increment_instruction();
-                false
-            }
-            Instruction::Bst => {
-                self.b = self.modulo();
-                self.increment_instruction();
-                false
-            }
-            Instruction::Jnz if self.a != 0 => {
-                self.ip = self.literal_operator() as usize;
-                false
-            }
-            Instruction::Jnz => {
-                self.increment_instruction();
-                false
-            }
-            Instruction::Bxc => {
-                self.b ^= self.c;
-                self.increment_instruction();
-                false
-            }
-            Instruction::Out => {
-                self.output.push(self.modulo() as u8);
-                self.increment_instruction();
-                true
-            }
-            Instruction::Bdv => {
-                self.b = self.division();
-                self.increment_instruction();
-                false
-            }
-            Instruction::Cdv => {
-                self.c = self.division();
-                self.increment_instruction();
-                false
-            }
-        }
+    /// A fresh VM for this program, with register A set to `a` and the others cleared
+    fn vm_with(&self, a: u64) -> Vm<'_, Op> {
+        let registers = Registers {
+            a,
+            ..Registers::default()
+        };
+        Vm::new(&self.program, registers)
     }
 
-    fn run(&mut self) {
-        while self.ip < self.instructions.len() {
-            self.op();
-        }
+    /// One disassembled statement per line, prefixed with its word address
+    fn disassemble(&self) -> String {
+        self.program
+            .iter()
+            .enumerate()
+            .map(|(i, op)| format!("{:>2}: {op}", i * 2))
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 
-    fn run_until_output(&mut self) {
-        while self.ip < self.instructions.len() {
-            if self.op() {
-                break;
-            }
-        }
+    /// The program as raw 3-bit words
+    fn words(&self) -> &[u8] {
+        &self.words
     }
 
-    fn output(&self) -> String {
-        self.output
-            .iter()
-            .map(ToString::to_string)
-            .collect::<Vec<String>>()
-            .join(",")
+    fn run(&self) -> String {
+        let mut vm = self.vm();
+        vm.run();
+        vm.state.output()
     }
 
-    fn reset_with(&mut self, a: u64) {
-        self.a = a;
-        self.b = 0;
-        self.c = 0;
-        self.ip = 0;
-        self.output.clear();
-        self.instructions = self
-            .original_instructions
-            .split_whitespace()
-            .last()
-            .unwrap_or_default()
-            .split(',')
-            .filter_map(|x| x.parse::<u8>().ok())
-            .filter_map(|n| Instruction::try_from(n).ok())
-            .collect();
-    }
-
-    fn is_self_replicate(&mut self, current_run: u64) -> bool {
-        self.reset_with(current_run);
+    fn is_self_replicate(&self, current_run: u64) -> bool {
+        let mut vm = self.vm_with(current_run);
         loop {
-            self.run_until_output();
-            if !self.original_instructions.starts_with(&self.output()) {
+            let outputs = vm.state.output.len();
+            let exit = vm.run_until(|r| r.output.len() > outputs);
+            if !self.original_instructions.starts_with(&vm.state.output()) {
                 return false;
-            } else if self.ip >= self.instructions.len() {
+            } else if exit.is_some() {
                 break;
             }
         }
-        self.output() == self.original_instructions
+        vm.state.output() == self.original_instructions
     }
 }
 
 fn find_replicator_recursive(machine: &Machine, range: Range<u64>, previous: &str) -> Option<u64> {
-    let mut current_machine = machine.clone();
     for n in range {
-        current_machine.reset_with(n);
-        current_machine.run_until_output();
+        let mut vm = machine.vm_with(n);
+        vm.run_until(|r| !r.output.is_empty());
         let output = if previous.is_empty() {
-            current_machine.output()
+            vm.state.output()
         } else {
-            [current_machine.output(), previous.to_string()].join(",")
+            [vm.state.output(), previous.to_string()].join(",")
         };
-        if output == current_machine.original_instructions {
+        if output == machine.original_instructions {
             return Some(n);
-        } else if current_machine.original_instructions.ends_with(&output) {
+        } else if machine.original_instructions.ends_with(&output) {
             let new_range = n * 8..(n + 1) * 8;
             let inner = find_replicator_recursive(machine, new_range, &output);
             if inner.is_some() {
@@ -253,6 +236,235 @@ fn find_replicator_recursive(machine: &Machine, range: Range<u64>, previous: &st
     None
 }
 
+/// A register value during symbolic execution, in terms of the initial value of register A
+/// at the start of a loop iteration
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Sym {
+    /// B or C as left by the previous iteration
+    Carried,
+    Const(u64),
+    /// `A >> shift`
+    A(u32),
+    Xor(Box<Self>, Box<Self>),
+    Mod8(Box<Self>),
+    Shr(Box<Self>, Box<Self>),
+}
+
+impl Sym {
+    fn xor(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Const(x), Self::Const(y)) => Self::Const(x ^ y),
+            (Self::Const(0), s) | (s, Self::Const(0)) => s,
+            (Self::Const(c), Self::Xor(x, y)) | (Self::Xor(x, y), Self::Const(c)) => match *y {
+                Self::Const(d) => x.xor(Self::Const(c ^ d)),
+                y => Self::Xor(
+                    Box::new(Self::Xor(x, Box::new(y))),
+                    Box::new(Self::Const(c)),
+                ),
+            },
+            (x, y) => Self::Xor(Box::new(x), Box::new(y)),
+        }
+    }
+
+    fn mod8(self) -> Self {
+        match self {
+            Self::Const(x) => Self::Const(x & 7),
+            s @ Self::Mod8(_) => s,
+            s => Self::Mod8(Box::new(s)),
+        }
+    }
+
+    fn shr(self, amount: Self) -> Self {
+        match (self, amount) {
+            (Self::Const(x), Self::Const(y)) => Self::Const(x.checked_shr(shift(y)).unwrap_or(0)),
+            (Self::A(s), Self::Const(y)) => Self::A(s.saturating_add(shift(y))),
+            (s, Self::Const(0)) => s,
+            (x, y) => Self::Shr(Box::new(x), Box::new(y)),
+        }
+    }
+
+    /// Moves every reference to A by `bits`, giving the same function for a later iteration
+    fn shifted(&self, bits: u32) -> Self {
+        match self {
+            Self::Carried => Self::Carried,
+            Self::Const(x) => Self::Const(*x),
+            Self::A(s) => Self::A(s.saturating_add(bits)),
+            Self::Xor(x, y) => Self::Xor(Box::new(x.shifted(bits)), Box::new(y.shifted(bits))),
+            Self::Mod8(x) => Self::Mod8(Box::new(x.shifted(bits))),
+            Self::Shr(x, y) => Self::Shr(Box::new(x.shifted(bits)), Box::new(y.shifted(bits))),
+        }
+    }
+
+    fn depends_on_carried(&self) -> bool {
+        match self {
+            Self::Carried => true,
+            Self::Const(_) | Self::A(_) => false,
+            Self::Xor(x, y) | Self::Shr(x, y) => x.depends_on_carried() || y.depends_on_carried(),
+            Self::Mod8(x) => x.depends_on_carried(),
+        }
+    }
+
+    /// Evaluates the expression, which must not depend on carried state
+    fn eval(&self, a: u64) -> u64 {
+        match self {
+            Self::Carried => unreachable!("outputs don't depend on carried state"),
+            Self::Const(x) => *x,
+            Self::A(s) => a.checked_shr(*s).unwrap_or(0),
+            Self::Xor(x, y) => x.eval(a) ^ y.eval(a),
+            Self::Mod8(x) => x.eval(a) & 7,
+            Self::Shr(x, y) => x.eval(a).checked_shr(shift(y.eval(a))).unwrap_or(0),
+        }
+    }
+}
+
+/// Clamps a shift amount so that shifting by it can't overflow
+fn shift(amount: u64) -> u32 {
+    u32::try_from(amount).unwrap_or(u32::MAX)
+}
+
+impl std::fmt::Display for Sym {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Carried => write!(f, "?"),
+            Self::Const(x) => write!(f, "{x}"),
+            Self::A(0) => write!(f, "A"),
+            Self::A(s) => write!(f, "(A >> {s})"),
+            Self::Xor(x, y) => write!(f, "({x} ^ {y})"),
+            Self::Mod8(x) => write!(f, "{x} % 8"),
+            Self::Shr(x, y) => write!(f, "({x} >> {y})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AnalysisError {
+    /// The program is not a single loop ending with `jnz 0`
+    NotASingleLoop,
+    /// Register A is shifted by a value that is not a constant
+    SymbolicShift,
+    /// Each iteration must output exactly once, not this many times
+    OutputsPerIteration(usize),
+    /// An output depends on the B or C values left over from the previous iteration
+    CarriedState,
+}
+
+/// The outcome of symbolically executing a single loop iteration
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LoopAnalysis {
+    /// The iteration's output, as a function of A's value at the start of the iteration
+    output: Sym,
+    /// How many bits A is shifted right on every iteration
+    step: u32,
+}
+
+impl LoopAnalysis {
+    /// The output of iteration `k` as a function of the initial value of A
+    fn output(&self, k: u32) -> Sym {
+        self.output.shifted(k * self.step)
+    }
+
+    /// Finds the smallest value of register A (with B and C at zero) that makes the program
+    /// output exactly `target`.
+    ///
+    /// The loop stops once A is zero, so A has exactly `step` bits per output and its highest
+    /// chunk must be non-zero if there is more than one output. Output `k` only depends on the
+    /// bits from chunk `k` and up, so the chunks are assigned from the highest one down, checking
+    /// each output as soon as all of the bits it depends on are known. Trying the chunk values in
+    /// increasing order means the first complete assignment is the minimal one.
+    fn find_minimal_a(&self, target: &[u8]) -> Option<u64> {
+        let n = u32::try_from(target.len()).ok()?;
+        // Every chunk of `step` bits is searched exhaustively, and all of them have to fit in A
+        if n == 0 || self.step == 0 || self.step > 63 {
+            return None;
+        }
+        if n.checked_mul(self.step)? > 64 {
+            return None;
+        }
+        self.search(target, n - 1, 0)
+    }
+
+    fn search(&self, target: &[u8], k: u32, high_bits: u64) -> Option<u64> {
+        let output = self.output(k);
+        let last = k as usize + 1 == target.len();
+        for chunk in 0..1 << self.step {
+            if last && chunk == 0 && k > 0 {
+                continue; // The loop would stop before printing everything
+            }
+            let a = high_bits | (chunk << (k * self.step));
+            if output.eval(a) != u64::from(target[k as usize]) {
+                continue;
+            }
+            if k == 0 {
+                return Some(a);
+            }
+            if let Some(a) = self.search(target, k - 1, a) {
+                return Some(a);
+            }
+        }
+        None
+    }
+}
+
+impl Machine {
+    /// Symbolically executes one iteration of the program's loop.
+    fn analyze(&self) -> Result<LoopAnalysis, AnalysisError> {
+        let Some((
+            Op {
+                instruction: Instruction::Jnz,
+                operand: 0,
+            },
+            body,
+        )) = self.program.split_last()
+        else {
+            return Err(AnalysisError::NotASingleLoop);
+        };
+        let (mut step, mut b, mut c) = (0, Sym::Carried, Sym::Carried);
+        let mut outputs = Vec::new();
+        for op in body {
+            let a = Sym::A(step);
+            let combo = match op.operand {
+                4 => a.clone(),
+                5 => b.clone(),
+                6 => c.clone(),
+                n => Sym::Const(u64::from(n)),
+            };
+            match op.instruction {
+                Instruction::Adv => match a.shr(combo) {
+                    Sym::A(shift) => step = shift,
+                    _ => return Err(AnalysisError::SymbolicShift),
+                },
+                Instruction::Bxl => b = b.xor(Sym::Const(u64::from(op.operand))),
+                Instruction::Bst => b = combo.mod8(),
+                Instruction::Jnz => return Err(AnalysisError::NotASingleLoop),
+                Instruction::Bxc => b = b.xor(c.clone()),
+                Instruction::Out => outputs.push(combo.mod8()),
+                Instruction::Bdv => b = a.shr(combo),
+                Instruction::Cdv => c = a.shr(combo),
+            }
+        }
+        if outputs.len() != 1 {
+            return Err(AnalysisError::OutputsPerIteration(outputs.len()));
+        }
+        let output = outputs.remove(0);
+        if output.depends_on_carried() {
+            return Err(AnalysisError::CarriedState);
+        }
+        Ok(LoopAnalysis { output, step })
+    }
+
+    /// Finds the smallest initial value of register A that makes the program output `target`,
+    /// see [`LoopAnalysis::find_minimal_a`]
+    fn find_minimal_a(&self, target: &[u8]) -> Result<Option<u64>, AnalysisError> {
+        let a = self.analyze()?.find_minimal_a(target);
+        if let Some(a) = a {
+            let mut vm = self.vm_with(a);
+            vm.run();
+            debug_assert_eq!(vm.state.output, target, "symbolic solution for A = {a}");
+        }
+        Ok(a)
+    }
+}
+
 #[aoc_tests]
 mod tests {
     const EXAMPLE1: &str = "Register A: 729
@@ -268,16 +480,14 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let mut machine: Machine = EXAMPLE1.parse().unwrap();
-        machine.run();
-        assert_eq!(machine.output(), "4,6,3,5,6,3,5,2,1,0");
+        let machine: Machine = EXAMPLE1.parse().unwrap();
+        assert_eq!(machine.run(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn part_1() {
-        let mut machine: Machine = read_input().parse().unwrap();
-        machine.run();
-        assert_eq!(machine.output(), "2,1,3,0,5,2,3,7,1");
+        let machine: Machine = read_input().parse().unwrap();
+        assert_eq!(machine.run(), "2,1,3,0,5,2,3,7,1");
     }
 
     #[test]
@@ -290,8 +500,136 @@ mod tests {
     fn part_2() {
         let machine: Machine = read_input().parse().unwrap();
         assert_eq!(
-            find_replicator_recursive(&machine, 1..8, ""),
-            Some(107416732707226),
+            machine.find_minimal_a(machine.words()),
+            Ok(Some(107_416_732_707_226)),
+        );
+    }
+
+    /// A typical puzzle input, which mixes A's low bits with bits further up through C
+    const REALISTIC: &str = "Register A: 0
+                             Register B: 0
+                             Register C: 0
+
+                             Program: 2,4,1,5,7,5,1,6,0,3,4,0,5,5,3,0";
+
+    #[test]
+    fn disassemble() {
+        let machine: Machine = REALISTIC.parse().unwrap();
+        assert_eq!(
+            machine.disassemble(),
+            " 0: b = a % 8
+ 2: b ^= 5
+ 4: c = a >> b
+ 6: b ^= 6
+ 8: a = a >> 3
+10: b ^= c
+12: out b % 8
+14: if a != 0 goto 0"
+        );
+    }
+
+    #[test]
+    fn symbolic_output() {
+        let machine: Machine = REALISTIC.parse().unwrap();
+        let analysis = machine.analyze().unwrap();
+        assert_eq!(analysis.step, 3);
+        assert_eq!(
+            analysis.output.to_string(),
+            "((A % 8 ^ 3) ^ (A >> (A % 8 ^ 5))) % 8"
+        );
+        for a in 0..4096 {
+            let mut vm = machine.vm_with(a);
+            vm.run();
+            for (k, &value) in (0..).zip(&vm.state.output) {
+                assert_eq!(analysis.output(k).eval(a), u64::from(value), "A = {a}");
+            }
+        }
+    }
+
+    #[test]
+    fn example_2_symbolic() {
+        let machine: Machine = EXAMPLE2.parse().unwrap();
+        assert_eq!(machine.find_minimal_a(machine.words()), Ok(Some(117_440)));
+    }
+
+    #[test]
+    fn minimal_a_matches_brute_force() {
+        for program in [EXAMPLE1, EXAMPLE2, REALISTIC] {
+            let machine: Machine = program.parse().unwrap();
+            let mut first_a = std::collections::HashMap::new();
+            for a in 0..1 << 12 {
+                let mut vm = machine.vm_with(a);
+                vm.run();
+                first_a.entry(vm.state.output).or_insert(a);
+            }
+            for (output, a) in first_a {
+                assert_eq!(machine.find_minimal_a(&output), Ok(Some(a)), "{output:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn arbitrary_target() {
+        let machine: Machine = REALISTIC.parse().unwrap();
+        let mut vm = machine.vm_with(0o1234_5670_1234_5670);
+        vm.run();
+        let target = vm.state.output;
+        let a = machine.find_minimal_a(&target).unwrap().unwrap();
+        assert!(a <= 0o1234_5670_1234_5670);
+        let mut vm = machine.vm_with(a);
+        vm.run();
+        assert_eq!(vm.state.output, target);
+        assert_eq!(machine.find_minimal_a(&[]), Ok(None));
+    }
+
+    #[test]
+    fn oversized_steps() {
+        let analysis = |step| LoopAnalysis {
+            output: Sym::Const(0),
+            step,
+        };
+        assert_eq!(analysis(64).find_minimal_a(&[0]), None);
+        assert_eq!(analysis(u32::MAX).find_minimal_a(&[0, 0]), None);
+        assert_eq!(analysis(32).find_minimal_a(&[0, 0, 0]), None);
+        assert_eq!(analysis(32).find_minimal_a(&[0, 0]), Some(1 << 32));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = EXAMPLE1
+            .replace("C: 0", "C: zero")
+            .parse::<Machine>()
+            .unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (3, "zero"));
+        let error = "Register A: 1\nRegister B: 2\nRegister C: 3\n\nProgram: 0,1,8,2"
+            .parse::<Machine>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (5, 10));
+        assert_eq!(error.expected, "a program of opcode pairs");
+    }
+
+    #[test]
+    fn unsupported_programs() {
+        let parse = |program: &str| -> Machine {
+            format!("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}")
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(
+            parse("0,3,5,4").analyze(),
+            Err(AnalysisError::NotASingleLoop)
+        );
+        assert_eq!(
+            parse("2,4,0,5,5,4,3,0").analyze(),
+            Err(AnalysisError::SymbolicShift)
+        );
+        assert_eq!(
+            parse("0,3,5,4,5,4,3,0").analyze(),
+            Err(AnalysisError::OutputsPerIteration(2))
+        );
+        assert_eq!(
+            parse("0,3,5,5,3,0").analyze(),
+            Err(AnalysisError::CarriedState)
         );
     }
 }
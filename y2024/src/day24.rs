@@ -1,141 +1,20 @@
+use advent_of_code_common::circuit::Circuit;
 use advent_of_code_macros::aoc_tests;
-use petgraph::prelude::{Directed, GraphMap, Incoming};
-use std::collections::BTreeMap;
-use tracing::{debug, warn};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Gate {
-    And,
-    Or,
-    Xor,
-    Unknown,
+fn parse_circuit(input: &str) -> Circuit {
+    input
+        .parse::<Circuit>()
+        .expect("valid netlist")
+        .with_width(1)
 }
 
-impl From<&str> for Gate {
-    fn from(s: &str) -> Self {
-        match s {
-            "AND" => Self::And,
-            "OR" => Self::Or,
-            "XOR" => Self::Xor,
-            _ => Self::Unknown,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-struct Wire<'a> {
-    name: &'a str,
-    gate: Gate,
-}
-
-impl std::fmt::Display for Wire<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {:?}", self.name, self.gate)
-    }
-}
-
-impl<'a> Wire<'a> {
-    fn new(name: &'a str, gate: impl AsRef<str>) -> Self {
-        Self {
-            name,
-            gate: Gate::from(gate.as_ref()),
-        }
-    }
-}
-
-type WireGraph<'a> = GraphMap<Wire<'a>, u8, Directed>;
-type WireValues<'a> = BTreeMap<Wire<'a>, Option<u8>>;
-
-fn parse_graph(input: &str) -> (WireGraph<'_>, WireValues<'_>) {
-    let mut graph = GraphMap::new();
-    let mut values = BTreeMap::new();
-    for line in input.lines() {
-        if line.contains(':') {
-            let (name, value) = line.split_once(": ").unwrap_or_default();
-            let value_wire = Wire::new(name.trim(), value);
-            match value.parse::<u8>() {
-                Ok(value) => {
-                    values.insert(value_wire, Some(value));
-                }
-                Err(_) => {
-                    warn!("Error parsing value: {value} in line {line}");
-                }
-            }
-            graph.add_node(value_wire);
-        }
-        if line.contains("->") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            let gate_wire = Wire::new(parts[4], parts[1]);
-            graph.add_node(gate_wire);
-        }
-    }
-    for line in input.lines() {
-        if line.contains("->") {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            let gate_wire = Wire::new(parts[4], parts[1]);
-            values.entry(gate_wire).or_insert(None);
-            if let Some(left_wire) = graph.nodes().find(|n| n.name == parts[0]) {
-                graph.add_edge(left_wire, gate_wire, 1);
-                values.entry(left_wire).or_insert(None);
-            }
-            if let Some(right_wire) = graph.nodes().find(|n| n.name == parts[2]) {
-                graph.add_edge(right_wire, gate_wire, 1);
-                values.entry(right_wire).or_insert(None);
-            }
-        }
-    }
-    (graph, values)
-}
-
-fn calc_wire_value<'a>(graph: &WireGraph<'a>, values: &mut WireValues<'a>, wire: Wire<'a>) -> u8 {
-    if let Some(Some(value)) = values.get(&wire) {
-        return *value;
-    }
-    debug!("Calculating value for {wire}");
-    let inputs = graph.neighbors_directed(wire, Incoming).collect::<Vec<_>>();
-    debug_assert!(inputs.len() == 2, "{wire} has {} inputs", inputs.len());
-    let left = inputs[0];
-    let right = inputs[1];
-    let left_value = calc_wire_value(graph, values, left);
-    let right_value = calc_wire_value(graph, values, right);
-    let value = match wire.gate {
-        Gate::And => left_value & right_value,
-        Gate::Or => left_value | right_value,
-        Gate::Xor => left_value ^ right_value,
-        Gate::Unknown => unreachable!(),
-    };
-    values.insert(wire, Some(value));
-    value
+fn bit_values(input: &str, register: char) -> u64 {
+    parse_circuit(input).evaluate().bus(&register.to_string())
 }
 
-fn bit_values(input: &str, register: char) -> u64 {
-    let (graph, mut values) = parse_graph(input);
-    let register_wires = graph
-        .nodes()
-        .filter(|n| n.name.starts_with(register))
-        .collect::<Vec<_>>();
-    for wire in register_wires {
-        if wire
-            .name
-            .trim_start_matches(register)
-            .parse::<u16>()
-            .is_ok()
-        {
-            calc_wire_value(&graph, &mut values, wire);
-        }
-    }
-    let mut result = 0_u64;
-    for (k, v) in &values {
-        if k.name.starts_with(register) {
-            debug!("{k} has value {v:?}");
-            if let Ok(exp) = k.name.trim_start_matches(register).parse::<u16>() {
-                result += u64::from(v.expect("register node value")) << exp;
-            }
-        }
-    }
-    let g = petgraph::dot::Dot::new(&graph);
-    println!("{g}");
-    result
+/// The wires with swapped outputs, assuming the circuit is meant to be an adder of `x` and `y`
+fn swapped_wires(input: &str) -> String {
+    parse_circuit(input).adder_faults("x", "y", "z").join(",")
 }
 
 #[aoc_tests]
@@ -160,15 +39,96 @@ mod tests {
         assert_eq!(bit_values(&example2, 'z'), 2024);
     }
 
+    /// A 3-bit adder with the outputs of `z01` and `c01` swapped
+    const SWAPPED_ADDER: &str = "x00: 1
+                                 x01: 1
+                                 x02: 0
+                                 y00: 1
+                                 y01: 0
+                                 y02: 1
+
+                                 x00 XOR y00 -> z00
+                                 x00 AND y00 -> c00
+                                 x01 XOR y01 -> s01
+                                 c00 XOR s01 -> c01
+                                 x01 AND y01 -> p01
+                                 s01 AND c00 -> q01
+                                 p01 OR q01 -> z01
+                                 x02 XOR y02 -> s02
+                                 c01 XOR s02 -> z02
+                                 x02 AND y02 -> p02
+                                 s02 AND c01 -> q02
+                                 p02 OR q02 -> z03";
+
+    #[test]
+    fn example_2() {
+        assert_eq!(swapped_wires(SWAPPED_ADDER), "c01,z01");
+        assert_ne!(bit_values(SWAPPED_ADDER, 'z'), 3 + 5);
+        let fixed = SWAPPED_ADDER
+            .replace("-> c01", "-> tmp")
+            .replace("-> z01", "-> c01")
+            .replace("-> tmp", "-> z01");
+        assert_eq!(swapped_wires(&fixed), "");
+        assert_eq!(bit_values(&fixed, 'z'), 3 + 5);
+        assert!(adds(&mut parse_circuit(&fixed)));
+        assert!(!adds(&mut parse_circuit(SWAPPED_ADDER)));
+    }
+
     #[test]
     fn part_1() {
         let input = read_input();
         assert_eq!(bit_values(&input, 'z'), 59336987801432);
     }
 
+    /// Every way of splitting `wires` into pairs
+    fn pairings<'a>(wires: &[&'a str]) -> Vec<Vec<(&'a str, &'a str)>> {
+        let Some((first, rest)) = wires.split_first() else {
+            return vec![vec![]];
+        };
+        (0..rest.len())
+            .flat_map(|i| {
+                let mut others = rest.to_vec();
+                let second = others.remove(i);
+                pairings(&others).into_iter().map(move |mut pairs| {
+                    pairs.push((first, second));
+                    pairs
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the circuit adds a few sample values of `x` and `y` correctly
+    fn adds(circuit: &mut Circuit) -> bool {
+        let bits = circuit.wires().filter(|wire| wire.starts_with('x')).count();
+        let max = (1 << bits) - 1;
+        let samples = [
+            (0, 0),
+            (max, 1),
+            (max, max),
+            (0x5555_5555_5555 & max, 0x3333_3333_3333 & max),
+        ];
+        samples.into_iter().all(|(x, y)| {
+            circuit.set_bus("x", x);
+            circuit.set_bus("y", y);
+            circuit.evaluate().bus("z") == x + y
+        })
+    }
+
     #[test]
-    fn reg() {
+    fn part_2() {
         let input = read_input();
-        assert_eq!(bit_values(&input, 'z'), 59336987801432, "z");
+        let swapped = swapped_wires(&input);
+        let wires: Vec<&str> = swapped.split(',').collect();
+        assert_eq!(wires.len(), 8, "{swapped}");
+        // Swapping the wires back in pairs must give a working adder
+        let repaired = pairings(&wires).into_iter().any(|pairs| {
+            let mut circuit = parse_circuit(&input);
+            pairs
+                .iter()
+                .all(|(a, b)| circuit.swap_outputs(a, b).is_ok())
+                && circuit.adder_faults("x", "y", "z").is_empty()
+                && adds(&mut circuit)
+        });
+        assert!(repaired, "{swapped}");
     }
 }
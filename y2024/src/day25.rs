@@ -1,24 +1,44 @@
+use advent_of_code_common::file::parse::{blocks, grid, ParseError, Parser};
 use advent_of_code_macros::aoc_tests;
-use itertools::{iproduct, Itertools};
+use itertools::iproduct;
 
-fn parse_input(input: &str) -> (Vec<[u8; 5]>, Vec<[u8; 5]>) {
+/// Whether the schematic is a lock (hanging from a full top row) rather than a key (standing on
+/// a full bottom row), and the height of each of its 5 columns
+fn schematic(rows: &[Vec<bool>]) -> Option<(bool, [u8; 5])> {
+    let is_full = |row: &Vec<bool>| row.iter().all(|&filled| filled);
+    let is_lock = is_full(rows.first()?);
+    if rows[0].len() != 5 || (!is_lock && !is_full(rows.last()?)) {
+        return None;
+    }
+    let mut heights = [0; 5];
+    for (x, height) in heights.iter_mut().enumerate() {
+        // The full row at the top or the bottom doesn't count
+        let filled = rows.iter().filter(|row| row[x]).count();
+        *height = u8::try_from(filled - 1).ok()?;
+    }
+    Some((is_lock, heights))
+}
+
+/// The heights of the locks and of the keys
+type Schematics = (Vec<[u8; 5]>, Vec<[u8; 5]>);
+
+fn parse_input(input: &str) -> Result<Schematics, ParseError> {
+    let cell = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    let schematics = grid(cell).try_map("a lock or a key, 5 columns wide", |rows| schematic(&rows));
     let mut locks = Vec::new();
     let mut keys = Vec::new();
-    for mut chunk in &input.lines().chunks(8) {
-        let is_lock = chunk.next().is_some_and(|line| line == "#####");
-        let mut object = [0; 5];
-        for line in chunk.take(5) {
-            for (j, c) in line.chars().enumerate() {
-                object[j] += u8::from(c == '#');
-            }
-        }
+    for (is_lock, heights) in blocks(schematics).parse(input)? {
         if is_lock {
-            locks.push(object);
+            locks.push(heights);
         } else {
-            keys.push(object);
+            keys.push(heights);
         }
     }
-    (locks, keys)
+    Ok((locks, keys))
 }
 
 fn count_fits(locks: &[[u8; 5]], keys: &[[u8; 5]]) -> usize {
@@ -71,14 +91,31 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let (locks, keys) = parse_input(EXAMPLE1);
+        let (locks, keys) = parse_input(EXAMPLE1).unwrap();
         assert_eq!(count_fits(&locks, &keys), 3);
     }
 
+    #[test]
+    fn empty_and_full_columns() {
+        let (locks, keys) = parse_input("#####\n.....\n.....\n\n.....\n#####\n#####").unwrap();
+        assert_eq!(locks, [[0; 5]]);
+        assert_eq!(keys, [[1; 5]]);
+        assert_eq!(count_fits(&locks, &keys), 1);
+    }
+
+    #[test]
+    fn invalid_schematics() {
+        let error = parse_input("#####\n.....\n\n.....\n#....\n.....").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.expected, "a lock or a key, 5 columns wide");
+        assert!(parse_input("####\n....").is_err());
+        assert!(parse_input("#####\n..x..").is_err());
+    }
+
     #[test]
     fn part_1() {
         let input = read_input();
-        let (locks, keys) = parse_input(&input);
+        let (locks, keys) = parse_input(&input).unwrap();
         assert_eq!(locks.last().unwrap(), &[3, 2, 3, 2, 4]);
         assert_eq!(keys.last().unwrap(), &[1, 4, 0, 3, 0]);
         assert_eq!(count_fits(&locks, &keys), 2770);
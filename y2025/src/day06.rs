@@ -1,15 +1,16 @@
-use advent_of_code_common::Solver;
+use advent_of_code_common::{Solver, input::Input};
 
-fn transpose(s: &str) -> Vec<String> {
-    let lines: Vec<Vec<char>> = s.lines().map(|line| line.chars().collect()).collect();
-    let max_len = lines.iter().map(Vec::len).max().unwrap_or(0);
-    let mut result = vec![String::with_capacity(lines.len()); max_len];
-    for line in lines {
-        for (i, &ch) in line.iter().enumerate() {
-            result[i].push(ch);
-        }
-    }
-    result
+/// The columns of the input grid, top to bottom
+fn transpose(input: &Input) -> Vec<String> {
+    let (width, height) = input.dimensions();
+    (0..width)
+        .map(|x| {
+            (0..height)
+                .filter_map(|y| input.get(x, y))
+                .map(char::from)
+                .collect()
+        })
+        .collect()
 }
 
 fn quick_maths(numbers: &[usize], op: char) -> usize {
@@ -52,35 +53,44 @@ impl Solver<'_> for Part1 {
     }
 }
 
+/// Numbers are written top to bottom in columns, and problems are separated by empty columns
+fn cephalopod_maths(input: &Input) -> usize {
+    transpose(input)
+        .chunk_by(|_, b| !b.trim().is_empty())
+        .map(|chunk| {
+            let mut current_op = "";
+            let mut numbers = Vec::with_capacity(chunk.len());
+            for line in chunk {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let n: usize = if line.ends_with('+') || line.ends_with('*') {
+                    let (n, op) = line.split_at(line.len() - 1);
+                    current_op = op;
+                    n.trim().parse().unwrap_or(0)
+                } else {
+                    line.trim().parse().unwrap_or(0)
+                };
+                numbers.push(n);
+            }
+            current_op
+                .chars()
+                .next()
+                .map_or(0, |op| quick_maths(&numbers, op))
+        })
+        .sum()
+}
+
 struct Part2;
-impl Solver<'_> for Part2 {
+impl<'a> Solver<'a> for Part2 {
     type Output = usize;
 
-    fn solve(&self, input: &str) -> Self::Output {
-        transpose(input)
-            .chunk_by(|_, b| !b.trim().is_empty())
-            .map(|chunk| {
-                let mut current_op = "";
-                let mut numbers = Vec::with_capacity(chunk.len());
-                for line in chunk {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
-                    let n: usize = if line.ends_with('+') || line.ends_with('*') {
-                        let (n, op) = line.split_at(line.len() - 1);
-                        current_op = op;
-                        n.trim().parse().unwrap_or(0)
-                    } else {
-                        line.trim().parse().unwrap_or(0)
-                    };
-                    numbers.push(n);
-                }
-                current_op
-                    .chars()
-                    .next()
-                    .map_or(0, |op| quick_maths(&numbers, op))
-            })
-            .sum()
+    fn solve(&self, input: &'a str) -> Self::Output {
+        cephalopod_maths(&Input::new(input))
+    }
+
+    fn solve_input(&self, input: &'a Input<'a>) -> Self::Output {
+        cephalopod_maths(input)
     }
 
     fn file_path(&self) -> std::path::PathBuf {
@@ -18,14 +18,14 @@ impl CellData {
 }
 
 impl TryFrom<char> for CellData {
-    type Error = ();
+    type Error = advent_of_code_common::Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' => Ok(Self::Empty),
             '^' => Ok(Self::Splitter),
             'S' => Ok(Self::Beam(1)),
-            _ => Err(()),
+            _ => Err(Self::Error::invalid_char(value, "one of '.', '^', 'S'")),
         }
     }
 }
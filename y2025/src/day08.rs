@@ -1,139 +1,33 @@
-use advent_of_code_common::Solver;
+use advent_of_code_common::algorithms::disjoint_set::{DisjointSet, kruskal};
+use advent_of_code_common::{Solver, coords::Point3};
 use itertools::Itertools;
-use std::{collections::HashSet, str::FromStr};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct Position {
-    x: usize,
-    y: usize,
-    z: usize,
+fn parse_cubes(input: &str) -> Vec<Point3> {
+    input.lines().filter_map(|line| line.parse().ok()).collect()
 }
 
-impl FromStr for Position {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(',');
-        let x = parts.next().ok_or(())?.parse().map_err(|_| ())?;
-        let y = parts.next().ok_or(())?.parse().map_err(|_| ())?;
-        let z = parts.next().ok_or(())?.parse().map_err(|_| ())?;
-        if parts.next().is_some() {
-            return Err(());
-        }
-        Ok(Self { x, y, z })
-    }
-}
-
-impl Position {
-    const fn euclidean_distance(&self, other: &Self) -> usize {
-        let dx = self.x.abs_diff(other.x);
-        let dy = self.y.abs_diff(other.y);
-        let dz = self.z.abs_diff(other.z);
-        // Turns out that using integer square root is sufficient for this problem
-        (dx * dx + dy * dy + dz * dz).isqrt()
-    }
-}
-
-type Cluster = HashSet<Position>;
-
-enum Inclusion {
-    None,
-    One,
-    Both,
-}
-
-#[derive(Clone)]
-struct Connection {
-    cubes: (Position, Position),
-    distance: usize,
-}
-
-impl Connection {
-    fn is_in_cluster(&self, cluster: &Cluster) -> Inclusion {
-        match (
-            cluster.contains(&self.cubes.0),
-            cluster.contains(&self.cubes.1),
-        ) {
-            (false, false) => Inclusion::None,
-            (true, true) => Inclusion::Both,
-            (true, false) | (false, true) => Inclusion::One,
-        }
-    }
-
-    fn insert_into_cluster(&self, cluster: &mut Cluster) {
-        cluster.insert(self.cubes.0.clone());
-        cluster.insert(self.cubes.1.clone());
-    }
-}
-
-fn sorted_connections(cubes: &[Position]) -> Vec<Connection> {
-    let mut connections = Vec::new();
-    for (a, b) in cubes.iter().tuple_combinations() {
-        connections.push(Connection {
-            cubes: (a.clone(), b.clone()),
-            distance: a.euclidean_distance(b),
-        });
-    }
-    connections.sort_unstable_by_key(|conn| conn.distance);
+/// Every pair of cubes, with the squared distance between them, closest first
+fn sorted_connections(cubes: &[Point3]) -> Vec<(Point3, Point3, isize)> {
+    let mut connections: Vec<_> = cubes
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| (*a, *b, a.squared_distance(b)))
+        .collect();
+    connections.sort_unstable_by_key(|&(_, _, distance)| distance);
     connections
 }
 
-fn build_clusters(input: &str, limit: usize) -> (Vec<Cluster>, Connection) {
-    let cubes: Vec<Position> = input.lines().filter_map(|line| line.parse().ok()).collect();
-    let connections = sorted_connections(&cubes);
-    let limit = limit.min(connections.len());
-    let mut clusters: Vec<Cluster> = Vec::new();
-    'outer: for connection in &connections[..limit] {
-        let mut found = None;
-        for (idx, cluster) in clusters.iter_mut().enumerate() {
-            match connection.is_in_cluster(cluster) {
-                Inclusion::One => {
-                    connection.insert_into_cluster(cluster);
-                    found = Some(idx);
-                    break;
-                }
-                Inclusion::Both => continue 'outer,
-                Inclusion::None => {}
-            }
-        }
-        if let Some(idx) = found {
-            let to_merge: Vec<usize> = clusters[idx + 1..]
-                .iter()
-                .enumerate()
-                .rev()
-                .filter_map(|(other_idx, cluster)| {
-                    if matches!(connection.is_in_cluster(cluster), Inclusion::None) {
-                        None
-                    } else {
-                        Some(other_idx + idx + 1)
-                    }
-                })
-                .collect();
-            for merge_idx in to_merge {
-                let merging_cluster = clusters.remove(merge_idx);
-                clusters[idx].extend(merging_cluster);
-            }
-        } else {
-            let mut new_cluster = Cluster::new();
-            connection.insert_into_cluster(&mut new_cluster);
-            clusters.push(new_cluster);
-        }
-        if clusters.len() == 1 && clusters[0].len() == cubes.len() {
-            return (clusters, connection.clone());
-        }
-    }
-    (clusters, connections[limit - 1].clone())
-}
-
 struct Part1(usize);
 impl Solver<'_> for Part1 {
     type Output = usize;
 
     fn solve(&self, input: &str) -> Self::Output {
-        let (clusters, _) = build_clusters(input, self.0);
-        let mut sizes: Vec<usize> = clusters.iter().map(Cluster::len).collect();
-        sizes.sort_unstable();
-        sizes.iter().rev().take(3).product()
+        let cubes = parse_cubes(input);
+        let mut clusters: DisjointSet<Point3> = cubes.iter().copied().collect();
+        for (a, b, _) in sorted_connections(&cubes).iter().take(self.0) {
+            clusters.union(a, b);
+        }
+        clusters.component_sizes().iter().take(3).product()
     }
 
     fn file_path(&self) -> std::path::PathBuf {
@@ -146,8 +40,13 @@ impl Solver<'_> for Part2 {
     type Output = usize;
 
     fn solve(&self, input: &str) -> Self::Output {
-        let (_, last) = build_clusters(input, usize::MAX);
-        last.cubes.0.x * last.cubes.1.x
+        let cubes = parse_cubes(input);
+        let connections = sorted_connections(&cubes);
+        // The connection that joins the last two clusters
+        let (a, b, _) = kruskal(cubes, connections)
+            .pop()
+            .expect("at least two cubes");
+        usize::try_from(a.x() * b.x()).expect("positive coordinates")
     }
 
     fn file_path(&self) -> std::path::PathBuf {
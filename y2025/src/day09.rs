@@ -1,5 +1,6 @@
 use advent_of_code_common::Solver;
 use advent_of_code_common::coords::Point;
+use advent_of_code_common::geometry::Polygon;
 use itertools::Itertools;
 
 fn parse_points(input: &str) -> Vec<Point> {
@@ -10,6 +11,21 @@ const fn area(a: &Point, b: &Point) -> usize {
     (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
 }
 
+/// Whether no edge of the (axis-aligned) loop of `points` passes through the inside of the
+/// rectangle between `a` and `b`. The rectangle can only be in the polygon if that holds, and
+/// checking it is much cheaper than [`Polygon::contains_rectangle`].
+fn no_edge_inside(points: &[Point], a: &Point, b: &Point) -> bool {
+    let (min_x, max_x) = (a.x.min(b.x), a.x.max(b.x));
+    let (min_y, max_y) = (a.y.min(b.y), a.y.max(b.y));
+    points.iter().circular_tuple_windows().all(|(c, d)| {
+        let to_the_left = c.x <= min_x && d.x <= min_x;
+        let to_the_right = c.x >= max_x && d.x >= max_x;
+        let above = c.y <= min_y && d.y <= min_y;
+        let below = c.y >= max_y && d.y >= max_y;
+        to_the_left || to_the_right || above || below
+    })
+}
+
 struct Part1(usize);
 impl Solver<'_> for Part1 {
     type Output = usize;
@@ -34,28 +50,15 @@ impl Solver<'_> for Part2 {
 
     fn solve(&self, input: &str) -> Self::Output {
         let points = parse_points(input);
+        let polygon = Polygon::new(points.clone());
         points
             .iter()
             .tuple_combinations()
             .map(|(a, b)| (a, b, area(a, b)))
             .sorted_by_key(|(_, _, area)| *area)
             .rev()
-            .find(|(a, b, _)| {
-                let min_x = a.x.min(b.x);
-                let max_x = a.x.max(b.x);
-                let min_y = a.y.min(b.y);
-                let max_y = a.y.max(b.y);
-                for (c, d) in points.iter().circular_tuple_windows() {
-                    let to_the_left = c.x <= min_x && d.x <= min_x;
-                    let to_the_right = c.x >= max_x && d.x >= max_x;
-                    let above = c.y <= min_y && d.y <= min_y;
-                    let below = c.y >= max_y && d.y >= max_y;
-                    if !(to_the_left || to_the_right || above || below) {
-                        return false;
-                    }
-                }
-                true
-            })
+            .filter(|(a, b, _)| no_edge_inside(&points, a, b))
+            .find(|(a, b, _)| polygon.contains_rectangle(a, b))
             .map_or(0, |(_, _, area)| area)
     }
 
@@ -1,4 +1,5 @@
 use advent_of_code_common::Solver;
+use advent_of_code_common::math::linear::Matrix;
 use itertools::Itertools;
 use std::collections::{HashSet, VecDeque};
 
@@ -75,8 +76,28 @@ impl Machine {
         None
     }
 
+    /// Each counter's joltage is the number of presses of the buttons wired to it, so this is the
+    /// smallest non-negative integer solution of `buttons * presses = jolts`
     fn min_clicks_jolts(&self) -> Option<usize> {
-        todo!()
+        let wiring = Matrix::from_rows((0..self.jolts.len()).map(|counter| {
+            self.buttons
+                .iter()
+                .map(move |button| i128::from(button.contains(&counter)))
+        }));
+        let jolts = self
+            .jolts
+            .iter()
+            .map(|&j| i128::try_from(j).ok())
+            .collect::<Option<Vec<i128>>>()?;
+        // A button can't be pressed more often than the lowest counter it increments
+        let bounds: Vec<i128> = self
+            .buttons
+            .iter()
+            .map(|button| button.iter().map(|&i| jolts[i]).min().unwrap_or(0))
+            .collect();
+        let costs = vec![1; self.buttons.len()];
+        let (clicks, _) = wiring.min_cost_integer_solution(&jolts, &bounds, &costs)?;
+        usize::try_from(clicks).ok()
     }
 }
 
@@ -126,7 +147,25 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "not yet implemented"]
+    fn min_clicks_jolts() {
+        let examples = [
+            ("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}", 10),
+            (
+                "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}",
+                12,
+            ),
+            (
+                "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+                11,
+            ),
+        ];
+        for (line, expected) in examples {
+            let machine: Machine = line.parse().unwrap();
+            assert_eq!(machine.min_clicks_jolts(), Some(expected), "{line}");
+        }
+    }
+
+    #[test]
     fn part_2() {
         expect_solution!(Part2, 0, 33);
         // expect_solution!(Part2, 1, 1562459680);